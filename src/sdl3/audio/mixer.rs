@@ -0,0 +1,464 @@
+//! A small software mixer built on audio streams.
+//!
+//! Every [`Voice`] plays on one of a fixed pool of [`AudioStream`]s that are all
+//! bound to a single playback device, so SDL does the actual mixing. Voice
+//! parameters (gain, pan, looping, fades) are applied while the stream pulls data,
+//! so changes take effect within one device buffer.
+//!
+//! For tests and offline rendering, [`Mixer::offline`] creates the same streams
+//! without binding them to a device; [`Mixer::render`] then pulls and sums them
+//! deterministically.
+//!
+//! # Example
+//! ```no_run
+//! use sdl3::audio::mixer::{Mixer, PlayOptions};
+//! use sdl3::audio::AudioSpecWAV;
+//! use std::time::Duration;
+//!
+//! let sdl_context = sdl3::init().unwrap();
+//! let audio_subsystem = sdl_context.audio().unwrap();
+//!
+//! let mut mixer = Mixer::open(&audio_subsystem, None, 16).unwrap();
+//! let wav = AudioSpecWAV::load_wav("./assets/sine.wav").unwrap();
+//! let sound = mixer.load_wav(&wav).unwrap();
+//!
+//! let voice = mixer
+//!     .play_with(
+//!         &sound,
+//!         &PlayOptions {
+//!             looping: true,
+//!             fade_in: Some(Duration::from_millis(500)),
+//!             ..PlayOptions::default()
+//!         },
+//!     )
+//!     .unwrap();
+//! voice.set_pan(-0.5);
+//!
+//! std::thread::sleep(Duration::from_secs(2));
+//! voice.fade_out(Duration::from_secs(1));
+//! std::thread::sleep(Duration::from_secs(1));
+//! ```
+
+use super::{convert_samples, AudioDevice, AudioFormat, AudioSpec, AudioSpecWAV, AudioStream};
+use crate::AudioSubsystem;
+use crate::Error;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// The mixer always works in interleaved stereo `f32`.
+const CHANNELS: usize = 2;
+const FRAME_SIZE: usize = CHANNELS * std::mem::size_of::<f32>();
+const DEFAULT_FREQ: i32 = 48_000;
+
+/// Decoded audio, converted to the format of the [`Mixer`] that loaded it.
+///
+/// Cloning a `Sound` is cheap; the samples are shared.
+#[derive(Clone, Debug)]
+pub struct Sound {
+    samples: Arc<[f32]>,
+}
+
+impl Sound {
+    /// The length of the sound in frames.
+    pub fn frames(&self) -> usize {
+        self.samples.len() / CHANNELS
+    }
+}
+
+/// How a sound starts playing. See [`Mixer::play_with`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayOptions {
+    /// Volume multiplier, `1.0` meaning unchanged.
+    pub gain: f32,
+    /// Stereo balance from `-1.0` (left only) to `1.0` (right only).
+    pub pan: f32,
+    /// Restart from the beginning when the end is reached.
+    pub looping: bool,
+    /// Ramp the volume up from silence over this duration.
+    pub fade_in: Option<Duration>,
+}
+
+impl Default for PlayOptions {
+    fn default() -> Self {
+        PlayOptions {
+            gain: 1.0,
+            pan: 0.0,
+            looping: false,
+            fade_in: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Fade {
+    from: f32,
+    to: f32,
+    frames: usize,
+    elapsed: usize,
+    stop_at_end: bool,
+}
+
+#[derive(Debug)]
+struct VoiceState {
+    generation: u64,
+    freq: i32,
+    samples: Arc<[f32]>,
+    position: usize,
+    playing: bool,
+    looping: bool,
+    gain: f32,
+    pan: f32,
+    fade_level: f32,
+    fade: Option<Fade>,
+}
+
+impl VoiceState {
+    fn idle(freq: i32) -> VoiceState {
+        VoiceState {
+            generation: 0,
+            freq,
+            samples: Arc::from(Vec::new()),
+            position: 0,
+            playing: false,
+            looping: false,
+            gain: 1.0,
+            pan: 0.0,
+            fade_level: 1.0,
+            fade: None,
+        }
+    }
+
+    fn duration_to_frames(&self, duration: Duration) -> usize {
+        (duration.as_secs_f64() * self.freq as f64).round() as usize
+    }
+
+    fn start_fade(&mut self, to: f32, duration: Duration, stop_at_end: bool) {
+        let frames = self.duration_to_frames(duration);
+        if frames == 0 {
+            self.fade_level = to;
+            self.fade = None;
+            if stop_at_end {
+                self.playing = false;
+            }
+        } else {
+            self.fade = Some(Fade {
+                from: self.fade_level,
+                to,
+                frames,
+                elapsed: 0,
+                stop_at_end,
+            });
+        }
+    }
+
+    /// Advances the fade by one frame and returns the level to apply to it.
+    /// Returns `None` once a fade-out has finished.
+    fn next_fade_level(&mut self) -> Option<f32> {
+        let Some(fade) = self.fade.as_mut() else {
+            return Some(self.fade_level);
+        };
+        if fade.elapsed >= fade.frames {
+            let stop = fade.stop_at_end;
+            self.fade_level = fade.to;
+            self.fade = None;
+            return if stop { None } else { Some(self.fade_level) };
+        }
+        let t = fade.elapsed as f32 / fade.frames as f32;
+        fade.elapsed += 1;
+        self.fade_level = fade.from + (fade.to - fade.from) * t;
+        Some(self.fade_level)
+    }
+
+    /// Renders up to `out.len() / 2` stereo frames and returns how many were written.
+    /// Fewer frames are written once the voice stops.
+    fn render(&mut self, out: &mut [f32]) -> usize {
+        let frames = self.samples.len() / CHANNELS;
+        let left_pan = (1.0 - self.pan).min(1.0);
+        let right_pan = (1.0 + self.pan).min(1.0);
+
+        let mut written = 0;
+        for frame in out.chunks_exact_mut(CHANNELS) {
+            if !self.playing {
+                break;
+            }
+            if self.position >= frames {
+                if self.looping && frames > 0 {
+                    self.position = 0;
+                } else {
+                    self.playing = false;
+                    break;
+                }
+            }
+            let Some(level) = self.next_fade_level() else {
+                self.playing = false;
+                break;
+            };
+            let gain = self.gain * level;
+            let sample = &self.samples[self.position * CHANNELS..][..CHANNELS];
+            frame[0] = sample[0] * gain * left_pan;
+            frame[1] = sample[1] * gain * right_pan;
+            self.position += 1;
+            written += 1;
+        }
+        written
+    }
+}
+
+/// A handle to a sound playing on a [`Mixer`].
+///
+/// Voices are recycled once they stop; a handle to a voice that has been reused
+/// for another sound does nothing.
+#[derive(Clone, Debug)]
+pub struct Voice {
+    state: Arc<Mutex<VoiceState>>,
+    generation: u64,
+}
+
+impl Voice {
+    /// Runs `f` on the voice state if this handle is still current.
+    fn with_state<R>(&self, f: impl FnOnce(&mut VoiceState) -> R) -> Option<R> {
+        let mut state = lock(&self.state);
+        if state.generation == self.generation {
+            Some(f(&mut state))
+        } else {
+            None
+        }
+    }
+
+    /// Whether the voice is still playing.
+    pub fn is_playing(&self) -> bool {
+        self.with_state(|state| state.playing).unwrap_or(false)
+    }
+
+    /// Sets the volume multiplier, `1.0` meaning unchanged.
+    pub fn set_gain(&self, gain: f32) {
+        self.with_state(|state| state.gain = gain.max(0.0));
+    }
+
+    /// Sets the stereo balance from `-1.0` (left only) to `1.0` (right only).
+    pub fn set_pan(&self, pan: f32) {
+        self.with_state(|state| state.pan = pan.clamp(-1.0, 1.0));
+    }
+
+    /// Sets whether the voice restarts from the beginning when it reaches the end.
+    pub fn set_looping(&self, looping: bool) {
+        self.with_state(|state| state.looping = looping);
+    }
+
+    /// Stops the voice immediately.
+    pub fn stop(&self) {
+        self.with_state(|state| state.playing = false);
+    }
+
+    /// Ramps the volume up from silence over `duration`.
+    pub fn fade_in(&self, duration: Duration) {
+        self.with_state(|state| {
+            state.fade_level = 0.0;
+            state.start_fade(1.0, duration, false);
+        });
+    }
+
+    /// Ramps the volume down to silence over `duration`, then stops the voice.
+    pub fn fade_out(&self, duration: Duration) {
+        self.with_state(|state| state.start_fade(0.0, duration, true));
+    }
+}
+
+struct Slot {
+    stream: AudioStream,
+    state: Arc<Mutex<VoiceState>>,
+}
+
+/// Plays [`Sound`]s on a fixed pool of voices. See the [module documentation](self).
+pub struct Mixer {
+    // Streams are declared before the device so they are destroyed first.
+    slots: Vec<Slot>,
+    device: Option<AudioDevice>,
+    spec: AudioSpec,
+    _audio: AudioSubsystem,
+}
+
+impl Mixer {
+    /// Opens the default playback device and binds `max_voices` streams to it.
+    ///
+    /// `freq` selects the mixing rate; `None` uses the device's own rate.
+    pub fn open(
+        audio: &AudioSubsystem,
+        freq: Option<i32>,
+        max_voices: usize,
+    ) -> Result<Mixer, Error> {
+        let device_spec = AudioSpec::new(freq, Some(CHANNELS as i32), Some(AudioFormat::f32_sys()));
+        let device = AudioDevice::open_playback(audio, None, &device_spec)?;
+        let freq = match freq {
+            Some(freq) => freq,
+            None => device.id().format()?.0.freq.unwrap_or(DEFAULT_FREQ),
+        };
+
+        let mut mixer = Mixer::new(audio, freq, max_voices)?;
+        let streams: Vec<&AudioStream> = mixer.slots.iter().map(|slot| &slot.stream).collect();
+        device.bind_streams(&streams)?;
+        mixer.device = Some(device);
+        Ok(mixer)
+    }
+
+    /// Creates a mixer that is not attached to any device. Audio is only produced by
+    /// calling [`Mixer::render`], which makes the output fully deterministic.
+    ///
+    /// The audio subsystem still needs to be initialized, for example with the
+    /// `dummy` driver.
+    pub fn offline(audio: &AudioSubsystem, freq: i32, max_voices: usize) -> Result<Mixer, Error> {
+        Mixer::new(audio, freq, max_voices)
+    }
+
+    fn new(audio: &AudioSubsystem, freq: i32, max_voices: usize) -> Result<Mixer, Error> {
+        let spec = AudioSpec::new(
+            Some(freq),
+            Some(CHANNELS as i32),
+            Some(AudioFormat::f32_sys()),
+        );
+        let mut slots = Vec::with_capacity(max_voices);
+        for _ in 0..max_voices {
+            let mut stream = AudioStream::new(Some(&spec), Some(&spec))?;
+            let state = Arc::new(Mutex::new(VoiceState::idle(freq)));
+            let callback_state = state.clone();
+            stream.set_get_callback(move |stream, additional, _| {
+                if additional <= 0 {
+                    return;
+                }
+                let frames = (additional as usize).div_ceil(FRAME_SIZE);
+                let mut buffer = vec![0.0f32; frames * CHANNELS];
+                let written = lock(&callback_state).render(&mut buffer);
+                if written > 0 {
+                    let _ = stream.put_samples(&buffer[..written * CHANNELS]);
+                }
+            })?;
+            slots.push(Slot { stream, state });
+        }
+        Ok(Mixer {
+            slots,
+            device: None,
+            spec,
+            _audio: audio.clone(),
+        })
+    }
+
+    /// The format the mixer works in: interleaved stereo `f32` at the mixing rate.
+    pub fn spec(&self) -> &AudioSpec {
+        &self.spec
+    }
+
+    /// The playback device, unless this is an offline mixer.
+    pub fn device(&self) -> Option<&AudioDevice> {
+        self.device.as_ref()
+    }
+
+    /// Converts WAVE data into a sound for this mixer.
+    pub fn load_wav(&self, wav: &AudioSpecWAV) -> Result<Sound, Error> {
+        let spec = AudioSpec::new(Some(wav.freq), Some(wav.channels as i32), Some(wav.format));
+        self.load_samples(&spec, wav.buffer())
+    }
+
+    /// Converts interleaved audio data in the format `spec` into a sound for this
+    /// mixer.
+    pub fn load_samples(&self, spec: &AudioSpec, data: &[u8]) -> Result<Sound, Error> {
+        let converted = convert_samples(spec, data, &self.spec)?;
+        let samples: Vec<f32> = converted
+            .chunks_exact(std::mem::size_of::<f32>())
+            .map(|bytes| f32::from_ne_bytes(bytes.try_into().unwrap()))
+            .collect();
+        Ok(Sound {
+            samples: samples.into(),
+        })
+    }
+
+    /// The number of voices currently playing.
+    pub fn playing_voices(&self) -> usize {
+        self.slots
+            .iter()
+            .filter(|slot| lock(&slot.state).playing)
+            .count()
+    }
+
+    /// Plays `sound` with the default [`PlayOptions`].
+    pub fn play(&mut self, sound: &Sound) -> Result<Voice, Error> {
+        self.play_with(sound, &PlayOptions::default())
+    }
+
+    /// Plays `sound` on a free voice. Fails if all voices are busy.
+    pub fn play_with(&mut self, sound: &Sound, options: &PlayOptions) -> Result<Voice, Error> {
+        let slot = self
+            .slots
+            .iter()
+            .find(|slot| !lock(&slot.state).playing)
+            .ok_or_else(|| Error("All mixer voices are in use".to_owned()))?;
+
+        // Drop anything left over from the previous sound on this voice.
+        slot.stream.clear()?;
+
+        let mut state = lock(&slot.state);
+        state.generation += 1;
+        state.samples = sound.samples.clone();
+        state.position = 0;
+        state.looping = options.looping;
+        state.gain = options.gain.max(0.0);
+        state.pan = options.pan.clamp(-1.0, 1.0);
+        state.fade = None;
+        match options.fade_in {
+            Some(duration) => {
+                state.fade_level = 0.0;
+                state.start_fade(1.0, duration, false);
+            }
+            None => state.fade_level = 1.0,
+        }
+        state.playing = true;
+
+        Ok(Voice {
+            state: slot.state.clone(),
+            generation: state.generation,
+        })
+    }
+
+    /// Stops every voice.
+    pub fn stop_all(&mut self) {
+        for slot in &self.slots {
+            lock(&slot.state).playing = false;
+        }
+    }
+
+    /// Renders `frames` stereo frames of all playing voices into a new buffer of
+    /// interleaved samples, clipped to `-1.0..=1.0`.
+    ///
+    /// This is meant for offline mixers; on a mixer bound to a device it competes
+    /// with the device for the voices' audio.
+    pub fn render(&mut self, frames: usize) -> Result<Vec<f32>, Error> {
+        let mut mix = vec![0.0f32; frames * CHANNELS];
+        let mut buffer = vec![0.0f32; frames * CHANNELS];
+        for slot in &self.slots {
+            // The device callback locks the stream before the state, so never query
+            // the stream while holding the state lock.
+            let playing = lock(&slot.state).playing;
+            if !playing && slot.stream.available_bytes()? == 0 {
+                continue;
+            }
+            buffer.fill(0.0);
+            let mut read = 0;
+            while read < buffer.len() {
+                let n = slot.stream.get_samples(&mut buffer[read..])?;
+                if n == 0 {
+                    break;
+                }
+                read += n;
+            }
+            for (out, sample) in mix.iter_mut().zip(&buffer[..read]) {
+                *out += sample;
+            }
+        }
+        mix.iter_mut()
+            .for_each(|sample| *sample = sample.clamp(-1.0, 1.0));
+        Ok(mix)
+    }
+}
+
+fn lock(state: &Mutex<VoiceState>) -> MutexGuard<'_, VoiceState> {
+    state.lock().unwrap()
+}
@@ -0,0 +1,2529 @@
+//! Audio Functions
+//!
+//! # Example
+//! ```no_run
+//! use sdl3::audio::{AudioCallback, AudioFormat, AudioSpec};
+//! use std::time::Duration;//!
+//!
+//! use sdl3::sys;
+//!
+//! struct SquareWave {
+//!     phase_inc: f32,
+//!     phase: f32,
+//!     volume: f32
+//! }
+//!
+//! impl AudioCallback<f32> for SquareWave {
+//!     fn callback(&mut self, out: &mut [f32]) {
+//!         // Generate a square wave
+//!         for x in out.iter_mut() {
+//!             *x = if self.phase <= 0.5 {
+//!                 self.volume
+//!             } else {
+//!                 -self.volume
+//!             };
+//!             self.phase = (self.phase + self.phase_inc) % 1.0;
+//!         }
+//!     }
+//! }
+//!
+//! let sdl_context = sdl3::init().unwrap();
+//! let audio_subsystem = sdl_context.audio().unwrap();
+//!
+//! let desired_spec = AudioSpec {
+//!     freq: Some(44100),
+//!     channels: Some(1),  // mono
+//!     format: Some(AudioFormat::S16BE) // signed 16 bit samples
+//! };
+//!
+//! let device = audio_subsystem.open_playback_stream(&desired_spec, |spec| {
+//!     // initialize the audio callback
+//!     SquareWave {
+//!         phase_inc: 440.0 / spec.freq as f32,
+//!         phase: 0.0,
+//!         volume: 0.25
+//!     }
+//! }).unwrap();
+//!
+//! // Start playback
+//! device.resume().expect("Failed to start playback");
+//!
+//! // Play for 2 seconds
+//! std::thread::sleep(Duration::from_millis(2000));
+//! ```
+
+use crate::get_error;
+use crate::iostream::IOStream;
+use crate::sys;
+use crate::AudioSubsystem;
+use crate::Error;
+use libc::c_void;
+use std::convert::TryInto;
+use std::ffi::{c_int, CStr};
+use std::fmt;
+use std::fmt::{Debug, Display};
+use std::io::{self, Read};
+use std::marker::PhantomData;
+use std::path::Path;
+use sys::audio::{SDL_AUDIO_DEVICE_DEFAULT_PLAYBACK, SDL_AUDIO_DEVICE_DEFAULT_RECORDING};
+use sys::stdinc::SDL_free;
+
+pub mod mixer;
+mod recorder;
+pub use self::recorder::{AudioRecorder, ChannelLevel, RecordableSample, RecorderState};
+
+impl AudioSubsystem {
+    /// Enumerate audio playback devices.
+    #[doc(alias = "SDL_GetAudioPlaybackDevices")]
+    pub fn audio_playback_device_ids(&self) -> Result<Vec<AudioDeviceID>, Error> {
+        unsafe {
+            self.audio_device_ids(|num_devices| {
+                sys::audio::SDL_GetAudioPlaybackDevices(num_devices)
+            })
+        }
+    }
+
+    /// Enumerate audio recording devices.
+    #[doc(alias = "SDL_GetAudioRecordingDevices")]
+    pub fn audio_recording_device_ids(&self) -> Result<Vec<AudioDeviceID>, Error> {
+        self.audio_device_ids(|num_devices| unsafe {
+            sys::audio::SDL_GetAudioRecordingDevices(num_devices)
+        })
+    }
+
+    fn audio_device_ids<F>(&self, get_devices: F) -> Result<Vec<AudioDeviceID>, Error>
+    where
+        F: FnOnce(&mut i32) -> *mut sys::audio::SDL_AudioDeviceID,
+    {
+        let mut num_devices: i32 = 0;
+        let devices = unsafe { get_devices(&mut num_devices) };
+        if devices.is_null() {
+            return Err(get_error());
+        }
+
+        let mut ret = Vec::new();
+        for i in 0..num_devices {
+            let instance_id = unsafe { *devices.offset(i as isize) };
+            ret.push(AudioDeviceID::Device(instance_id));
+        }
+
+        unsafe { SDL_free(devices as *mut c_void) };
+        Ok(ret)
+    }
+    /// Open a default playback device with the specified audio spec.
+    pub fn open_playback_device(&self, spec: &AudioSpec) -> Result<AudioDevice, Error> {
+        self.open_device(SDL_AUDIO_DEVICE_DEFAULT_PLAYBACK, spec)
+    }
+
+    /// Open a default recording device with the specified audio spec.
+    pub fn open_recording_device(&self, spec: &AudioSpec) -> Result<AudioDevice, Error> {
+        self.open_device(SDL_AUDIO_DEVICE_DEFAULT_RECORDING, spec)
+    }
+
+    pub fn default_playback_device(&self) -> AudioDevice {
+        AudioDevice::new(AudioDeviceID::Device(SDL_AUDIO_DEVICE_DEFAULT_PLAYBACK))
+    }
+
+    pub fn default_recording_device(&self) -> AudioDevice {
+        AudioDevice::new(AudioDeviceID::Device(SDL_AUDIO_DEVICE_DEFAULT_RECORDING))
+    }
+
+    /// General method to open a device by ID.
+    fn open_device(
+        &self,
+        device_id: sys::audio::SDL_AudioDeviceID,
+        spec: &AudioSpec,
+    ) -> Result<AudioDevice, Error> {
+        let sdl_spec: sys::audio::SDL_AudioSpec = spec.clone().into();
+        let device = unsafe { sys::audio::SDL_OpenAudioDevice(device_id, &sdl_spec) };
+        if device == 0 {
+            Err(get_error())
+        } else {
+            Ok(AudioDevice::new(AudioDeviceID::Device(device)))
+        }
+    }
+
+    pub fn open_playback_stream_with_callback<CB, Channel>(
+        &self,
+        device: &AudioDevice,
+        spec: &AudioSpec,
+        callback: CB,
+    ) -> Result<AudioStreamWithCallback<CB>, Error>
+    where
+        CB: AudioCallback<Channel>,
+        Channel: AudioFormatNum + 'static,
+    {
+        device.open_playback_stream_with_callback(spec, callback)
+    }
+
+    pub fn open_playback_stream<CB, Channel>(
+        &self,
+        spec: &AudioSpec,
+        callback: CB,
+    ) -> Result<AudioStreamWithCallback<CB>, Error>
+    where
+        CB: AudioCallback<Channel>,
+        Channel: AudioFormatNum + 'static,
+    {
+        let device = AudioDevice::open_playback(self, None, spec)?;
+        device.open_playback_stream_with_callback(spec, callback)
+    }
+
+    pub fn open_recording_stream<CB, Channel>(
+        &self,
+        spec: &AudioSpec,
+        callback: CB,
+    ) -> Result<AudioStreamWithCallback<CB>, Error>
+    where
+        CB: AudioCallback<Channel>,
+        Channel: AudioFormatNum + 'static,
+    {
+        let device = AudioDevice::open_recording(self, None, spec)?;
+        device.open_playback_stream_with_callback(spec, callback)
+    }
+
+    #[doc(alias = "SDL_GetCurrentAudioDriver")]
+    pub fn current_audio_driver(&self) -> &'static str {
+        unsafe {
+            let buf = sys::audio::SDL_GetCurrentAudioDriver();
+            assert!(!buf.is_null());
+
+            CStr::from_ptr(buf as *const _).to_str().unwrap()
+        }
+    }
+
+    #[doc(alias = "SDL_GetAudioDeviceName")]
+    pub fn audio_playback_device_name(&self, index: u32) -> Result<String, Error> {
+        unsafe {
+            let dev_name = sys::audio::SDL_GetAudioDeviceName(index);
+            if dev_name.is_null() {
+                Err(get_error())
+            } else {
+                let cstr = CStr::from_ptr(dev_name as *const _);
+                Ok(cstr.to_str().unwrap().to_owned())
+            }
+        }
+    }
+
+    #[doc(alias = "SDL_GetAudioDeviceName")]
+    pub fn audio_recording_device_name(&self, index: u32) -> Result<String, Error> {
+        unsafe {
+            let dev_name = sys::audio::SDL_GetAudioDeviceName(index);
+            if dev_name.is_null() {
+                Err(get_error())
+            } else {
+                let cstr = CStr::from_ptr(dev_name as *const _);
+                Ok(cstr.to_str().unwrap().to_owned())
+            }
+        }
+    }
+}
+
+#[repr(u32)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum AudioFormat {
+    UNKNOWN = sys::audio::SDL_AUDIO_UNKNOWN.0,
+
+    /// Unsigned 8-bit samples
+    U8 = sys::audio::SDL_AUDIO_U8.0,
+    /// Signed 8-bit samples
+    S8 = sys::audio::SDL_AUDIO_S8.0,
+    /// Signed 16-bit samples, little-endian
+    S16LE = sys::audio::SDL_AUDIO_S16LE.0,
+    /// Signed 16-bit samples, big-endian
+    S16BE = sys::audio::SDL_AUDIO_S16BE.0,
+    /// Signed 32-bit samples, little-endian
+    S32LE = sys::audio::SDL_AUDIO_S32LE.0,
+    /// Signed 32-bit samples, big-endian
+    S32BE = sys::audio::SDL_AUDIO_S32BE.0,
+    /// 32-bit floating point samples, little-endian
+    F32LE = sys::audio::SDL_AUDIO_F32LE.0,
+    /// 32-bit floating point samples, big-endian
+    F32BE = sys::audio::SDL_AUDIO_F32BE.0,
+}
+
+impl AudioFormat {
+    fn from_ll(raw: sys::audio::SDL_AudioFormat) -> Option<AudioFormat> {
+        match raw {
+            sys::audio::SDL_AUDIO_UNKNOWN => Some(AudioFormat::UNKNOWN),
+            sys::audio::SDL_AUDIO_U8 => Some(AudioFormat::U8),
+            sys::audio::SDL_AUDIO_S8 => Some(AudioFormat::S8),
+            sys::audio::SDL_AUDIO_S16LE => Some(AudioFormat::S16LE),
+            sys::audio::SDL_AUDIO_S16BE => Some(AudioFormat::S16BE),
+            sys::audio::SDL_AUDIO_S32LE => Some(AudioFormat::S32LE),
+            sys::audio::SDL_AUDIO_S32BE => Some(AudioFormat::S32BE),
+            sys::audio::SDL_AUDIO_F32LE => Some(AudioFormat::F32LE),
+            sys::audio::SDL_AUDIO_F32BE => Some(AudioFormat::F32BE),
+            _ => None,
+        }
+    }
+
+    #[doc(alias = "SDL_AudioFormat")]
+    fn to_ll(self) -> sys::audio::SDL_AudioFormat {
+        self.into()
+    }
+
+    /// The size of one sample in bits.
+    #[doc(alias = "SDL_AUDIO_BITSIZE")]
+    pub fn bit_size(self) -> u32 {
+        sys::audio::SDL_AUDIO_BITSIZE(self.to_ll())
+    }
+
+    /// The size of one sample in bytes.
+    #[doc(alias = "SDL_AUDIO_BYTESIZE")]
+    pub fn byte_size(self) -> usize {
+        sys::audio::SDL_AUDIO_BYTESIZE(self.to_ll()) as usize
+    }
+
+    #[doc(alias = "SDL_AUDIO_ISFLOAT")]
+    pub fn is_float(self) -> bool {
+        sys::audio::SDL_AUDIO_ISFLOAT(self.to_ll())
+    }
+
+    #[doc(alias = "SDL_AUDIO_ISBIGENDIAN")]
+    pub fn is_big_endian(self) -> bool {
+        sys::audio::SDL_AUDIO_ISBIGENDIAN(self.to_ll())
+    }
+
+    #[doc(alias = "SDL_AUDIO_ISSIGNED")]
+    pub fn is_signed(self) -> bool {
+        sys::audio::SDL_AUDIO_ISSIGNED(self.to_ll())
+    }
+
+    /// The byte value that makes up a buffer of silence in this format,
+    /// `0x80` for [`AudioFormat::U8`] and `0x00` for everything else.
+    #[doc(alias = "SDL_GetSilenceValueForFormat")]
+    pub fn silence_value(self) -> u8 {
+        unsafe { sys::audio::SDL_GetSilenceValueForFormat(self.to_ll()) as u8 }
+    }
+}
+
+impl From<AudioFormat> for sys::audio::SDL_AudioFormat {
+    fn from(format: AudioFormat) -> sys::audio::SDL_AudioFormat {
+        match format {
+            AudioFormat::UNKNOWN => sys::audio::SDL_AUDIO_UNKNOWN,
+            AudioFormat::U8 => sys::audio::SDL_AUDIO_U8,
+            AudioFormat::S8 => sys::audio::SDL_AUDIO_S8,
+            AudioFormat::S16LE => sys::audio::SDL_AUDIO_S16LE,
+            AudioFormat::S16BE => sys::audio::SDL_AUDIO_S16BE,
+            AudioFormat::S32LE => sys::audio::SDL_AUDIO_S32LE,
+            AudioFormat::S32BE => sys::audio::SDL_AUDIO_S32BE,
+            AudioFormat::F32LE => sys::audio::SDL_AUDIO_F32LE,
+            AudioFormat::F32BE => sys::audio::SDL_AUDIO_F32BE,
+        }
+    }
+}
+
+#[cfg(target_endian = "little")]
+impl AudioFormat {
+    /// Signed 16-bit samples, native endian
+    #[inline]
+    pub const fn s16_sys() -> AudioFormat {
+        AudioFormat::S16LE
+    }
+    /// Signed 32-bit samples, native endian
+    #[inline]
+    pub const fn s32_sys() -> AudioFormat {
+        AudioFormat::S32LE
+    }
+    /// 32-bit floating point samples, native endian
+    #[inline]
+    pub const fn f32_sys() -> AudioFormat {
+        AudioFormat::F32LE
+    }
+}
+
+#[cfg(target_endian = "big")]
+impl AudioFormat {
+    /// Signed 16-bit samples, native endian
+    #[inline]
+    pub const fn s16_sys() -> AudioFormat {
+        AudioFormat::S16MSB
+    }
+    /// Signed 32-bit samples, native endian
+    #[inline]
+    pub const fn s32_sys() -> AudioFormat {
+        AudioFormat::S32MSB
+    }
+    /// 32-bit floating point samples, native endian
+    #[inline]
+    pub const fn f32_sys() -> AudioFormat {
+        AudioFormat::F32MSB
+    }
+}
+
+#[doc(alias = "SDL_GetAudioDriver")]
+#[derive(Copy, Clone)]
+pub struct DriverIterator {
+    length: i32,
+    index: i32,
+}
+
+impl Iterator for DriverIterator {
+    type Item = &'static str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'static str> {
+        if self.index >= self.length {
+            None
+        } else {
+            unsafe {
+                let buf = sys::audio::SDL_GetAudioDriver(self.index);
+                assert!(!buf.is_null());
+                self.index += 1;
+
+                Some(CStr::from_ptr(buf as *const _).to_str().unwrap())
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let l = self.length as usize;
+        (l, Some(l))
+    }
+}
+
+impl ExactSizeIterator for DriverIterator {}
+
+/// Gets an iterator of all audio drivers compiled into the SDL2 library.
+#[doc(alias = "SDL_GetAudioDriver")]
+#[inline]
+pub fn drivers() -> DriverIterator {
+    // This function is thread-safe and doesn't require the audio subsystem to be initialized.
+    // The list of drivers are read-only and statically compiled into SDL2, varying by platform.
+
+    // SDL_GetNumAudioDrivers can never return a negative value.
+    DriverIterator {
+        length: unsafe { sys::audio::SDL_GetNumAudioDrivers() },
+        index: 0,
+    }
+}
+
+pub struct AudioSpecWAV {
+    pub freq: i32,
+    pub format: AudioFormat,
+    pub channels: u8,
+    audio_buf: *mut u8,
+    audio_len: u32,
+}
+
+impl AudioSpecWAV {
+    /// Loads a WAVE from the file path.
+    pub fn load_wav<P: AsRef<Path>>(path: P) -> Result<AudioSpecWAV, Error> {
+        let mut file = IOStream::from_file(path, "rb")?;
+        AudioSpecWAV::load_wav_rw(&mut file)
+    }
+
+    /// Loads a WAVE from the data source.
+    #[doc(alias = "SDL_LoadWAV_RW")]
+    pub fn load_wav_rw(src: &mut IOStream) -> Result<AudioSpecWAV, Error> {
+        use std::mem::MaybeUninit;
+        use std::ptr::null_mut;
+
+        let mut desired = MaybeUninit::uninit();
+        let mut audio_buf: *mut u8 = null_mut();
+        let mut audio_len: u32 = 0;
+        unsafe {
+            let ret = sys::audio::SDL_LoadWAV_IO(
+                src.raw(),
+                false,
+                desired.as_mut_ptr(),
+                &mut audio_buf,
+                &mut audio_len,
+            );
+            if !ret {
+                Err(get_error())
+            } else {
+                let desired = desired.assume_init();
+                Ok(AudioSpecWAV {
+                    freq: desired.freq,
+                    format: AudioFormat::from_ll(desired.format).unwrap(),
+                    channels: desired.channels.try_into().unwrap(),
+                    audio_buf,
+                    audio_len,
+                })
+            }
+        }
+    }
+
+    pub fn buffer(&self) -> &[u8] {
+        use std::slice::from_raw_parts;
+        unsafe {
+            let ptr = self.audio_buf as *const u8;
+            let len = self.audio_len as usize;
+            from_raw_parts(ptr, len)
+        }
+    }
+}
+
+impl Drop for AudioSpecWAV {
+    #[doc(alias = "SDL_free")]
+    fn drop(&mut self) {
+        unsafe {
+            SDL_free(self.audio_buf as *mut _);
+        }
+    }
+}
+
+/// Writes audio data out as a WAVE file.
+///
+/// The RIFF header is written up front with placeholder sizes, which are filled in
+/// by [`WavWriter::finish`], or on drop if `finish` was not called. The target
+/// stream therefore has to be seekable.
+///
+/// Samples are stored little-endian, as WAVE requires: big-endian input is
+/// byte-swapped and `S8` input is stored as unsigned 8-bit.
+///
+/// # Example
+/// ```no_run
+/// use sdl3::audio::{AudioFormat, AudioSpec, WavWriter};
+///
+/// let spec = AudioSpec::new(Some(44_100), Some(2), Some(AudioFormat::f32_sys()));
+/// let mut writer = WavWriter::create("out.wav", &spec).unwrap();
+/// writer.write_samples(&[0.0f32; 44_100 * 2]).unwrap();
+/// writer.finish().unwrap();
+/// ```
+pub struct WavWriter<'a> {
+    stream: Option<IOStream<'a>>,
+    freq: i32,
+    channels: i32,
+    format: AudioFormat,
+    data_len: u32,
+}
+
+impl<'a> WavWriter<'a> {
+    const HEADER_LEN: u32 = 44;
+
+    /// Starts a WAVE file on `stream`, which must be positioned at the start of the
+    /// file. `spec` must have every field set.
+    pub fn new(stream: IOStream<'a>, spec: &AudioSpec) -> Result<WavWriter<'a>, Error> {
+        let (freq, channels, format) = match (spec.freq, spec.channels, spec.format) {
+            (Some(freq), Some(channels), Some(format))
+                if freq > 0 && channels > 0 && format != AudioFormat::UNKNOWN =>
+            {
+                (freq, channels, format)
+            }
+            _ => {
+                return Err(Error(
+                    "WavWriter needs a frequency, channel count and format".to_owned(),
+                ))
+            }
+        };
+        let mut writer = WavWriter {
+            stream: Some(stream),
+            freq,
+            channels,
+            format,
+            data_len: 0,
+        };
+        writer.write_header()?;
+        Ok(writer)
+    }
+
+    /// Creates (or truncates) the file at `path` and starts a WAVE file in it.
+    pub fn create<P: AsRef<Path>>(path: P, spec: &AudioSpec) -> Result<WavWriter<'static>, Error> {
+        WavWriter::new(IOStream::from_file(path, "wb")?, spec)
+    }
+
+    /// The format of the audio being written.
+    pub fn spec(&self) -> AudioSpec {
+        AudioSpec::new(Some(self.freq), Some(self.channels), Some(self.format))
+    }
+
+    /// The number of bytes of audio data written so far.
+    pub fn data_len(&self) -> u32 {
+        self.data_len
+    }
+
+    /// Appends interleaved audio data in the writer's format.
+    /// The length must be a whole number of frames.
+    pub fn write_data(&mut self, data: &[u8]) -> Result<(), Error> {
+        let sample_size = self.format.byte_size();
+        let frame_size = sample_size * self.channels as usize;
+        if data.len() % frame_size != 0 {
+            return Err(Error(format!(
+                "Audio data of {} bytes is not a whole number of {} byte frames",
+                data.len(),
+                frame_size
+            )));
+        }
+        let data_len = u32::try_from(data.len())
+            .ok()
+            .and_then(|len| len.checked_add(self.data_len))
+            .filter(|&len| len <= u32::MAX - Self::HEADER_LEN)
+            .ok_or_else(|| Error("WAVE data would exceed 4 GiB".to_owned()))?;
+
+        if self.format.is_big_endian() || self.format == AudioFormat::S8 {
+            let mut data = data.to_vec();
+            if self.format == AudioFormat::S8 {
+                data.iter_mut().for_each(|b| *b ^= 0x80);
+            } else {
+                data.chunks_exact_mut(sample_size)
+                    .for_each(|sample| sample.reverse());
+            }
+            self.write_all(&data)?;
+        } else {
+            self.write_all(data)?;
+        }
+        self.data_len = data_len;
+        Ok(())
+    }
+
+    /// Appends interleaved samples. Fails if `T` does not match the writer's format.
+    pub fn write_samples<T: AudioFormatNum>(&mut self, samples: &[T]) -> Result<(), Error> {
+        if T::audio_format() != self.format {
+            return Err(Error(format!(
+                "Sample type {:?} does not match WAVE format {:?}",
+                T::audio_format(),
+                self.format
+            )));
+        }
+        let bytes = unsafe {
+            std::slice::from_raw_parts(samples.as_ptr().cast(), std::mem::size_of_val(samples))
+        };
+        self.write_data(bytes)
+    }
+
+    /// Fills in the header sizes and closes the stream.
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.finalize()
+    }
+
+    fn finalize(&mut self) -> Result<(), Error> {
+        use std::io::{Seek, SeekFrom};
+
+        let Some(stream) = self.stream.as_mut() else {
+            return Ok(());
+        };
+        let end = stream.stream_position().map_err(io_to_sdl_error)?;
+        stream.seek(SeekFrom::Start(0)).map_err(io_to_sdl_error)?;
+        self.write_header()?;
+        let stream = self.stream.as_mut().unwrap();
+        stream.seek(SeekFrom::Start(end)).map_err(io_to_sdl_error)?;
+        self.stream = None;
+        Ok(())
+    }
+
+    fn write_header(&mut self) -> Result<(), Error> {
+        // WAVE_FORMAT_PCM or WAVE_FORMAT_IEEE_FLOAT
+        let format_tag: u16 = if self.format.is_float() { 3 } else { 1 };
+        let sample_size = self.format.byte_size() as u32;
+        let block_align = sample_size * self.channels as u32;
+
+        let mut header = Vec::with_capacity(Self::HEADER_LEN as usize);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&(Self::HEADER_LEN - 8 + self.data_len).to_le_bytes());
+        header.extend_from_slice(b"WAVE");
+        header.extend_from_slice(b"fmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&format_tag.to_le_bytes());
+        header.extend_from_slice(&(self.channels as u16).to_le_bytes());
+        header.extend_from_slice(&(self.freq as u32).to_le_bytes());
+        header.extend_from_slice(&(self.freq as u32 * block_align).to_le_bytes());
+        header.extend_from_slice(&(block_align as u16).to_le_bytes());
+        header.extend_from_slice(&(self.format.bit_size() as u16).to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&self.data_len.to_le_bytes());
+        self.write_all(&header)
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> Result<(), Error> {
+        use std::io::Write;
+
+        match self.stream.as_mut() {
+            Some(stream) => stream.write_all(data).map_err(io_to_sdl_error),
+            None => Err(Error("WavWriter is already finished".to_owned())),
+        }
+    }
+}
+
+impl Drop for WavWriter<'_> {
+    fn drop(&mut self) {
+        let _ = self.finalize();
+    }
+}
+
+fn io_to_sdl_error(error: io::Error) -> Error {
+    Error(format!("IO error: {}", error))
+}
+
+/// Converts a buffer of audio data from one format to another, resampling and
+/// remixing channels as needed. Both specs must have every field set.
+#[doc(alias = "SDL_ConvertAudioSamples")]
+pub fn convert_samples(
+    src_spec: &AudioSpec,
+    src: &[u8],
+    dst_spec: &AudioSpec,
+) -> Result<Vec<u8>, Error> {
+    let sdl_src_spec = sys::audio::SDL_AudioSpec::from(src_spec);
+    let sdl_dst_spec = sys::audio::SDL_AudioSpec::from(dst_spec);
+    let src_len: c_int = src
+        .len()
+        .try_into()
+        .map_err(|_| Error("Audio buffer is too large to convert".to_owned()))?;
+    let mut dst_data: *mut u8 = std::ptr::null_mut();
+    let mut dst_len: c_int = 0;
+    unsafe {
+        let result = sys::audio::SDL_ConvertAudioSamples(
+            &sdl_src_spec,
+            src.as_ptr(),
+            src_len,
+            &sdl_dst_spec,
+            &mut dst_data,
+            &mut dst_len,
+        );
+        if !result {
+            return Err(get_error());
+        }
+        let converted = if dst_data.is_null() {
+            Vec::new()
+        } else {
+            std::slice::from_raw_parts(dst_data, dst_len as usize).to_vec()
+        };
+        SDL_free(dst_data as *mut c_void);
+        Ok(converted)
+    }
+}
+
+/// Mixes `src` into `dst`, both in `format`, scaling `src` by `volume` and
+/// clipping the result. A volume of `1.0` leaves `src` unchanged.
+///
+/// `dst` must be at least as long as `src`; only the first `src.len()` bytes are
+/// touched.
+#[doc(alias = "SDL_MixAudio")]
+pub fn mix_into(dst: &mut [u8], src: &[u8], format: AudioFormat, volume: f32) -> Result<(), Error> {
+    if dst.len() < src.len() {
+        return Err(Error(format!(
+            "Mix destination of {} bytes is shorter than source of {} bytes",
+            dst.len(),
+            src.len()
+        )));
+    }
+    let len: u32 = src
+        .len()
+        .try_into()
+        .map_err(|_| Error("Audio buffer is too large to mix".to_owned()))?;
+    let result = unsafe {
+        sys::audio::SDL_MixAudio(dst.as_mut_ptr(), src.as_ptr(), format.to_ll(), len, volume)
+    };
+    if result {
+        Ok(())
+    } else {
+        Err(get_error())
+    }
+}
+
+pub trait AudioCallback<Channel>: Send + 'static
+where
+    Channel: AudioFormatNum + 'static,
+{
+    fn callback(&mut self, out: &mut [Channel]);
+}
+
+/// A phantom type for retrieving the `SDL_AudioFormat` of a given generic type.
+/// All format types are returned as native-endian.
+pub trait AudioFormatNum: Copy + 'static {
+    fn audio_format() -> AudioFormat;
+
+    /// The appropriately typed silence value for the audio format used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // The AudioFormatNum trait has to be imported for the Channel::SILENCE part to work.
+    /// use sdl3::audio::{AudioCallback, AudioFormatNum};
+    ///
+    /// struct Silence;
+    ///
+    /// impl<Channel> AudioCallback<Channel> for Silence
+    /// where
+    ///     Channel: AudioFormatNum,
+    /// {
+    ///     fn callback(&mut self, out: &mut [Channel]) {
+    ///         for dst in out.iter_mut() {
+    ///             *dst = Channel::SILENCE;
+    ///        }
+    ///     }
+    /// }
+    /// ```
+    const SILENCE: Self;
+}
+
+/// `AUDIO_S8`
+impl AudioFormatNum for i8 {
+    fn audio_format() -> AudioFormat {
+        AudioFormat::S8
+    }
+    const SILENCE: i8 = 0;
+}
+/// `AUDIO_U8`
+impl AudioFormatNum for u8 {
+    fn audio_format() -> AudioFormat {
+        AudioFormat::U8
+    }
+    const SILENCE: u8 = 0x80;
+}
+/// `AUDIO_S16`
+impl AudioFormatNum for i16 {
+    fn audio_format() -> AudioFormat {
+        AudioFormat::s16_sys()
+    }
+    const SILENCE: i16 = 0;
+}
+/// `AUDIO_S32`
+impl AudioFormatNum for i32 {
+    fn audio_format() -> AudioFormat {
+        AudioFormat::s32_sys()
+    }
+    const SILENCE: i32 = 0;
+}
+/// `AUDIO_F32`
+impl AudioFormatNum for f32 {
+    fn audio_format() -> AudioFormat {
+        AudioFormat::f32_sys()
+    }
+    const SILENCE: f32 = 0.0;
+}
+
+#[derive(Clone, Debug)]
+pub struct AudioSpec {
+    /// DSP frequency (samples per second). Set to None for the device's fallback frequency.
+    pub freq: Option<i32>,
+    /// Number of separate audio channels. Set to None for the device's fallback number of channels.
+    pub channels: Option<i32>,
+    /// Audio format. Set to None for the device's fallback audio format.
+    pub format: Option<AudioFormat>,
+}
+
+impl From<AudioSpec> for sys::audio::SDL_AudioSpec {
+    fn from(val: AudioSpec) -> Self {
+        AudioSpec::convert_to_ll(val.freq, val.channels, val.format)
+    }
+}
+
+impl From<&AudioSpec> for sys::audio::SDL_AudioSpec {
+    fn from(spec: &AudioSpec) -> Self {
+        sys::audio::SDL_AudioSpec {
+            freq: spec.freq.unwrap_or(0), // SDL uses 0 to indicate default frequency
+            format: spec.format.unwrap_or(AudioFormat::UNKNOWN).to_ll(), // Use AudioFormat::Unknown for default
+            channels: spec.channels.unwrap_or(0), // SDL uses 0 to indicate default channels
+        }
+    }
+}
+
+impl AudioSpec {
+    fn convert_to_ll<R, C, F>(rate: R, channels: C, format: F) -> sys::audio::SDL_AudioSpec
+    where
+        R: Into<Option<i32>>,
+        C: Into<Option<i32>>,
+        F: Into<Option<AudioFormat>>,
+    {
+        let channels = channels.into();
+        let freq = rate.into();
+        let format = format.into();
+
+        sys::audio::SDL_AudioSpec {
+            freq: freq.unwrap_or(0),
+            format: format.unwrap_or(AudioFormat::UNKNOWN).to_ll(),
+            channels: channels.unwrap_or(0),
+        }
+    }
+}
+
+impl From<&sys::audio::SDL_AudioSpec> for AudioSpec {
+    fn from(sdl_spec: &sys::audio::SDL_AudioSpec) -> Self {
+        Self {
+            freq: if sdl_spec.freq != 0 {
+                Some(sdl_spec.freq)
+            } else {
+                None // SDL used default frequency
+            },
+            format: if sdl_spec.format != sys::audio::SDL_AUDIO_UNKNOWN {
+                Some(AudioFormat::from_ll(sdl_spec.format).expect("Unknown audio format"))
+            } else {
+                None // SDL used default format
+            },
+            channels: if sdl_spec.channels != 0 {
+                Some(sdl_spec.channels)
+            } else {
+                None // SDL used default channels
+            },
+        }
+    }
+}
+impl AudioSpec {
+    /// Creates a new `AudioSpec` with specified values.
+    /// Use `None` for any parameter to indicate the device's default value.
+    pub fn new(freq: Option<i32>, channels: Option<i32>, format: Option<AudioFormat>) -> Self {
+        Self {
+            freq,
+            channels,
+            format,
+        }
+    }
+
+    // fn convert_from_ll(spec: sys::audio::SDL_AudioSpec) -> AudioSpec {
+    //     AudioSpec {
+    //         freq: Some(spec.freq.into()),
+    //         format: AudioFormat::from_ll(spec.format),
+    //         channels: Some(spec.channels),
+    //     }
+    // }
+}
+
+impl Default for AudioSpec {
+    /// Creates an `AudioSpec` with all fields set to `None` (use device defaults).
+    fn default() -> Self {
+        Self {
+            freq: None,
+            channels: None,
+            format: None,
+        }
+    }
+}
+
+/// A speaker position within an interleaved audio frame.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum ChannelPosition {
+    /// The only channel of a mono signal.
+    Front,
+    FrontLeft,
+    FrontRight,
+    FrontCenter,
+    LowFrequency,
+    BackLeft,
+    BackRight,
+    BackCenter,
+    SideLeft,
+    SideRight,
+}
+
+/// The speaker layouts SDL uses for a given channel count.
+///
+/// Interleaved audio data is expected in the order returned by
+/// [`ChannelLayout::positions`]; a [`ChannelMap`] can be used to feed data in a
+/// different order.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum ChannelLayout {
+    /// 1 channel: front.
+    Mono,
+    /// 2 channels: front left, front right.
+    Stereo,
+    /// 3 channels: front left, front right, LFE.
+    TwoPointOne,
+    /// 4 channels: front left, front right, back left, back right.
+    Quad,
+    /// 5 channels: front left, front right, LFE, back left, back right.
+    FourPointOne,
+    /// 6 channels: front left, front right, front center, LFE, back left, back right.
+    FivePointOne,
+    /// 7 channels: front left, front right, front center, LFE, back center, side left,
+    /// side right.
+    SixPointOne,
+    /// 8 channels: front left, front right, front center, LFE, back left, back right,
+    /// side left, side right.
+    SevenPointOne,
+}
+
+impl ChannelLayout {
+    /// Returns SDL's layout for the given number of channels, if it has one.
+    pub fn from_channels(channels: i32) -> Option<ChannelLayout> {
+        match channels {
+            1 => Some(ChannelLayout::Mono),
+            2 => Some(ChannelLayout::Stereo),
+            3 => Some(ChannelLayout::TwoPointOne),
+            4 => Some(ChannelLayout::Quad),
+            5 => Some(ChannelLayout::FourPointOne),
+            6 => Some(ChannelLayout::FivePointOne),
+            7 => Some(ChannelLayout::SixPointOne),
+            8 => Some(ChannelLayout::SevenPointOne),
+            _ => None,
+        }
+    }
+
+    /// The number of channels in this layout.
+    pub fn channels(self) -> i32 {
+        self.positions().len() as i32
+    }
+
+    /// The speaker positions of this layout, in SDL's interleaving order.
+    pub fn positions(self) -> &'static [ChannelPosition] {
+        use self::ChannelPosition::*;
+        match self {
+            ChannelLayout::Mono => &[Front],
+            ChannelLayout::Stereo => &[FrontLeft, FrontRight],
+            ChannelLayout::TwoPointOne => &[FrontLeft, FrontRight, LowFrequency],
+            ChannelLayout::Quad => &[FrontLeft, FrontRight, BackLeft, BackRight],
+            ChannelLayout::FourPointOne => {
+                &[FrontLeft, FrontRight, LowFrequency, BackLeft, BackRight]
+            }
+            ChannelLayout::FivePointOne => &[
+                FrontLeft,
+                FrontRight,
+                FrontCenter,
+                LowFrequency,
+                BackLeft,
+                BackRight,
+            ],
+            ChannelLayout::SixPointOne => &[
+                FrontLeft,
+                FrontRight,
+                FrontCenter,
+                LowFrequency,
+                BackCenter,
+                SideLeft,
+                SideRight,
+            ],
+            ChannelLayout::SevenPointOne => &[
+                FrontLeft,
+                FrontRight,
+                FrontCenter,
+                LowFrequency,
+                BackLeft,
+                BackRight,
+                SideLeft,
+                SideRight,
+            ],
+        }
+    }
+
+    /// The index of `position` within a frame of this layout.
+    pub fn index_of(self, position: ChannelPosition) -> Option<usize> {
+        self.positions().iter().position(|&p| p == position)
+    }
+}
+
+impl AudioSpec {
+    /// The speaker layout implied by the channel count, if set.
+    pub fn layout(&self) -> Option<ChannelLayout> {
+        self.channels.and_then(ChannelLayout::from_channels)
+    }
+}
+
+/// Reorders or mutes the channels of an audio stream.
+///
+/// Entry `i` holds the channel that channel `i` is routed to, or `None` if it is
+/// muted. For example `[1, 0]` swaps the left and right channels of a stereo signal.
+/// A channel map cannot change the number of channels.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct ChannelMap {
+    map: Vec<c_int>,
+}
+
+impl ChannelMap {
+    /// A map that routes each of `channels` channels to itself.
+    pub fn identity(channels: usize) -> ChannelMap {
+        ChannelMap {
+            map: (0..channels as c_int).collect(),
+        }
+    }
+
+    /// Builds a map from a list of target channels, `None` meaning muted.
+    pub fn new<I>(targets: I) -> ChannelMap
+    where
+        I: IntoIterator<Item = Option<usize>>,
+    {
+        ChannelMap {
+            map: targets
+                .into_iter()
+                .map(|t| t.map_or(-1, |t| t as c_int))
+                .collect(),
+        }
+    }
+
+    /// The number of channels in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// The channel that `channel` is routed to, or `None` if it is muted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is out of range.
+    pub fn target(&self, channel: usize) -> Option<usize> {
+        let target = self.map[channel];
+        if target < 0 {
+            None
+        } else {
+            Some(target as usize)
+        }
+    }
+
+    /// Routes `channel` to `target`, or mutes it if `target` is `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is out of range.
+    pub fn set_target(&mut self, channel: usize, target: Option<usize>) {
+        self.map[channel] = target.map_or(-1, |t| t as c_int);
+    }
+
+    /// Mutes `channel`.
+    pub fn mute(&mut self, channel: usize) {
+        self.set_target(channel, None);
+    }
+
+    /// Exchanges the routing of channels `a` and `b`.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.map.swap(a, b);
+    }
+
+    /// The raw map, as passed to SDL (`-1` for muted channels).
+    pub fn as_slice(&self) -> &[c_int] {
+        &self.map
+    }
+
+    /// Copies a map returned by SDL and frees the original.
+    /// Returns `None` for a null pointer, which SDL uses for "no remapping".
+    unsafe fn from_sdl(raw: *mut c_int, count: c_int) -> Option<ChannelMap> {
+        if raw.is_null() {
+            return None;
+        }
+        let map = std::slice::from_raw_parts(raw, count.max(0) as usize).to_vec();
+        SDL_free(raw as *mut c_void);
+        Some(ChannelMap { map })
+    }
+}
+
+impl From<ChannelLayout> for ChannelMap {
+    fn from(layout: ChannelLayout) -> ChannelMap {
+        ChannelMap::identity(layout.channels() as usize)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum AudioDeviceID {
+    Device(sys::audio::SDL_AudioDeviceID),
+}
+
+impl Copy for AudioDeviceID {}
+
+impl AudioDeviceID {
+    pub fn id(&self) -> sys::audio::SDL_AudioDeviceID {
+        match *self {
+            AudioDeviceID::Device(id) => id,
+        }
+    }
+
+    pub fn name(&self) -> Result<String, Error> {
+        unsafe {
+            let name_ptr = sys::audio::SDL_GetAudioDeviceName(self.id());
+            if name_ptr.is_null() {
+                return Err(get_error());
+            }
+            Ok(CStr::from_ptr(name_ptr).to_str().unwrap().to_owned())
+        }
+    }
+
+    /// Get the current format of the device, along with its buffer size in sample
+    /// frames.
+    ///
+    /// For the default device IDs this reports the format of the current default
+    /// device.
+    #[doc(alias = "SDL_GetAudioDeviceFormat")]
+    pub fn format(&self) -> Result<(AudioSpec, i32), Error> {
+        let mut spec = AudioSpec::default().into();
+        let mut sample_frames: c_int = 0;
+        let result = unsafe {
+            sys::audio::SDL_GetAudioDeviceFormat(self.id(), &mut spec, &mut sample_frames)
+        };
+        if result {
+            Ok((AudioSpec::from(&spec), sample_frames))
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Whether this is a physical device, as opposed to a logical device opened by
+    /// the application.
+    #[doc(alias = "SDL_IsAudioDevicePhysical")]
+    pub fn is_physical(&self) -> bool {
+        unsafe { sys::audio::SDL_IsAudioDevicePhysical(self.id()) }
+    }
+
+    /// Whether this is a playback device, as opposed to a recording device.
+    #[doc(alias = "SDL_IsAudioDevicePlayback")]
+    pub fn is_playback(&self) -> bool {
+        unsafe { sys::audio::SDL_IsAudioDevicePlayback(self.id()) }
+    }
+
+    /// Whether this device plays back or records audio.
+    pub fn direction(&self) -> AudioDirection {
+        if self.is_playback() {
+            AudioDirection::Playback
+        } else {
+            AudioDirection::Recording
+        }
+    }
+}
+
+impl PartialEq for AudioDeviceID {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+impl Eq for AudioDeviceID {}
+
+impl std::hash::Hash for AudioDeviceID {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id().hash(state)
+    }
+}
+
+/// Whether an audio device plays back or records audio.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum AudioDirection {
+    Playback,
+    Recording,
+}
+
+impl AudioDirection {
+    pub(crate) fn from_recording(recording: bool) -> AudioDirection {
+        if recording {
+            AudioDirection::Recording
+        } else {
+            AudioDirection::Playback
+        }
+    }
+
+    pub(crate) fn is_recording(self) -> bool {
+        self == AudioDirection::Recording
+    }
+}
+
+/// A change reported by [`AudioDeviceWatcher::handle_event`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum AudioDeviceChange {
+    Added(AudioDeviceID, AudioDirection),
+    Removed(AudioDeviceID, AudioDirection),
+    FormatChanged(AudioDeviceID, AudioDirection),
+    /// The default device for `direction` is now the one called `name`.
+    DefaultChanged {
+        direction: AudioDirection,
+        name: Option<String>,
+    },
+}
+
+/// Keeps a live list of the audio devices, updated from the event stream.
+///
+/// Feed every event to [`AudioDeviceWatcher::handle_event`]; it updates the lists
+/// and reports what changed. Default devices are tracked by name, because SDL
+/// only exposes them through the special default device IDs. A change of default
+/// device is picked up on the next audio device event, which SDL sends whenever
+/// devices come and go or the default device's format changes.
+///
+/// # Example
+/// ```no_run
+/// use sdl3::audio::{AudioDeviceChange, AudioDeviceWatcher};
+///
+/// let sdl_context = sdl3::init().unwrap();
+/// let audio_subsystem = sdl_context.audio().unwrap();
+/// let mut watcher = AudioDeviceWatcher::new(&audio_subsystem).unwrap();
+/// let mut event_pump = sdl_context.event_pump().unwrap();
+///
+/// for event in event_pump.wait_iter() {
+///     for change in watcher.handle_event(&event) {
+///         if let AudioDeviceChange::DefaultChanged { direction, name } = change {
+///             println!("default {:?} device is now {:?}", direction, name);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AudioDeviceWatcher {
+    _audio: AudioSubsystem,
+    playback: Vec<AudioDeviceID>,
+    recording: Vec<AudioDeviceID>,
+    default_playback: Option<String>,
+    default_recording: Option<String>,
+}
+
+impl AudioDeviceWatcher {
+    /// Takes a snapshot of the current devices.
+    pub fn new(audio: &AudioSubsystem) -> Result<AudioDeviceWatcher, Error> {
+        Ok(AudioDeviceWatcher {
+            _audio: audio.clone(),
+            playback: audio.audio_playback_device_ids()?,
+            recording: audio.audio_recording_device_ids()?,
+            default_playback: Self::default_name(AudioDirection::Playback),
+            default_recording: Self::default_name(AudioDirection::Recording),
+        })
+    }
+
+    fn default_name(direction: AudioDirection) -> Option<String> {
+        let id = match direction {
+            AudioDirection::Playback => SDL_AUDIO_DEVICE_DEFAULT_PLAYBACK,
+            AudioDirection::Recording => SDL_AUDIO_DEVICE_DEFAULT_RECORDING,
+        };
+        AudioDeviceID::Device(id).name().ok()
+    }
+
+    /// The physical playback devices currently present.
+    pub fn playback_devices(&self) -> &[AudioDeviceID] {
+        &self.playback
+    }
+
+    /// The physical recording devices currently present.
+    pub fn recording_devices(&self) -> &[AudioDeviceID] {
+        &self.recording
+    }
+
+    /// The name of the current default playback device, if there is one.
+    pub fn default_playback_name(&self) -> Option<&str> {
+        self.default_playback.as_deref()
+    }
+
+    /// The name of the current default recording device, if there is one.
+    pub fn default_recording_name(&self) -> Option<&str> {
+        self.default_recording.as_deref()
+    }
+
+    /// Updates the device lists from `event` and returns what changed.
+    /// Events unrelated to audio devices are ignored.
+    pub fn handle_event(&mut self, event: &crate::event::Event) -> Vec<AudioDeviceChange> {
+        use crate::event::Event;
+
+        let mut changes = Vec::new();
+        match *event {
+            Event::AudioDeviceAdded {
+                which, direction, ..
+            } => {
+                let devices = self.devices_mut(direction);
+                if !devices.contains(&which) {
+                    devices.push(which);
+                    changes.push(AudioDeviceChange::Added(which, direction));
+                }
+            }
+            Event::AudioDeviceRemoved {
+                which, direction, ..
+            } => {
+                let devices = self.devices_mut(direction);
+                if let Some(index) = devices.iter().position(|&id| id == which) {
+                    devices.remove(index);
+                    changes.push(AudioDeviceChange::Removed(which, direction));
+                }
+            }
+            Event::AudioDeviceFormatChanged {
+                which, direction, ..
+            } => {
+                changes.push(AudioDeviceChange::FormatChanged(which, direction));
+            }
+            _ => return changes,
+        }
+
+        for direction in [AudioDirection::Playback, AudioDirection::Recording] {
+            let name = Self::default_name(direction);
+            let current = match direction {
+                AudioDirection::Playback => &mut self.default_playback,
+                AudioDirection::Recording => &mut self.default_recording,
+            };
+            if *current != name {
+                *current = name.clone();
+                changes.push(AudioDeviceChange::DefaultChanged { direction, name });
+            }
+        }
+        changes
+    }
+
+    fn devices_mut(&mut self, direction: AudioDirection) -> &mut Vec<AudioDeviceID> {
+        match direction {
+            AudioDirection::Playback => &mut self.playback,
+            AudioDirection::Recording => &mut self.recording,
+        }
+    }
+}
+
+/// Represents an open audio device (playback or recording).
+#[derive(Clone)]
+pub struct AudioDevice {
+    device_id: AudioDeviceID,
+}
+
+impl PartialEq for AudioDevice {
+    fn eq(&self, other: &Self) -> bool {
+        self.device_id == other.device_id
+    }
+}
+
+impl Eq for AudioDevice {}
+
+impl Drop for AudioDevice {
+    fn drop(&mut self) {
+        unsafe {
+            sys::audio::SDL_CloseAudioDevice(self.device_id.id());
+        }
+    }
+}
+
+impl AudioDevice {
+    pub fn id(&self) -> AudioDeviceID {
+        self.device_id
+    }
+
+    pub fn new(device_id: AudioDeviceID) -> Self {
+        AudioDevice { device_id }
+    }
+
+    /// Get the name of the audio device.
+    #[doc(alias = "SDL_GetAudioDeviceName")]
+    pub fn name(&self) -> Result<String, Error> {
+        unsafe {
+            let name_ptr = sys::audio::SDL_GetAudioDeviceName(self.device_id.id());
+            if name_ptr.is_null() {
+                return Err(get_error());
+            }
+            Ok(CStr::from_ptr(name_ptr).to_str().unwrap().to_owned())
+        }
+    }
+
+    /// Get the channel map of the audio device.
+    /// Returns `None` if the device uses SDL's default channel order.
+    #[doc(alias = "SDL_GetAudioDeviceChannelMap")]
+    pub fn channel_map(&self) -> Option<ChannelMap> {
+        let mut count: c_int = 0;
+        unsafe {
+            let raw = sys::audio::SDL_GetAudioDeviceChannelMap(self.device_id.id(), &mut count);
+            ChannelMap::from_sdl(raw, count)
+        }
+    }
+
+    /// Sets a callback that sees the final mix of this device before it is sent to
+    /// the hardware, and may modify it. This is useful for metering or effects.
+    ///
+    /// The callback receives the device's format and the interleaved `f32` samples of
+    /// the mix. It runs on SDL's audio thread, so it should not block.
+    ///
+    /// The callback stays installed until the returned [`AudioPostmixCallback`] is
+    /// dropped. Only one postmix callback can be set per device at a time.
+    #[doc(alias = "SDL_SetAudioPostmixCallback")]
+    pub fn set_postmix_callback<F>(&self, callback: F) -> Result<AudioPostmixCallback<F>, Error>
+    where
+        F: FnMut(&AudioSpec, &mut [f32]) + Send + 'static,
+    {
+        let mut callback = Box::new(callback);
+        let result = unsafe {
+            sys::audio::SDL_SetAudioPostmixCallback(
+                self.device_id.id(),
+                Some(audio_postmix_callback_marshall::<F>),
+                &mut *callback as *mut F as *mut c_void,
+            )
+        };
+        if result {
+            Ok(AudioPostmixCallback {
+                device_id: self.device_id,
+                _callback: callback,
+            })
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Binds an audio stream to this device.
+    #[doc(alias = "SDL_BindAudioStream")]
+    pub fn bind_stream(&self, stream: &AudioStream) -> Result<(), Error> {
+        let result = unsafe { sys::audio::SDL_BindAudioStream(self.device_id.id(), stream.stream) };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Binds multiple audio streams to this device.
+    #[doc(alias = "SDL_BindAudioStreams")]
+    pub fn bind_streams(&self, streams: &[&AudioStream]) -> Result<(), Error> {
+        let streams_ptrs: Vec<*mut sys::audio::SDL_AudioStream> =
+            streams.iter().map(|s| s.stream).collect();
+        let result = unsafe {
+            sys::audio::SDL_BindAudioStreams(
+                self.device_id.id(),
+                streams_ptrs.as_ptr() as *mut _,
+                streams.len() as i32,
+            )
+        };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Opens a new audio device for playback or recording (given the desired parameters).
+    #[doc(alias = "SDL_OpenAudioDevice")]
+    fn open<'a, D>(device: D, spec: &AudioSpec, recording: bool) -> Result<AudioDevice, Error>
+    where
+        D: Into<Option<&'a AudioDeviceID>>,
+    {
+        let desired = AudioSpec::convert_to_ll(spec.freq, spec.channels, spec.format);
+
+        unsafe {
+            let sdl_device = match device.into() {
+                Some(device) => device.id(),
+                // use default device if no device is specified
+                None => {
+                    if recording {
+                        SDL_AUDIO_DEVICE_DEFAULT_RECORDING
+                    } else {
+                        SDL_AUDIO_DEVICE_DEFAULT_PLAYBACK
+                    }
+                }
+            };
+            let device_id = sys::audio::SDL_OpenAudioDevice(sdl_device, &desired);
+            match device_id {
+                0 => Err(get_error()),
+                id => {
+                    let device_id = AudioDeviceID::Device(id);
+
+                    Ok(AudioDevice::new(device_id))
+                }
+            }
+        }
+    }
+
+    /// Opens a new audio device for playback (given the desired parameters).
+    pub fn open_playback<'a, D>(
+        _a: &AudioSubsystem,
+        device: D,
+        spec: &AudioSpec,
+    ) -> Result<AudioDevice, Error>
+    where
+        D: Into<Option<&'a AudioDeviceID>>,
+    {
+        AudioDevice::open(device, spec, false)
+    }
+
+    /// Opens a new audio device for recording (given the desired parameters).
+    pub fn open_recording<'a, D>(
+        _a: &AudioSubsystem,
+        device: D,
+        spec: &AudioSpec,
+    ) -> Result<AudioDevice, Error>
+    where
+        D: Into<Option<&'a AudioDeviceID>>,
+    {
+        AudioDevice::open(device, spec, true)
+    }
+
+    /// Pauses playback of the audio device.
+    #[doc(alias = "SDL_PauseAudioDevice")]
+    pub fn pause(&self) -> bool {
+        unsafe { sys::audio::SDL_PauseAudioDevice(self.device_id.id()) }
+    }
+
+    /// Starts playback of the audio device.
+    #[doc(alias = "SDL_ResumeAudioDevice")]
+    pub fn resume(&self) -> bool {
+        unsafe { sys::audio::SDL_ResumeAudioDevice(self.device_id.id()) }
+    }
+
+    /// Opens a new audio stream for this device with the specified spec.
+    /// The device begins paused, so you must call `stream.resume()` to start playback.
+    #[doc(alias = "SDL_OpenAudioDeviceStream")]
+    pub fn open_playback_stream_with_callback<CB, Channel>(
+        &self,
+        spec: &AudioSpec,
+        callback: CB,
+    ) -> Result<AudioStreamWithCallback<CB>, Error>
+    where
+        CB: AudioCallback<Channel>,
+        Channel: AudioFormatNum + 'static,
+    {
+        let sdl_audiospec: sys::audio::SDL_AudioSpec = spec.clone().into();
+
+        if sdl_audiospec.format != Channel::audio_format().to_ll() {
+            return Err(Error(
+                "AudioSpec format does not match AudioCallback Channel type".to_string(),
+            ));
+        }
+
+        let callback_box = Box::new(callback);
+        let c_userdata = Box::into_raw(callback_box) as *mut c_void;
+
+        unsafe extern "C" fn audio_stream_callback<CB, Channel>(
+            userdata: *mut c_void,
+            sdl_stream: *mut sys::audio::SDL_AudioStream,
+            len: c_int,
+            _bytes: c_int,
+        ) where
+            CB: AudioCallback<Channel>,
+            Channel: AudioFormatNum + 'static,
+        {
+            let callback = &mut *(userdata as *mut CB);
+            let sample_count = len as usize / size_of::<Channel>();
+            let mut buffer = vec![Channel::SILENCE; sample_count];
+            callback.callback(&mut buffer);
+            let buffer_ptr = buffer.as_ptr() as *const c_void;
+            let ret = sys::audio::SDL_PutAudioStreamData(sdl_stream, buffer_ptr, len);
+            if !ret {
+                eprintln!("Error pushing audio data into stream: {}", get_error());
+            }
+        }
+
+        unsafe {
+            let stream = sys::audio::SDL_OpenAudioDeviceStream(
+                self.device_id.id(),
+                &sdl_audiospec,
+                Some(audio_stream_callback::<CB, Channel>),
+                c_userdata,
+            );
+
+            if stream.is_null() {
+                // Drop the callback box
+                let _ = Box::from_raw(c_userdata as *mut CB);
+                Err(get_error())
+            } else {
+                Ok(AudioStreamWithCallback {
+                    base_stream: AudioStream::from_ll(stream),
+                    _marker: PhantomData,
+                    c_userdata,
+                })
+            }
+        }
+    }
+
+    /// Opens a new audio stream for recording with the specified spec.
+    /// The device begins paused, so you must call `stream.resume()` to start recording.
+    #[doc(alias = "SDL_OpenAudioDeviceStream")]
+    pub fn open_recording_stream_with_callback<CB, Channel>(
+        &self,
+        spec: &AudioSpec,
+        callback: CB,
+    ) -> Result<AudioStreamWithCallback<CB>, Error>
+    where
+        CB: AudioRecordingCallback<Channel>,
+        Channel: AudioFormatNum + 'static,
+    {
+        // Convert Rust AudioSpec to SDL_AudioSpec
+        let sdl_audiospec: sys::audio::SDL_AudioSpec = spec.clone().into();
+
+        if sdl_audiospec.format != Channel::audio_format().to_ll() {
+            return Err(Error(
+                "AudioSpec format does not match AudioCallback Channel type".to_string(),
+            ));
+        }
+
+        let callback_box = Box::new(callback);
+        let c_userdata = Box::into_raw(callback_box) as *mut c_void;
+
+        unsafe {
+            let stream = sys::audio::SDL_OpenAudioDeviceStream(
+                self.device_id.id(),
+                &sdl_audiospec,
+                Some(audio_recording_stream_callback::<CB, Channel>),
+                c_userdata,
+            );
+
+            if stream.is_null() {
+                // Drop the callback box
+                let _ = Box::from_raw(c_userdata as *mut CB);
+                Err(get_error())
+            } else {
+                Ok(AudioStreamWithCallback {
+                    base_stream: AudioStream::from_ll(stream),
+                    _marker: PhantomData,
+                    c_userdata,
+                })
+            }
+        }
+    }
+}
+
+/// A postmix callback installed on an audio device with
+/// [`AudioDevice::set_postmix_callback`]. Dropping it removes the callback.
+pub struct AudioPostmixCallback<F> {
+    device_id: AudioDeviceID,
+    _callback: Box<F>,
+}
+
+impl<F> Drop for AudioPostmixCallback<F> {
+    #[doc(alias = "SDL_SetAudioPostmixCallback")]
+    fn drop(&mut self) {
+        // This fails harmlessly if the device was closed already, in which case the
+        // callback will not run again either.
+        unsafe {
+            sys::audio::SDL_SetAudioPostmixCallback(
+                self.device_id.id(),
+                None,
+                std::ptr::null_mut(),
+            );
+        }
+    }
+}
+
+unsafe extern "C" fn audio_postmix_callback_marshall<F>(
+    userdata: *mut c_void,
+    spec: *const sys::audio::SDL_AudioSpec,
+    buffer: *mut f32,
+    buflen: c_int,
+) where
+    F: FnMut(&AudioSpec, &mut [f32]) + Send + 'static,
+{
+    let callback = &mut *(userdata as *mut F);
+    let spec = AudioSpec::from(&*spec);
+    let samples = std::slice::from_raw_parts_mut(buffer, buflen as usize / size_of::<f32>());
+    callback(&spec, samples);
+}
+
+pub struct AudioStream {
+    stream: *mut sys::audio::SDL_AudioStream,
+    get_callback: Option<Box<AudioStreamCallbackBox>>,
+    put_callback: Option<Box<AudioStreamCallbackBox>>,
+}
+
+/// A callback installed with [`AudioStream::set_get_callback`] or
+/// [`AudioStream::set_put_callback`].
+///
+/// It receives the stream, the number of bytes the stream needs (or just received)
+/// right now, and the total number of bytes requested (or received) in this call.
+type AudioStreamCallbackBox = Box<dyn FnMut(&AudioStream, i32, i32) + Send + 'static>;
+
+unsafe extern "C" fn audio_stream_callback_marshall(
+    userdata: *mut c_void,
+    stream: *mut sys::audio::SDL_AudioStream,
+    additional_amount: c_int,
+    total_amount: c_int,
+) {
+    let callback = &mut *(userdata as *mut AudioStreamCallbackBox);
+    // The stream is owned elsewhere, so make sure this view of it never destroys it.
+    let stream = std::mem::ManuallyDrop::new(AudioStream::from_ll(stream));
+    callback(&stream, additional_amount, total_amount);
+}
+
+impl Drop for AudioStream {
+    /// Destroys the audio stream, unbinding it automatically from the device.
+    /// If this stream was created with SDL_OpenAudioDeviceStream, the audio device that was opened alongside this stream’s creation will be closed, too.
+    fn drop(&mut self) {
+        if !self.stream.is_null() {
+            unsafe {
+                sys::audio::SDL_DestroyAudioStream(self.stream);
+            }
+            self.stream = std::ptr::null_mut();
+        }
+    }
+}
+
+impl Debug for AudioStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Get the device "name [ID]"
+        let device_name = self
+            .device_id()
+            .and_then(|id| id.name().ok())
+            .unwrap_or("Unknown".to_string());
+        let device_name = format!(
+            "{} [{}]",
+            device_name,
+            self.device_id().map(|id| id.id()).unwrap_or(0)
+        );
+
+        // Get the audio specs
+        let (src_spec, dst_spec) = match self.get_format() {
+            Ok((src, dst)) => (Some(src), Some(dst)),
+            Err(_) => (None, None),
+        };
+
+        // Get the gain
+        let gain = self.get_gain().ok();
+
+        // Begin building the debug struct
+        let mut ds = f.debug_struct("AudioStream");
+
+        ds.field("device", &device_name);
+
+        if let Some(src_spec) = src_spec {
+            ds.field("src_spec", &src_spec);
+        } else {
+            ds.field("src_spec", &"Unknown");
+        }
+
+        if let Some(dst_spec) = dst_spec {
+            ds.field("dst_spec", &dst_spec);
+        } else {
+            ds.field("dst_spec", &"Unknown");
+        }
+
+        if let Some(gain) = gain {
+            ds.field("gain", &gain);
+        } else {
+            ds.field("gain", &"Unknown");
+        }
+
+        ds.finish()
+    }
+}
+impl Display for AudioStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = self.device_id().and_then(|id| id.name().ok()) {
+            write!(f, "AudioStream({})", name)
+        } else {
+            write!(f, "AudioStream")
+        }
+    }
+}
+
+impl AudioStream {
+    fn from_ll(stream: *mut sys::audio::SDL_AudioStream) -> AudioStream {
+        AudioStream {
+            stream,
+            get_callback: None,
+            put_callback: None,
+        }
+    }
+
+    /// Get the SDL_AudioStream pointer.
+    #[doc(alias = "SDL_AudioStream")]
+    pub fn stream(&mut self) -> *mut sys::audio::SDL_AudioStream {
+        self.stream
+    }
+
+    /// Get the device ID bound to the stream.
+    /// If the stream is not bound to a device, this will return `None`.
+    #[doc(alias = "SDL_GetAudioStreamDevice")]
+    pub fn device_id(&self) -> Option<AudioDeviceID> {
+        let device_id = unsafe { sys::audio::SDL_GetAudioStreamDevice(self.stream) };
+        // If not bound, or invalid, this returns zero, which is not a valid device ID.
+        if device_id != 0 {
+            Some(AudioDeviceID::Device(device_id))
+        } else {
+            None
+        }
+    }
+
+    pub fn device_name(&self) -> Option<String> {
+        self.device_id().and_then(|id| id.name().ok())
+    }
+
+    /// Creates a new audio stream that converts audio data from the source format (`src_spec`)
+    /// to the destination format (`dst_spec`).
+    ///
+    /// # Arguments
+    ///
+    /// * `src_spec` - The format details of the input audio.
+    /// * `dst_spec` - The format details of the output audio.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(AudioStream)` on success or an error message on failure.
+    ///
+    /// # Safety
+    ///
+    /// This function is safe to call from any thread.
+    pub fn new(src_spec: Option<&AudioSpec>, dst_spec: Option<&AudioSpec>) -> Result<Self, Error> {
+        let sdl_src_spec = src_spec.map(sys::audio::SDL_AudioSpec::from);
+        let sdl_dst_spec = dst_spec.map(sys::audio::SDL_AudioSpec::from);
+
+        let sdl_src_spec_ptr = sdl_src_spec
+            .as_ref()
+            .map_or(std::ptr::null(), |spec| spec as *const _);
+        let sdl_dst_spec_ptr = sdl_dst_spec
+            .as_ref()
+            .map_or(std::ptr::null(), |spec| spec as *const _);
+
+        let stream =
+            unsafe { sys::audio::SDL_CreateAudioStream(sdl_src_spec_ptr, sdl_dst_spec_ptr) };
+        if stream.is_null() {
+            Err(get_error())
+        } else {
+            Ok(Self::from_ll(stream))
+        }
+    }
+
+    /// Creates a new audio stream for playback.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_spec` - The format of audio data the application will provide.
+    /// * `device_spec` - The format of audio data the audio device expects.
+    ///                   If `None`, SDL will choose an appropriate format.
+    pub fn new_playback_stream(
+        app_spec: &AudioSpec,
+        device_spec: Option<&AudioSpec>,
+    ) -> Result<Self, Error> {
+        Self::new(Some(app_spec), device_spec)
+    }
+
+    /// Creates a new audio stream for recording.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_spec` - The format of audio data the audio device provides.
+    ///                   If `None`, SDL will choose an appropriate format.
+    /// * `app_spec` - The format of audio data the application wants to receive.
+    pub fn new_recording_stream(
+        device_spec: Option<&AudioSpec>,
+        app_spec: &AudioSpec,
+    ) -> Result<Self, Error> {
+        Self::new(device_spec, Some(app_spec))
+    }
+
+    /// Create an `AudioStream` for this device with the specified spec.
+    /// This device will be closed when the stream is dropped.
+    /// The device begins paused, so you must call `stream.resume()` to start playback.
+    #[doc(alias = "SDL_OpenAudioDeviceStream")]
+    pub fn open_device_stream(
+        device_id: AudioDeviceID,
+        spec: Option<&AudioSpec>,
+    ) -> Result<AudioStream, Error> {
+        let sdl_spec = spec.map(|spec| spec.into());
+        let sdl_spec_ptr = crate::util::option_to_ptr(sdl_spec.as_ref());
+
+        let stream = unsafe {
+            sys::audio::SDL_OpenAudioDeviceStream(
+                device_id.id(),
+                sdl_spec_ptr,
+                // not using callbacks here
+                None,
+                std::ptr::null_mut(),
+            )
+        };
+        if stream.is_null() {
+            Err(get_error())
+        } else {
+            Ok(Self::from_ll(stream))
+        }
+    }
+
+    /// Retrieves the source and destination formats of the audio stream.
+    ///
+    /// Returns a tuple `(src_spec, dst_spec)` where each is an `Option<AudioSpec>`.
+    #[doc(alias = "SDL_GetAudioStreamFormat")]
+    pub fn get_format(&self) -> Result<(Option<AudioSpec>, Option<AudioSpec>), Error> {
+        let mut sdl_src_spec = AudioSpec::default().into();
+        let mut sdl_dst_spec = AudioSpec::default().into();
+        let result = unsafe {
+            sys::audio::SDL_GetAudioStreamFormat(self.stream, &mut sdl_src_spec, &mut sdl_dst_spec)
+        };
+        if result {
+            let src_spec = if sdl_src_spec.format != sys::audio::SDL_AUDIO_UNKNOWN {
+                Some(AudioSpec::from(&sdl_src_spec))
+            } else {
+                None
+            };
+            let dst_spec = if sdl_dst_spec.format != sys::audio::SDL_AUDIO_UNKNOWN {
+                Some(AudioSpec::from(&sdl_dst_spec))
+            } else {
+                None
+            };
+            Ok((src_spec, dst_spec))
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Retrieves the gain of the audio stream.
+    ///
+    /// Returns the gain as a `f32` on success, or an error message on failure.
+    #[doc(alias = "SDL_GetAudioStreamGain")]
+    pub fn get_gain(&self) -> Result<f32, Error> {
+        let gain = unsafe { sys::audio::SDL_GetAudioStreamGain(self.stream) };
+        if gain >= 0.0 {
+            Ok(gain)
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Changes the format of the audio stream.
+    ///
+    /// Passing `None` for either spec leaves that side of the stream unchanged.
+    /// If the stream is bound to a device, the side facing the device is managed
+    /// by SDL and cannot be changed here.
+    #[doc(alias = "SDL_SetAudioStreamFormat")]
+    pub fn set_format(
+        &self,
+        src_spec: Option<&AudioSpec>,
+        dst_spec: Option<&AudioSpec>,
+    ) -> Result<(), Error> {
+        let sdl_src_spec = src_spec.map(sys::audio::SDL_AudioSpec::from);
+        let sdl_dst_spec = dst_spec.map(sys::audio::SDL_AudioSpec::from);
+        let result = unsafe {
+            sys::audio::SDL_SetAudioStreamFormat(
+                self.stream,
+                crate::util::option_to_ptr(sdl_src_spec.as_ref()),
+                crate::util::option_to_ptr(sdl_dst_spec.as_ref()),
+            )
+        };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Sets the gain of the audio stream.
+    ///
+    /// The gain is a volume multiplier applied to the audio data, `1.0` meaning
+    /// no change. It must be zero or greater.
+    #[doc(alias = "SDL_SetAudioStreamGain")]
+    pub fn set_gain(&self, gain: f32) -> Result<(), Error> {
+        let result = unsafe { sys::audio::SDL_SetAudioStreamGain(self.stream, gain) };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Retrieves the frequency ratio of the audio stream.
+    #[doc(alias = "SDL_GetAudioStreamFrequencyRatio")]
+    pub fn get_frequency_ratio(&self) -> Result<f32, Error> {
+        let ratio = unsafe { sys::audio::SDL_GetAudioStreamFrequencyRatio(self.stream) };
+        if ratio > 0.0 {
+            Ok(ratio)
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Sets the frequency ratio of the audio stream.
+    ///
+    /// Values above `1.0` play the audio faster and at a higher pitch, values below
+    /// `1.0` play it slower and lower. SDL accepts ratios between `0.01` and `100.0`.
+    #[doc(alias = "SDL_SetAudioStreamFrequencyRatio")]
+    pub fn set_frequency_ratio(&self, ratio: f32) -> Result<(), Error> {
+        let result = unsafe { sys::audio::SDL_SetAudioStreamFrequencyRatio(self.stream, ratio) };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Tells the stream that no more data is coming for now, so any buffered input
+    /// is converted and made available immediately.
+    #[doc(alias = "SDL_FlushAudioStream")]
+    pub fn flush(&self) -> Result<(), Error> {
+        let result = unsafe { sys::audio::SDL_FlushAudioStream(self.stream) };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Discards any pending data in the stream.
+    #[doc(alias = "SDL_ClearAudioStream")]
+    pub fn clear(&self) -> Result<(), Error> {
+        let result = unsafe { sys::audio::SDL_ClearAudioStream(self.stream) };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Gets the number of bytes currently queued in the stream, in the input format.
+    ///
+    /// Unlike [`AudioStream::available_bytes`], this counts data that has been put
+    /// into the stream but not yet converted.
+    #[doc(alias = "SDL_GetAudioStreamQueued")]
+    pub fn queued_bytes(&self) -> Result<i32, Error> {
+        let queued = unsafe { sys::audio::SDL_GetAudioStreamQueued(self.stream) };
+        if queued == -1 {
+            Err(get_error())
+        } else {
+            Ok(queued)
+        }
+    }
+
+    /// Retrieves the input channel map of the audio stream.
+    /// Returns `None` if no remapping is applied.
+    #[doc(alias = "SDL_GetAudioStreamInputChannelMap")]
+    pub fn get_input_channel_map(&self) -> Option<ChannelMap> {
+        let mut count: c_int = 0;
+        unsafe {
+            let raw = sys::audio::SDL_GetAudioStreamInputChannelMap(self.stream, &mut count);
+            ChannelMap::from_sdl(raw, count)
+        }
+    }
+
+    /// Retrieves the output channel map of the audio stream.
+    /// Returns `None` if no remapping is applied.
+    #[doc(alias = "SDL_GetAudioStreamOutputChannelMap")]
+    pub fn get_output_channel_map(&self) -> Option<ChannelMap> {
+        let mut count: c_int = 0;
+        unsafe {
+            let raw = sys::audio::SDL_GetAudioStreamOutputChannelMap(self.stream, &mut count);
+            ChannelMap::from_sdl(raw, count)
+        }
+    }
+
+    /// Sets the input channel map of the audio stream, reordering data passed to
+    /// [`AudioStream::put_data`]. `None` removes any remapping.
+    ///
+    /// The map must have as many entries as the stream's input spec has channels.
+    #[doc(alias = "SDL_SetAudioStreamInputChannelMap")]
+    pub fn set_input_channel_map(&self, map: Option<&ChannelMap>) -> Result<(), Error> {
+        let (ptr, count) = channel_map_to_ll(map);
+        let result =
+            unsafe { sys::audio::SDL_SetAudioStreamInputChannelMap(self.stream, ptr, count) };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Sets the output channel map of the audio stream, reordering data read from it.
+    /// `None` removes any remapping.
+    ///
+    /// The map must have as many entries as the stream's output spec has channels.
+    #[doc(alias = "SDL_SetAudioStreamOutputChannelMap")]
+    pub fn set_output_channel_map(&self, map: Option<&ChannelMap>) -> Result<(), Error> {
+        let (ptr, count) = channel_map_to_ll(map);
+        let result =
+            unsafe { sys::audio::SDL_SetAudioStreamOutputChannelMap(self.stream, ptr, count) };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Sets a callback that runs whenever data is requested from the stream, for
+    /// example when a bound playback device needs more audio.
+    ///
+    /// The callback receives the stream, so it can put just enough data to satisfy
+    /// the request, along with the number of bytes needed right now (which may be
+    /// zero) and the total number of bytes being requested. It runs on SDL's audio
+    /// thread with the stream locked, so it should not block.
+    ///
+    /// The stream owns the callback; it is replaced by the next call and dropped
+    /// along with the stream.
+    #[doc(alias = "SDL_SetAudioStreamGetCallback")]
+    pub fn set_get_callback<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(&AudioStream, i32, i32) + Send + 'static,
+    {
+        let mut callback: Box<AudioStreamCallbackBox> = Box::new(Box::new(callback));
+        let userdata = &mut *callback as *mut AudioStreamCallbackBox as *mut c_void;
+        let result = unsafe {
+            sys::audio::SDL_SetAudioStreamGetCallback(
+                self.stream,
+                Some(audio_stream_callback_marshall),
+                userdata,
+            )
+        };
+        if result {
+            self.get_callback = Some(callback);
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Removes the callback set with [`AudioStream::set_get_callback`].
+    #[doc(alias = "SDL_SetAudioStreamGetCallback")]
+    pub fn clear_get_callback(&mut self) -> Result<(), Error> {
+        let result = unsafe {
+            sys::audio::SDL_SetAudioStreamGetCallback(self.stream, None, std::ptr::null_mut())
+        };
+        if result {
+            self.get_callback = None;
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Sets a callback that runs whenever data is put into the stream, for example
+    /// when a bound recording device delivers audio.
+    ///
+    /// The callback receives the stream, so it can read the new data right away,
+    /// along with the number of bytes just added and the total number of bytes
+    /// added in this call. It runs with the stream locked, so it should not block.
+    ///
+    /// The stream owns the callback; it is replaced by the next call and dropped
+    /// along with the stream.
+    #[doc(alias = "SDL_SetAudioStreamPutCallback")]
+    pub fn set_put_callback<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(&AudioStream, i32, i32) + Send + 'static,
+    {
+        let mut callback: Box<AudioStreamCallbackBox> = Box::new(Box::new(callback));
+        let userdata = &mut *callback as *mut AudioStreamCallbackBox as *mut c_void;
+        let result = unsafe {
+            sys::audio::SDL_SetAudioStreamPutCallback(
+                self.stream,
+                Some(audio_stream_callback_marshall),
+                userdata,
+            )
+        };
+        if result {
+            self.put_callback = Some(callback);
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Removes the callback set with [`AudioStream::set_put_callback`].
+    #[doc(alias = "SDL_SetAudioStreamPutCallback")]
+    pub fn clear_put_callback(&mut self) -> Result<(), Error> {
+        let result = unsafe {
+            sys::audio::SDL_SetAudioStreamPutCallback(self.stream, None, std::ptr::null_mut())
+        };
+        if result {
+            self.put_callback = None;
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Pauses playback of the audio stream.
+    #[doc(alias = "SDL_PauseAudioStream")]
+    pub fn pause(&self) -> Result<(), Error> {
+        let result = unsafe { sys::audio::SDL_PauseAudioStreamDevice(self.stream) };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Resumes playback of the audio stream.
+    #[doc(alias = "SDL_ResumeAudioStream")]
+    pub fn resume(&self) -> Result<(), Error> {
+        let result = unsafe { sys::audio::SDL_ResumeAudioStreamDevice(self.stream) };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Gets the number of converted/resampled bytes available.
+    #[doc(alias = "SDL_GetAudioStreamAvailable")]
+    pub fn available_bytes(&self) -> Result<i32, Error> {
+        let available = unsafe { sys::audio::SDL_GetAudioStreamAvailable(self.stream) };
+        if available == -1 {
+            Err(get_error())
+        } else {
+            Ok(available)
+        }
+    }
+
+    /// Checks that `T` matches the format of one side of the stream, and if
+    /// `channels` is given, that the channel count matches as well.
+    fn check_sample_type<T: AudioFormatNum>(
+        &self,
+        input: bool,
+        channels: Option<usize>,
+    ) -> Result<(), Error> {
+        let (src_spec, dst_spec) = self.get_format()?;
+        let (side, spec) = if input {
+            ("input", src_spec)
+        } else {
+            ("output", dst_spec)
+        };
+        let spec = spec.ok_or_else(|| Error(format!("Audio stream has no {} format", side)))?;
+        if spec.format != Some(T::audio_format()) {
+            return Err(Error(format!(
+                "Audio stream {} format {:?} does not match sample type {:?}",
+                side,
+                spec.format,
+                T::audio_format()
+            )));
+        }
+        if let Some(channels) = channels {
+            if spec.channels != Some(channels as i32) {
+                return Err(Error(format!(
+                    "Audio stream {} has {:?} channels, frames have {}",
+                    side, spec.channels, channels
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds raw bytes to the stream without checking them against its format.
+    fn put_raw(&self, ptr: *const c_void, len: usize) -> Result<(), Error> {
+        let result = unsafe { sys::audio::SDL_PutAudioStreamData(self.stream, ptr, len as c_int) };
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Reads raw bytes from the stream without checking them against its format.
+    /// Returns the number of bytes read.
+    fn get_raw(&self, ptr: *mut c_void, len: usize) -> Result<usize, Error> {
+        let ret = unsafe { sys::audio::SDL_GetAudioStreamData(self.stream, ptr, len as c_int) };
+        if ret == -1 {
+            Err(get_error())
+        } else {
+            Ok(ret as usize)
+        }
+    }
+
+    /// Adds interleaved samples to the stream.
+    ///
+    /// Fails if the stream's input format does not match `T`.
+    #[doc(alias = "SDL_PutAudioStreamData")]
+    pub fn put_samples<T: AudioFormatNum>(&self, samples: &[T]) -> Result<(), Error> {
+        self.check_sample_type::<T>(true, None)?;
+        self.put_raw(samples.as_ptr().cast(), std::mem::size_of_val(samples))
+    }
+
+    /// Reads interleaved samples from the stream into `buf`.
+    /// Returns the number of complete samples read.
+    ///
+    /// Fails if the stream's output format does not match `T`.
+    #[doc(alias = "SDL_GetAudioStreamData")]
+    pub fn get_samples<T: AudioFormatNum>(&self, buf: &mut [T]) -> Result<usize, Error> {
+        self.check_sample_type::<T>(false, None)?;
+        let bytes = self.get_raw(buf.as_mut_ptr().cast(), std::mem::size_of_val(buf))?;
+        Ok(bytes / size_of::<T>())
+    }
+
+    /// Adds frames of `N` interleaved channels to the stream.
+    ///
+    /// Fails if the stream's input format does not match `T` or it does not have `N`
+    /// channels.
+    #[doc(alias = "SDL_PutAudioStreamData")]
+    pub fn put_frames<T: AudioFormatNum, const N: usize>(
+        &self,
+        frames: &[[T; N]],
+    ) -> Result<(), Error> {
+        self.check_sample_type::<T>(true, Some(N))?;
+        self.put_raw(frames.as_ptr().cast(), std::mem::size_of_val(frames))
+    }
+
+    /// Reads frames of `N` interleaved channels from the stream into `buf`.
+    /// Returns the number of complete frames read.
+    ///
+    /// Fails if the stream's output format does not match `T` or it does not have `N`
+    /// channels.
+    #[doc(alias = "SDL_GetAudioStreamData")]
+    pub fn get_frames<T: AudioFormatNum, const N: usize>(
+        &self,
+        buf: &mut [[T; N]],
+    ) -> Result<usize, Error> {
+        self.check_sample_type::<T>(false, Some(N))?;
+        let bytes = self.get_raw(buf.as_mut_ptr().cast(), std::mem::size_of_val(buf))?;
+        Ok(bytes / size_of::<[T; N]>())
+    }
+
+    /// Reads samples as f32 into the provided buffer.
+    /// Returns the number of samples read.
+    ///
+    /// Unlike [`AudioStream::get_samples`], this also accepts f32 output in the
+    /// non-native byte order and swaps it.
+    pub fn read_f32_samples(&mut self, buf: &mut [f32]) -> io::Result<usize> {
+        let swapped = if cfg!(target_endian = "little") {
+            AudioFormat::F32BE
+        } else {
+            AudioFormat::F32LE
+        };
+        self.read_any_endian_samples(buf, swapped, |v| f32::from_bits(v.to_bits().swap_bytes()))
+    }
+
+    /// Reads samples as i16 into the provided buffer.
+    /// Returns the number of samples read.
+    ///
+    /// Unlike [`AudioStream::get_samples`], this also accepts i16 output in the
+    /// non-native byte order and swaps it.
+    pub fn read_i16_samples(&mut self, buf: &mut [i16]) -> io::Result<usize> {
+        let swapped = if cfg!(target_endian = "little") {
+            AudioFormat::S16BE
+        } else {
+            AudioFormat::S16LE
+        };
+        self.read_any_endian_samples(buf, swapped, i16::swap_bytes)
+    }
+
+    /// Reads samples of type `T`, or of `swapped` (`T` in the other byte order)
+    /// followed by swapping each sample.
+    fn read_any_endian_samples<T: AudioFormatNum>(
+        &self,
+        buf: &mut [T],
+        swapped: AudioFormat,
+        swap: fn(T) -> T,
+    ) -> io::Result<usize> {
+        let to_io = |e: Error| io::Error::new(io::ErrorKind::InvalidData, e);
+
+        let (_, output_spec) = self.get_format().map_err(to_io)?;
+        if output_spec.and_then(|spec| spec.format) != Some(swapped) {
+            return self.get_samples(buf).map_err(to_io);
+        }
+
+        let bytes = self
+            .get_raw(buf.as_mut_ptr().cast(), std::mem::size_of_val(buf))
+            .map_err(to_io)?;
+        let samples_read = bytes / size_of::<T>();
+        for v in &mut buf[..samples_read] {
+            *v = swap(*v);
+        }
+        Ok(samples_read)
+    }
+
+    /// Adds data to the stream.
+    /// The bytes must already be in the stream's input format; see
+    /// [`AudioStream::put_samples`] for a typed alternative.
+    pub fn put_data(&self, buf: &[u8]) -> Result<(), Error> {
+        self.put_raw(buf.as_ptr().cast(), buf.len())
+    }
+}
+
+fn channel_map_to_ll(map: Option<&ChannelMap>) -> (*const c_int, c_int) {
+    match map {
+        Some(map) => (map.as_slice().as_ptr(), map.len() as c_int),
+        None => (std::ptr::null(), 0),
+    }
+}
+
+impl Read for AudioStream {
+    /// Reads audio data from the stream.
+    /// Note that this reads bytes from the stream, not samples.
+    /// You must convert the bytes to samples based on the format of the stream.
+    /// `get_samples` and `get_frames` are provided for convenience.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.get_raw(buf.as_mut_ptr().cast(), buf.len())
+            .map_err(io::Error::other)
+    }
+}
+
+// Streams with callbacks
+pub struct AudioStreamWithCallback<CB> {
+    base_stream: AudioStream,
+    c_userdata: *mut c_void,
+    _marker: PhantomData<CB>,
+}
+
+impl<CB> Drop for AudioStreamWithCallback<CB> {
+    fn drop(&mut self) {
+        // `base_stream` will be dropped automatically.
+        if !self.c_userdata.is_null() {
+            unsafe {
+                // Drop the callback box
+                let _ = Box::from_raw(self.c_userdata as *mut CB);
+            }
+            self.c_userdata = std::ptr::null_mut();
+        }
+    }
+}
+
+impl<CB> AudioStreamWithCallback<CB> {
+    /// Pauses the audio stream.
+    pub fn pause(&self) -> Result<(), Error> {
+        self.base_stream.pause()
+    }
+
+    /// Resumes the audio stream.
+    pub fn resume(&self) -> Result<(), Error> {
+        self.base_stream.resume()
+    }
+}
+
+pub trait AudioRecordingCallback<Channel>: Send + 'static
+where
+    Channel: AudioFormatNum + 'static,
+{
+    fn callback(&mut self, input: &[Channel]);
+}
+
+unsafe extern "C" fn audio_recording_stream_callback<CB, Channel>(
+    userdata: *mut c_void,
+    sdl_stream: *mut sys::audio::SDL_AudioStream,
+    len: c_int,
+    _bytes: c_int,
+) where
+    CB: AudioRecordingCallback<Channel>,
+    Channel: AudioFormatNum + 'static,
+{
+    let callback = &mut *(userdata as *mut CB);
+
+    // Allocate a buffer to receive the recorded data
+    let sample_count = len as usize / size_of::<Channel>();
+    let mut buffer = vec![Channel::SILENCE; sample_count];
+
+    // Pull data from the stream
+    let buffer_ptr = buffer.as_mut_ptr() as *mut c_void;
+    let ret = sys::audio::SDL_GetAudioStreamData(sdl_stream, buffer_ptr, len);
+
+    if ret != len {
+        eprintln!("Error getting audio data from stream: {}", get_error());
+        return;
+    }
+
+    // Call the user's callback with the captured audio data
+    callback.callback(&buffer);
+}
+
+// TODO:
+//
+// /// Similar to `std::sync::MutexGuard`, but for use with `AudioStream::lock()`.
+// pub struct AudioStreamLockGuard<'a>
+// where
+//     CB: AudioCallback<F>,
+//     CB: 'a,
+//     F: AudioFormatNum + 'static,
+// {
+//     stream: &'a mut AudioStream<CB>,
+//     _nosend: PhantomData<*mut ()>,
+// }
+//
+// impl<'a, CB: AudioCallback> Deref for AudioStreamLockGuard<'a, CB> {
+//     type Target = CB;
+//     #[doc(alias = "SDL_UnlockAudioStream")]
+//     fn deref(&self) -> &CB {
+//         (*self.device.userdata).as_ref().expect("Missing callback")
+//     }
+// }
+//
+// impl<'a, CB: AudioCallback> DerefMut for AudioStreamLockGuard<'a, CB> {
+//     fn deref_mut(&mut self) -> &mut CB {
+//         (*self.device.userdata).as_mut().expect("Missing callback")
+//     }
+// }
+//
+// impl<'a, CB: AudioCallback> Drop for AudioStreamLockGuard<'a, CB> {
+//     fn drop(&mut self) {
+//         unsafe { sys::SDL_UnlockAudioStream(self._audio_stream) }
+//     }
+// }
+
+#[cfg(test)]
+mod test {
+    use super::{ChannelLayout, ChannelMap, ChannelPosition};
+
+    #[test]
+    fn channel_layout_order() {
+        for channels in 1..=8 {
+            let layout = ChannelLayout::from_channels(channels).unwrap();
+            assert_eq!(layout.channels(), channels);
+        }
+        assert_eq!(ChannelLayout::from_channels(9), None);
+
+        let layout = ChannelLayout::FivePointOne;
+        assert_eq!(layout.index_of(ChannelPosition::FrontCenter), Some(2));
+        assert_eq!(layout.index_of(ChannelPosition::LowFrequency), Some(3));
+        assert_eq!(layout.index_of(ChannelPosition::SideLeft), None);
+        assert_eq!(
+            ChannelLayout::SevenPointOne.index_of(ChannelPosition::SideRight),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn channel_map_edit() {
+        let mut map = ChannelMap::from(ChannelLayout::Stereo);
+        assert_eq!(map.as_slice(), &[0, 1]);
+
+        map.swap(0, 1);
+        assert_eq!(map.as_slice(), &[1, 0]);
+
+        map.mute(1);
+        assert_eq!(map.target(0), Some(1));
+        assert_eq!(map.target(1), None);
+        assert_eq!(map, ChannelMap::new([Some(1), None]));
+        assert_eq!(map.as_slice(), &[1, -1]);
+    }
+}
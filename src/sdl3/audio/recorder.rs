@@ -0,0 +1,456 @@
+use super::{AudioDevice, AudioDeviceID, AudioFormatNum, AudioSpec, AudioStream, WavWriter};
+use crate::iostream::IOStream;
+use crate::AudioSubsystem;
+use crate::Error;
+use std::cell::UnsafeCell;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A sample type that can be recorded and metered.
+pub trait RecordableSample: AudioFormatNum + Send {
+    /// The sample scaled to `-1.0..=1.0`.
+    fn to_f32(self) -> f32;
+}
+
+impl RecordableSample for u8 {
+    fn to_f32(self) -> f32 {
+        (self as f32 - 128.0) / 128.0
+    }
+}
+
+impl RecordableSample for i8 {
+    fn to_f32(self) -> f32 {
+        self as f32 / 128.0
+    }
+}
+
+impl RecordableSample for i16 {
+    fn to_f32(self) -> f32 {
+        self as f32 / 32_768.0
+    }
+}
+
+impl RecordableSample for i32 {
+    fn to_f32(self) -> f32 {
+        self as f32 / 2_147_483_648.0
+    }
+}
+
+impl RecordableSample for f32 {
+    fn to_f32(self) -> f32 {
+        self
+    }
+}
+
+/// A fixed-size single-producer, single-consumer sample queue.
+///
+/// `head` and `tail` count the samples ever written and read; the producer only
+/// touches the free part of the buffer and the consumer only the filled part, so
+/// neither side needs a lock.
+struct SampleRing<T> {
+    buf: Box<[UnsafeCell<T>]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+unsafe impl<T: Send> Sync for SampleRing<T> {}
+
+impl<T: Copy> SampleRing<T> {
+    fn new(capacity: usize, fill: T) -> SampleRing<T> {
+        SampleRing {
+            buf: (0..capacity).map(|_| UnsafeCell::new(fill)).collect(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn len(&self) -> usize {
+        self.head
+            .load(Ordering::Acquire)
+            .wrapping_sub(self.tail.load(Ordering::Acquire))
+    }
+
+    /// Producer side. Returns how many samples fit.
+    fn push(&self, data: &[T]) -> usize {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        let free = self.capacity() - head.wrapping_sub(tail);
+        let count = data.len().min(free);
+        for (i, &sample) in data[..count].iter().enumerate() {
+            let index = head.wrapping_add(i) % self.capacity();
+            unsafe { *self.buf[index].get() = sample };
+        }
+        self.head.store(head.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    /// Consumer side. Copies up to `out.len()` samples without consuming them.
+    fn peek(&self, out: &mut [T]) -> usize {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        let count = out.len().min(head.wrapping_sub(tail));
+        for (i, sample) in out[..count].iter_mut().enumerate() {
+            let index = tail.wrapping_add(i) % self.capacity();
+            *sample = unsafe { *self.buf[index].get() };
+        }
+        count
+    }
+
+    /// Consumer side. Marks `count` samples as read.
+    fn consume(&self, count: usize) {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let count = count.min(self.len());
+        self.tail.store(tail.wrapping_add(count), Ordering::Release);
+    }
+
+    /// Consumer side. Drops everything queued so far.
+    fn clear(&self) {
+        self.consume(self.len());
+    }
+}
+
+/// The level of one channel over the most recent block of recorded audio.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ChannelLevel {
+    /// The largest absolute sample value, from `0.0` to `1.0`.
+    pub peak: f32,
+    /// The root mean square of the samples, from `0.0` to `1.0`.
+    pub rms: f32,
+}
+
+/// Per-channel levels, stored as `f32` bits so the audio thread can update them
+/// without locking.
+struct Levels {
+    peak: Box<[AtomicU32]>,
+    rms: Box<[AtomicU32]>,
+}
+
+impl Levels {
+    fn new(channels: usize) -> Levels {
+        Levels {
+            peak: (0..channels).map(|_| AtomicU32::new(0)).collect(),
+            rms: (0..channels).map(|_| AtomicU32::new(0)).collect(),
+        }
+    }
+
+    fn measure<T: RecordableSample>(&self, samples: &[T]) {
+        let channels = self.peak.len();
+        let frames = samples.len() / channels;
+        if frames == 0 {
+            return;
+        }
+        for channel in 0..channels {
+            let mut peak = 0.0f32;
+            let mut sum = 0.0f32;
+            for frame in samples.chunks_exact(channels) {
+                let value = frame[channel].to_f32();
+                peak = peak.max(value.abs());
+                sum += value * value;
+            }
+            let rms = (sum / frames as f32).sqrt();
+            self.peak[channel].store(peak.to_bits(), Ordering::Relaxed);
+            self.rms[channel].store(rms.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    fn get(&self) -> Vec<ChannelLevel> {
+        self.peak
+            .iter()
+            .zip(self.rms.iter())
+            .map(|(peak, rms)| ChannelLevel {
+                peak: f32::from_bits(peak.load(Ordering::Relaxed)),
+                rms: f32::from_bits(rms.load(Ordering::Relaxed)),
+            })
+            .collect()
+    }
+
+    fn reset(&self) {
+        for level in self.peak.iter().chain(self.rms.iter()) {
+            level.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+struct RecorderShared<T> {
+    ring: SampleRing<T>,
+    levels: Levels,
+    dropped: AtomicU64,
+}
+
+impl<T: RecordableSample> RecorderShared<T> {
+    /// Producer side. Moves the audio available in `stream` into the ring, in whole frames.
+    fn capture(&self, stream: &AudioStream) {
+        let channels = self.levels.peak.len();
+        let mut buffer = [T::SILENCE; 1024];
+        let buffer = &mut buffer[..1024 - 1024 % channels];
+        while let Ok(read @ 1..) = stream.get_samples(buffer) {
+            let samples = &buffer[..read];
+            self.levels.measure(samples);
+            let pushed = self.ring.push(samples);
+            self.dropped
+                .fetch_add((read - pushed) as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Whether an [`AudioRecorder`] is capturing.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum RecorderState {
+    Stopped,
+    Recording,
+    Paused,
+}
+
+/// Captures audio from a recording device into a lock-free ring buffer.
+///
+/// The device delivers audio on SDL's audio thread, which appends it to the ring
+/// buffer and updates the per-channel levels; the application drains the buffer
+/// with [`AudioRecorder::read`] or saves it with [`AudioRecorder::write_wav`].
+/// When the buffer is full, new audio is dropped and counted in
+/// [`AudioRecorder::dropped_samples`].
+///
+/// # Example
+/// ```no_run
+/// use sdl3::audio::AudioRecorder;
+/// use std::time::Duration;
+///
+/// let sdl_context = sdl3::init().unwrap();
+/// let audio_subsystem = sdl_context.audio().unwrap();
+///
+/// // Ten seconds of 48kHz mono audio.
+/// let mut recorder = AudioRecorder::<f32>::open(&audio_subsystem, None, 48_000, 1, 480_000).unwrap();
+/// recorder.start().unwrap();
+/// for _ in 0..50 {
+///     std::thread::sleep(Duration::from_millis(100));
+///     println!("level: {:?}", recorder.levels()[0]);
+/// }
+/// recorder.stop().unwrap();
+/// recorder.save_wav("recording.wav").unwrap();
+/// ```
+pub struct AudioRecorder<T: RecordableSample> {
+    // The stream is declared first so it is destroyed before the device is closed.
+    stream: AudioStream,
+    device: AudioDevice,
+    shared: Arc<RecorderShared<T>>,
+    spec: AudioSpec,
+    state: RecorderState,
+    _audio: AudioSubsystem,
+}
+
+impl<T: RecordableSample> AudioRecorder<T> {
+    /// Opens a recording device (the default one if `device` is `None`) for audio
+    /// in `T`'s format at `freq` with `channels` channels, buffering up to
+    /// `capacity_frames` frames.
+    ///
+    /// The recorder starts out stopped.
+    pub fn open(
+        audio: &AudioSubsystem,
+        device: Option<&AudioDeviceID>,
+        freq: i32,
+        channels: i32,
+        capacity_frames: usize,
+    ) -> Result<AudioRecorder<T>, Error> {
+        if channels <= 0 {
+            return Err(Error("AudioRecorder needs at least one channel".to_owned()));
+        }
+        let spec = AudioSpec::new(Some(freq), Some(channels), Some(T::audio_format()));
+        let device = AudioDevice::open_recording(audio, device, &spec)?;
+        device.pause();
+
+        let shared = Arc::new(RecorderShared {
+            ring: SampleRing::new(capacity_frames * channels as usize, T::SILENCE),
+            levels: Levels::new(channels as usize),
+            dropped: AtomicU64::new(0),
+        });
+
+        let mut stream = AudioStream::new(None, Some(&spec))?;
+        let callback_shared = shared.clone();
+        stream.set_put_callback(move |stream, _, _| callback_shared.capture(stream))?;
+        device.bind_stream(&stream)?;
+
+        Ok(AudioRecorder {
+            stream,
+            device,
+            shared,
+            spec,
+            state: RecorderState::Stopped,
+            _audio: audio.clone(),
+        })
+    }
+
+    /// The format of the recorded audio.
+    pub fn spec(&self) -> &AudioSpec {
+        &self.spec
+    }
+
+    /// The recording device.
+    pub fn device(&self) -> &AudioDevice {
+        &self.device
+    }
+
+    pub fn state(&self) -> RecorderState {
+        self.state
+    }
+
+    /// Starts capturing. Starting a stopped recorder discards the previous recording;
+    /// starting a paused one continues it.
+    pub fn start(&mut self) -> Result<(), Error> {
+        if self.state == RecorderState::Stopped {
+            self.clear()?;
+        }
+        if !self.device.resume() {
+            return Err(crate::get_error());
+        }
+        self.state = RecorderState::Recording;
+        Ok(())
+    }
+
+    /// Pauses capturing, keeping what was recorded so far.
+    pub fn pause(&mut self) -> Result<(), Error> {
+        if self.state == RecorderState::Recording {
+            self.pause_device()?;
+            self.state = RecorderState::Paused;
+        }
+        Ok(())
+    }
+
+    /// Stops capturing. The recording, including the audio still being converted when
+    /// the device stopped, stays available until the next [`AudioRecorder::start`].
+    pub fn stop(&mut self) -> Result<(), Error> {
+        if self.state != RecorderState::Stopped {
+            self.pause_device()?;
+            self.stream.flush()?;
+            // Flushing doesn't call the put callback. The paused device no longer feeds
+            // the stream, so this thread can take over as the ring's producer.
+            self.shared.capture(&self.stream);
+            self.state = RecorderState::Stopped;
+        }
+        Ok(())
+    }
+
+    fn pause_device(&self) -> Result<(), Error> {
+        if self.device.pause() {
+            Ok(())
+        } else {
+            Err(crate::get_error())
+        }
+    }
+
+    /// Discards all buffered audio and resets the levels.
+    pub fn clear(&mut self) -> Result<(), Error> {
+        self.stream.clear()?;
+        self.shared.ring.clear();
+        self.shared.levels.reset();
+        self.shared.dropped.store(0, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// The peak and RMS level of each channel over the most recently captured block.
+    pub fn levels(&self) -> Vec<ChannelLevel> {
+        self.shared.levels.get()
+    }
+
+    /// The number of samples currently buffered.
+    pub fn buffered_samples(&self) -> usize {
+        self.shared.ring.len()
+    }
+
+    /// The number of samples that could be buffered.
+    pub fn capacity_samples(&self) -> usize {
+        self.shared.ring.capacity()
+    }
+
+    /// The number of samples dropped because the buffer was full.
+    pub fn dropped_samples(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Moves up to `out.len()` buffered samples into `out`, oldest first.
+    /// Returns the number of samples read.
+    pub fn read(&mut self, out: &mut [T]) -> usize {
+        let count = self.shared.ring.peek(out);
+        self.shared.ring.consume(count);
+        count
+    }
+
+    /// Writes all buffered audio to `stream` as a WAVE file, leaving the buffer
+    /// untouched.
+    pub fn write_wav(&self, stream: IOStream) -> Result<(), Error> {
+        let mut samples = vec![T::SILENCE; self.shared.ring.len()];
+        let count = self.shared.ring.peek(&mut samples);
+        let channels = self.spec.channels.unwrap_or(1) as usize;
+        let whole_frames = count - count % channels;
+
+        let mut writer = WavWriter::new(stream, &self.spec)?;
+        writer.write_samples(&samples[..whole_frames])?;
+        writer.finish()
+    }
+
+    /// Saves all buffered audio to a WAVE file at `path`, leaving the buffer
+    /// untouched.
+    pub fn save_wav<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        self.write_wav(IOStream::from_file(path, "wb")?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChannelLevel, Levels, SampleRing};
+
+    #[test]
+    fn ring_wraps_and_overflows() {
+        let ring = SampleRing::new(4, 0i16);
+        assert_eq!(ring.push(&[1, 2, 3]), 3);
+
+        let mut out = [0i16; 2];
+        assert_eq!(ring.peek(&mut out), 2);
+        ring.consume(2);
+        assert_eq!(out, [1, 2]);
+
+        // Wraps around the end of the buffer, and drops what does not fit.
+        assert_eq!(ring.push(&[4, 5, 6, 7]), 3);
+        assert_eq!(ring.len(), 4);
+
+        let mut out = [0i16; 8];
+        assert_eq!(ring.peek(&mut out), 4);
+        assert_eq!(&out[..4], &[3, 4, 5, 6]);
+
+        ring.clear();
+        assert_eq!(ring.len(), 0);
+        assert_eq!(ring.push(&[8]), 1);
+        assert_eq!(ring.peek(&mut out), 1);
+        assert_eq!(out[0], 8);
+    }
+
+    #[test]
+    fn levels_per_channel() {
+        let levels = Levels::new(2);
+        levels.measure(&[0.5f32, 0.0, -0.5, 0.0, 0.5, 1.0, -0.5, 0.0]);
+        let measured = levels.get();
+        assert_eq!(
+            measured[0],
+            ChannelLevel {
+                peak: 0.5,
+                rms: 0.5
+            }
+        );
+        assert_eq!(
+            measured[1],
+            ChannelLevel {
+                peak: 1.0,
+                rms: 0.5
+            }
+        );
+
+        levels.measure(&[i16::MIN, 0]);
+        assert_eq!(levels.get()[0].peak, 1.0);
+
+        levels.reset();
+        assert_eq!(levels.get(), [ChannelLevel::default(); 2]);
+    }
+}
@@ -0,0 +1,433 @@
+//! Typed custom events.
+//!
+//! A payload pushed with [`EventSubsystem::push_custom_event`](crate::EventSubsystem) is kept
+//! on the Rust side; the SDL event only carries a key to it in `data1`. Every [`Event::Custom`]
+//! converted from that SDL event shares the payload. It is dropped once it has been taken
+//! with [`CustomEventType::take`], or once the event has been removed from the SDL queue and
+//! no `Event` refers to it anymore. Events are removed from the queue by:
+//!
+//! - polling or waiting for events through [`EventPump`](crate::EventPump),
+//! - [`flush_event`](crate::EventSubsystem::flush_event) and
+//!   [`flush_events`](crate::EventSubsystem::flush_events),
+//! - [`set_event_enabled`](crate::EventSubsystem::set_event_enabled) disabling the type,
+//! - a filter installed with [`set_event_filter`](crate::EventSubsystem::set_event_filter)
+//!   rejecting events already queued.
+//!
+//! A payload whose push fails, e.g. because the filter rejected it, is dropped right away.
+//! Events SDL removes on its own keep their payloads alive until the process exits. That is
+//! the case for events still queued when the event subsystem shuts down, and for events
+//! pushed from another thread while their type is being disabled.
+
+use std::any::Any;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use super::Event;
+use crate::sys;
+
+/// Type-erased `Mutex<Option<T>>` holding a payload until it's taken.
+trait PayloadSlot: Send + Sync {
+    fn is_present(&self) -> bool;
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + Send> PayloadSlot for Mutex<Option<T>> {
+    fn is_present(&self) -> bool {
+        self.lock().unwrap().is_some()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+type Slot = Arc<dyn PayloadSlot>;
+
+struct Pending {
+    slot: Slot,
+    /// Number of copies of the event pushed or being pushed, and not removed from the
+    /// queue yet.
+    queued: usize,
+}
+
+#[derive(Default)]
+struct Registry {
+    /// SDL event numbers handed out by `register_custom_event`.
+    types: HashSet<u32>,
+    /// Payloads of events that may still be in the SDL queue.
+    payloads: HashMap<usize, Pending>,
+    next_key: usize,
+}
+
+lazy_static! {
+    static ref REGISTRY: Mutex<Registry> = Mutex::new(Registry::default());
+}
+
+thread_local! {
+    /// Set while `SDL_SetEventFilter` runs the new filter over the queued events on this
+    /// thread, so rejected custom events release their payloads.
+    static FILTERING_QUEUE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Shared handle to the payload of an [`Event::Custom`].
+///
+/// Obtain the payload itself with [`CustomEventType::take`].
+#[derive(Clone)]
+pub struct CustomPayload {
+    key: usize,
+    slot: Option<Slot>,
+}
+
+impl CustomPayload {
+    pub(super) fn key(&self) -> usize {
+        self.key
+    }
+
+    /// Returns `true` if the payload is still there, i.e. hasn't been taken yet.
+    pub fn is_present(&self) -> bool {
+        self.slot.as_ref().is_some_and(|slot| slot.is_present())
+    }
+}
+
+impl PartialEq for CustomPayload {
+    fn eq(&self, other: &CustomPayload) -> bool {
+        self.key == other.key
+    }
+}
+
+impl fmt::Debug for CustomPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomPayload")
+            .field("key", &self.key)
+            .field("present", &self.is_present())
+            .finish()
+    }
+}
+
+/// Token for a registered custom event type carrying payloads of type `T`.
+///
+/// Returned by [`EventSubsystem::register_custom_event`](crate::EventSubsystem::register_custom_event).
+/// Every registration yields a distinct SDL event number, so the same `T` can back several
+/// event types; [`with_code`](Self::with_code) further splits one type by its `code` field.
+/// The token is `Copy` and can be sent to other threads.
+pub struct CustomEventType<T> {
+    type_: u32,
+    code: i32,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+impl<T> Clone for CustomEventType<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CustomEventType<T> {}
+
+impl<T> PartialEq for CustomEventType<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_ == other.type_ && self.code == other.code
+    }
+}
+
+impl<T> Eq for CustomEventType<T> {}
+
+impl<T> fmt::Debug for CustomEventType<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomEventType")
+            .field("type_", &self.type_)
+            .field("code", &self.code)
+            .finish()
+    }
+}
+
+impl<T: Any + Send> CustomEventType<T> {
+    pub(super) fn register(type_: u32) -> CustomEventType<T> {
+        REGISTRY.lock().unwrap().types.insert(type_);
+        CustomEventType {
+            type_,
+            code: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The SDL event number of this type.
+    pub fn event_type(&self) -> u32 {
+        self.type_
+    }
+
+    /// The `code` events pushed with this token carry.
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+
+    /// Returns a token for the same event type whose events carry `code` instead.
+    pub fn with_code(&self, code: i32) -> CustomEventType<T> {
+        CustomEventType {
+            type_: self.type_,
+            code,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns `true` if `event` is of this type and has this token's code.
+    pub fn matches(&self, event: &Event) -> bool {
+        matches!(*event, Event::Custom { type_, code, .. } if type_ == self.type_ && code == self.code)
+    }
+
+    /// Returns the code of `event` if it is of this type, regardless of the token's code.
+    pub fn code_of(&self, event: &Event) -> Option<i32> {
+        match *event {
+            Event::Custom { type_, code, .. } if type_ == self.type_ => Some(code),
+            _ => None,
+        }
+    }
+
+    /// Takes the payload out of `event`.
+    ///
+    /// Returns `None` if the event isn't of this type and code, or if the payload has already
+    /// been taken through this or another copy of the event.
+    pub fn take(&self, event: &Event) -> Option<T> {
+        if !self.matches(event) {
+            return None;
+        }
+        let Event::Custom { ref payload, .. } = *event else {
+            return None;
+        };
+        take_payload(payload)
+    }
+
+    /// Builds an event carrying `payload`; the payload is registered once it's pushed.
+    pub(super) fn event(&self, payload: T) -> Event {
+        let slot: Slot = Arc::new(Mutex::new(Some(payload)));
+        let key = {
+            let mut registry = REGISTRY.lock().unwrap();
+            registry.next_key += 1;
+            registry.next_key
+        };
+        Event::Custom {
+            timestamp: 0,
+            window_id: 0,
+            type_: self.type_,
+            code: self.code,
+            payload: CustomPayload {
+                key,
+                slot: Some(slot),
+            },
+        }
+    }
+}
+
+/// Takes a payload of type `T` out of its slot.
+pub(super) fn take_payload<T: Any>(payload: &CustomPayload) -> Option<T> {
+    let slot = payload.slot.as_ref()?;
+    let value = slot
+        .as_any()
+        .downcast_ref::<Mutex<Option<T>>>()?
+        .lock()
+        .unwrap()
+        .take();
+    if value.is_some() {
+        // Drop the registry's reference; queued copies of the event see an empty slot.
+        let removed = REGISTRY.lock().unwrap().payloads.remove(&payload.key);
+        drop(removed);
+    }
+    value
+}
+
+/// Looks up the payload for an SDL event of a registered custom type.
+pub(super) fn lookup(type_: u32, data1: *mut libc::c_void) -> Option<CustomPayload> {
+    let registry = REGISTRY.lock().unwrap();
+    if !registry.types.contains(&type_) {
+        return None;
+    }
+    let key = data1 as usize;
+    Some(CustomPayload {
+        key,
+        slot: registry.payloads.get(&key).map(|p| p.slot.clone()),
+    })
+}
+
+/// A push of an `Event::Custom` in progress.
+pub(super) struct PushTicket {
+    key: usize,
+}
+
+/// Registers the payload of an event about to be pushed.
+///
+/// The copy is counted as queued before it's pushed, so a thread dequeuing it before
+/// `end_push` finds the entry.
+pub(super) fn begin_push(payload: &CustomPayload) -> PushTicket {
+    if let Some(slot) = &payload.slot {
+        let mut registry = REGISTRY.lock().unwrap();
+        registry
+            .payloads
+            .entry(payload.key)
+            .or_insert_with(|| Pending {
+                slot: slot.clone(),
+                queued: 0,
+            })
+            .queued += 1;
+    }
+    PushTicket { key: payload.key }
+}
+
+/// Finishes a push started with `begin_push`.
+pub(super) fn end_push(ticket: PushTicket, queued: bool) {
+    if !queued {
+        release(ticket.key);
+    }
+}
+
+/// Notes that one copy of the event with payload `key` left the SDL queue, dropping the
+/// registry's reference once no copy is left there.
+fn release(key: usize) {
+    let mut registry = REGISTRY.lock().unwrap();
+    let Some(entry) = registry.payloads.get_mut(&key) else {
+        return;
+    };
+    entry.queued = entry.queued.saturating_sub(1);
+    let removed = if entry.queued == 0 {
+        registry.payloads.remove(&key)
+    } else {
+        None
+    };
+    drop(registry);
+    // Payload destructors run without the registry locked, so they may push events.
+    drop(removed);
+}
+
+/// Notes that an event was taken out of the SDL queue; once no copy of it is left there,
+/// the converted `Event`s are the only owners of the payload.
+pub(super) fn dequeued(event: &Event) {
+    if let Event::Custom { ref payload, .. } = *event {
+        release(payload.key);
+    }
+}
+
+/// Releases the payload of a raw event removed from the queue without being converted.
+fn dequeued_raw(raw: &sys::events::SDL_Event, types: &HashSet<u32>) {
+    let type_ = unsafe { raw.r#type };
+    if types.contains(&type_) {
+        release(unsafe { raw.user.data1 } as usize);
+    }
+}
+
+/// Removes the events in `min_type..=max_type` from the queue, like `SDL_FlushEvents`,
+/// releasing the payloads of custom events among them.
+pub(super) fn flush(min_type: u32, max_type: u32) {
+    let types = REGISTRY.lock().unwrap().types.clone();
+    let custom = types
+        .iter()
+        .copied()
+        .filter(|type_| (min_type..=max_type).contains(type_));
+    let (Some(low), Some(high)) = (custom.clone().min(), custom.max()) else {
+        unsafe { sys::events::SDL_FlushEvents(min_type, max_type) };
+        return;
+    };
+
+    // SDL flushes the range around the custom types; the custom types themselves are taken
+    // out here, so a payload is never flushed without being released.
+    unsafe {
+        if min_type < low {
+            sys::events::SDL_FlushEvents(min_type, low - 1);
+        }
+        if high < max_type {
+            sys::events::SDL_FlushEvents(high + 1, max_type);
+        }
+    }
+    let mut events: [sys::events::SDL_Event; 64] = unsafe { mem::zeroed() };
+    loop {
+        let read = unsafe {
+            sys::events::SDL_PeepEvents(
+                events.as_mut_ptr(),
+                events.len() as libc::c_int,
+                sys::events::SDL_GETEVENT,
+                low,
+                high,
+            )
+        };
+        if read <= 0 {
+            break;
+        }
+        for raw in &events[..read as usize] {
+            dequeued_raw(raw, &types);
+        }
+    }
+}
+
+/// Runs `f`, which installs an event filter, releasing the payloads of queued custom events
+/// the filter rejects.
+pub(super) fn filtering_queue<R>(f: impl FnOnce() -> R) -> R {
+    FILTERING_QUEUE.with(|filtering| filtering.set(true));
+    let result = f();
+    FILTERING_QUEUE.with(|filtering| filtering.set(false));
+    result
+}
+
+/// Called when an event filter rejects `event`.
+///
+/// Outside `filtering_queue` the rejected event is being pushed, and `end_push` accounts
+/// for it.
+pub(super) fn filter_rejected(event: &Event) {
+    if FILTERING_QUEUE.with(|filtering| filtering.get()) {
+        dequeued(event);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{begin_push, dequeued, end_push, lookup, CustomEventType, REGISTRY};
+    use crate::event::Event;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct Tracked(Arc<AtomicUsize>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    // Walks a payload through push and dequeue without touching the SDL queue.
+    #[test]
+    fn test_payload_lifetime() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let custom = CustomEventType::<Tracked>::register(0xfff0);
+        let event = custom.event(Tracked(drops.clone()));
+        let Event::Custom { ref payload, .. } = event else {
+            unreachable!()
+        };
+        let key = payload.key();
+
+        // A failed push unregisters the payload again.
+        end_push(begin_push(payload), false);
+        assert!(!REGISTRY.lock().unwrap().payloads.contains_key(&key));
+
+        end_push(begin_push(payload), true);
+        let received = Event::Custom {
+            timestamp: 0,
+            window_id: 0,
+            type_: 0xfff0,
+            code: 0,
+            payload: lookup(0xfff0, key as *mut libc::c_void).unwrap(),
+        };
+        drop(event);
+        dequeued(&received);
+        assert!(!REGISTRY.lock().unwrap().payloads.contains_key(&key));
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+        assert!(custom.with_code(3).take(&received).is_none());
+        let value = custom.take(&received).unwrap();
+        assert!(custom.take(&received).is_none());
+        drop(value);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+        drop(received);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+}
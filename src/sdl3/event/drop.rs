@@ -0,0 +1,225 @@
+//! Grouping of drag and drop events.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::Event;
+
+/// What was dropped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DropData {
+    File(PathBuf),
+    Text(String),
+}
+
+/// One file or piece of text of a completed drop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropItem {
+    pub window_id: u32,
+    pub data: DropData,
+    /// Drop position relative to the window.
+    pub x: f32,
+    pub y: f32,
+    /// The application the drop came from, if the platform reports it.
+    pub source: Option<String>,
+}
+
+#[derive(Default)]
+struct PendingDrop {
+    items: Vec<DropItem>,
+    position: Option<(f32, f32)>,
+}
+
+/// Collects the `DropFile`/`DropText` events between `DropBegin` and `DropComplete`.
+///
+/// Drops are tracked per window, so several can be in progress at once.
+///
+/// # Example
+/// ```no_run
+/// use sdl3::event::{DropData, DropSession};
+///
+/// let sdl = sdl3::init().unwrap();
+/// let mut event_pump = sdl.event_pump().unwrap();
+/// let mut drops = DropSession::new();
+///
+/// for event in event_pump.wait_iter() {
+///     if let Some(items) = drops.handle_event(&event) {
+///         for item in items {
+///             if let DropData::File(path) = item.data {
+///                 println!("{} dropped at {}, {}", path.display(), item.x, item.y);
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct DropSession {
+    pending: HashMap<u32, PendingDrop>,
+}
+
+impl DropSession {
+    pub fn new() -> DropSession {
+        DropSession::default()
+    }
+
+    /// Feeds an event into the session.
+    ///
+    /// Returns the items of a drop once its `DropComplete` arrives; a completed drop
+    /// without any items yields an empty `Vec`. Other events are ignored.
+    pub fn handle_event(&mut self, event: &Event) -> Option<Vec<DropItem>> {
+        match event {
+            Event::DropBegin { window_id, .. } => {
+                self.pending.insert(*window_id, PendingDrop::default());
+                None
+            }
+            Event::DropPosition {
+                window_id, x, y, ..
+            } => {
+                self.pending.entry(*window_id).or_default().position = Some((*x, *y));
+                None
+            }
+            Event::DropFile {
+                window_id,
+                filename,
+                x,
+                y,
+                source,
+                ..
+            } => {
+                self.push(*window_id, DropData::File(filename.into()), *x, *y, source);
+                None
+            }
+            Event::DropText {
+                window_id,
+                filename,
+                x,
+                y,
+                source,
+                ..
+            } => {
+                self.push(*window_id, DropData::Text(filename.clone()), *x, *y, source);
+                None
+            }
+            Event::DropComplete { window_id, .. } => Some(
+                self.pending
+                    .remove(window_id)
+                    .map(|drop| drop.items)
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` while a drop onto the window is in progress.
+    pub fn is_active(&self, window_id: u32) -> bool {
+        self.pending.contains_key(&window_id)
+    }
+
+    /// The last position reported while dragging over the window, for drag-over feedback.
+    pub fn position(&self, window_id: u32) -> Option<(f32, f32)> {
+        self.pending.get(&window_id)?.position
+    }
+
+    /// The items received so far for a drop still in progress.
+    pub fn items(&self, window_id: u32) -> &[DropItem] {
+        self.pending
+            .get(&window_id)
+            .map_or(&[], |drop| drop.items.as_slice())
+    }
+
+    fn push(&mut self, window_id: u32, data: DropData, x: f32, y: f32, source: &Option<String>) {
+        let drop = self.pending.entry(window_id).or_default();
+        drop.position = Some((x, y));
+        drop.items.push(DropItem {
+            window_id,
+            data,
+            x,
+            y,
+            source: source.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DropData, DropSession};
+    use crate::event::Event;
+    use std::path::PathBuf;
+
+    fn file(window_id: u32, name: &str, x: f32) -> Event {
+        Event::DropFile {
+            timestamp: 0,
+            window_id,
+            filename: name.to_owned(),
+            x,
+            y: 10.0,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn test_groups_items_per_window() {
+        let mut session = DropSession::new();
+        let events = [
+            Event::DropBegin {
+                timestamp: 0,
+                window_id: 1,
+            },
+            Event::DropBegin {
+                timestamp: 0,
+                window_id: 2,
+            },
+            Event::DropPosition {
+                timestamp: 0,
+                window_id: 1,
+                x: 4.0,
+                y: 5.0,
+                source: None,
+            },
+            file(1, "a.png", 1.0),
+            file(2, "other.txt", 2.0),
+            Event::DropText {
+                timestamp: 0,
+                window_id: 1,
+                filename: "hello".to_owned(),
+                x: 3.0,
+                y: 10.0,
+                source: Some("editor".to_owned()),
+            },
+        ];
+        for event in &events {
+            assert_eq!(session.handle_event(event), None);
+        }
+        assert!(session.is_active(1));
+        assert_eq!(session.position(1), Some((3.0, 10.0)));
+        assert_eq!(session.items(2).len(), 1);
+
+        let items = session
+            .handle_event(&Event::DropComplete {
+                timestamp: 0,
+                window_id: 1,
+            })
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].data, DropData::File(PathBuf::from("a.png")));
+        assert_eq!(items[1].data, DropData::Text("hello".to_owned()));
+        assert_eq!(items[1].source.as_deref(), Some("editor"));
+        assert!(!session.is_active(1));
+        assert!(session.is_active(2));
+    }
+
+    #[test]
+    fn test_complete_without_items() {
+        let mut session = DropSession::new();
+        session.handle_event(&Event::DropBegin {
+            timestamp: 0,
+            window_id: 1,
+        });
+        let items = session.handle_event(&Event::DropComplete {
+            timestamp: 0,
+            window_id: 1,
+        });
+        assert_eq!(items, Some(Vec::new()));
+        assert_eq!(session.items(1), &[]);
+    }
+}
@@ -3,7 +3,6 @@ Event Handling
  */
 
 use std::borrow::ToOwned;
-use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::ffi::CStr;
 use std::iter::FromIterator;
@@ -11,19 +10,21 @@ use std::marker::PhantomData;
 use std::mem;
 use std::mem::transmute;
 use std::ptr;
-use std::sync::Mutex;
 
+use crate::audio::{AudioDeviceID, AudioDirection};
 use crate::gamepad;
 use crate::gamepad::{Axis, Button};
 use crate::get_error;
 use crate::joystick;
 use crate::joystick::HatState;
 use crate::keyboard;
+use crate::keyboard::KeyboardId;
 use crate::keyboard::Keycode;
 use crate::keyboard::Mod;
 use crate::keyboard::Scancode;
 use crate::mouse;
-use crate::mouse::{MouseButton, MouseState, MouseWheelDirection};
+use crate::mouse::{MouseButton, MouseId, MouseState, MouseWheelDirection};
+use crate::pen::{PenAxis, PenId, PenInputFlags};
 use crate::sys;
 use crate::sys::events::SDL_EventFilter;
 use crate::video::Orientation;
@@ -39,36 +40,65 @@ use sys::everything::SDL_DisplayOrientation;
 use sys::stdinc::Uint16;
 use sys::video::SDL_DisplayID;
 
-struct CustomEventTypeMaps {
-    sdl_id_to_type_id: HashMap<u32, ::std::any::TypeId>,
-    type_id_to_sdl_id: HashMap<::std::any::TypeId, u32>,
-}
+mod custom;
+pub use self::custom::{CustomEventType, CustomPayload};
 
-impl CustomEventTypeMaps {
-    fn new() -> Self {
-        CustomEventTypeMaps {
-            sdl_id_to_type_id: HashMap::new(),
-            type_id_to_sdl_id: HashMap::new(),
-        }
-    }
-}
+mod drop;
+pub use self::drop::{DropData, DropItem, DropSession};
 
-lazy_static! {
-    static ref CUSTOM_EVENT_TYPES: Mutex<CustomEventTypeMaps> =
-        Mutex::new(CustomEventTypeMaps::new());
-}
+pub mod record;
+
+#[cfg(feature = "async-events")]
+mod stream;
+#[cfg(feature = "async-events")]
+pub use self::stream::{EventStream, PushCustomEvent};
 
 impl crate::EventSubsystem {
     /// Removes all events in the event queue that match the specified event type.
     #[doc(alias = "SDL_FlushEvent")]
     pub fn flush_event(&self, event_type: EventType) {
-        unsafe { sys::events::SDL_FlushEvent(event_type.into()) };
+        let event_type = u32::from(event_type);
+        custom::flush(event_type, event_type);
+        queue_drained();
     }
 
     /// Removes all events in the event queue that match the specified type range.
     #[doc(alias = "SDL_FlushEvents")]
     pub fn flush_events(&self, min_type: u32, max_type: u32) {
-        unsafe { sys::events::SDL_FlushEvents(min_type, max_type) };
+        custom::flush(min_type, max_type);
+        queue_drained();
+    }
+
+    /// Enables or disables processing of all events of the given type.
+    ///
+    /// Disabled events are never added to the event queue, and any pending events of
+    /// that type are removed from it.
+    ///
+    /// # Example
+    /// ```
+    /// use sdl3::event::EventType;
+    ///
+    /// let sdl = sdl3::init().unwrap();
+    /// let ev = sdl.event().unwrap();
+    ///
+    /// ev.set_event_enabled(EventType::MouseMotion, false);
+    /// assert!(!ev.is_event_enabled(EventType::MouseMotion));
+    /// ```
+    #[doc(alias = "SDL_SetEventEnabled")]
+    pub fn set_event_enabled(&self, event_type: EventType, enabled: bool) {
+        let event_type = u32::from(event_type);
+        if !enabled {
+            // Take queued custom events out first, so their payloads are released.
+            custom::flush(event_type, event_type);
+            queue_drained();
+        }
+        unsafe { sys::events::SDL_SetEventEnabled(event_type, enabled) };
+    }
+
+    /// Returns `true` if events of the given type are being processed.
+    #[doc(alias = "SDL_EventEnabled")]
+    pub fn is_event_enabled(&self, event_type: EventType) -> bool {
+        unsafe { sys::events::SDL_EventEnabled(event_type.into()) }
     }
 
     /// Reads the events at the front of the event queue, until the maximum amount
@@ -177,36 +207,26 @@ impl crate::EventSubsystem {
         }
     }
 
-    /// Register a custom event
+    /// Register a custom event type carrying payloads of type `T`.
     ///
-    /// It returns an error when the same type is registered twice.
+    /// Every call registers a new SDL event number, so the same `T` may be registered
+    /// several times for distinct event types. Use the returned token to push events and to
+    /// take the payload back out of received ones.
     ///
     /// # Example
     /// See [push_custom_event](#method.push_custom_event)
-    #[inline(always)]
-    pub fn register_custom_event<T: ::std::any::Any>(&self) -> Result<(), Error> {
-        use std::any::TypeId;
-        let event_id = *(unsafe { self.register_events(1) })?.first().unwrap();
-        let mut cet = CUSTOM_EVENT_TYPES.lock().unwrap();
-        let type_id = TypeId::of::<Box<T>>();
-
-        if cet.type_id_to_sdl_id.contains_key(&type_id) {
-            return Err(Error(
-                "The same event type can not be registered twice!".to_owned(),
-            ));
-        }
-
-        cet.sdl_id_to_type_id.insert(event_id, type_id);
-        cet.type_id_to_sdl_id.insert(type_id, event_id);
-
-        Ok(())
+    pub fn register_custom_event<T: ::std::any::Any + Send>(
+        &self,
+    ) -> Result<CustomEventType<T>, Error> {
+        let event_id = unsafe { self.register_event() }?;
+        Ok(CustomEventType::register(event_id))
     }
 
-    /// Push a custom event
+    /// Push a custom event of a type registered with
+    /// [register_custom_event](#method.register_custom_event).
     ///
-    /// If the event type ``T`` was not registered using
-    /// [register_custom_event](#method.register_custom_event),
-    /// this method will panic.
+    /// The payload is dropped once it's taken out of the received event, or once the event
+    /// is flushed from the queue without anyone holding on to it.
     ///
     /// # Example: pushing and receiving a custom event
     /// ```
@@ -218,20 +238,21 @@ impl crate::EventSubsystem {
     /// let ev = sdl.event().unwrap();
     /// let mut ep = sdl.event_pump().unwrap();
     ///
-    /// ev.register_custom_event::<SomeCustomEvent>().unwrap();
-    ///
-    /// let event = SomeCustomEvent { a: 42 };
+    /// let custom = ev.register_custom_event::<SomeCustomEvent>().unwrap();
     ///
-    /// ev.push_custom_event(event);
+    /// ev.push_custom_event(&custom, SomeCustomEvent { a: 42 }).unwrap();
     ///
     /// let received = ep.poll_event().unwrap(); // or within a for event in ep.poll_iter()
-    /// if received.is_user_event() {
-    ///     let e2 = received.as_user_event_type::<SomeCustomEvent>().unwrap();
+    /// if let Some(e2) = custom.take(&received) {
     ///     assert_eq!(e2.a, 42);
     /// }
     /// ```
-    pub fn push_custom_event<T: ::std::any::Any>(&self, event: T) -> Result<(), Error> {
-        self.event_sender().push_custom_event(event)
+    pub fn push_custom_event<T: ::std::any::Any + Send>(
+        &self,
+        event_type: &CustomEventType<T>,
+        payload: T,
+    ) -> Result<(), Error> {
+        self.event_sender().push_custom_event(event_type, payload)
     }
 
     /// Create an event sender that can be sent to other threads.
@@ -265,6 +286,35 @@ impl crate::EventSubsystem {
     ) -> EventWatch<'a, CB> {
         EventWatch::add(callback)
     }
+
+    /// Sets a filter that is called for every event before it is added to the event queue.
+    ///
+    /// The event is queued if the filter returns `true` and dropped otherwise. Events
+    /// already in the queue that the filter rejects are removed as well.
+    ///
+    /// There is only one filter at a time: this replaces any filter set before, and dropping
+    /// the returned guard removes the filter if it is still the one installed. Like event
+    /// watches, the filter may be called from whichever thread pushes the event.
+    ///
+    /// # Example: drop all mouse motion
+    /// ```
+    /// use sdl3::event::Event;
+    ///
+    /// let sdl = sdl3::init().unwrap();
+    /// let ev = sdl.event().unwrap();
+    ///
+    /// // `let _ = ...` is insufficient, as it is dropped immediately.
+    /// let _event_filter = ev.set_event_filter(|event: &Event| {
+    ///     !matches!(event, Event::MouseMotion { .. })
+    /// });
+    /// ```
+    #[doc(alias = "SDL_SetEventFilter")]
+    pub fn set_event_filter<'a, F: FnMut(&Event) -> bool + Send + 'a>(
+        &self,
+        filter: F,
+    ) -> EventFilter<'a, F> {
+        EventFilter::set(filter)
+    }
 }
 
 /// Types of events that can be delivered.
@@ -280,6 +330,8 @@ pub enum EventType {
     AppDidEnterBackground = sys::events::SDL_EVENT_DID_ENTER_BACKGROUND.0,
     AppWillEnterForeground = sys::events::SDL_EVENT_WILL_ENTER_FOREGROUND.0,
     AppDidEnterForeground = sys::events::SDL_EVENT_DID_ENTER_FOREGROUND.0,
+    LocaleChanged = sys::events::SDL_EVENT_LOCALE_CHANGED.0,
+    SystemThemeChanged = sys::events::SDL_EVENT_SYSTEM_THEME_CHANGED.0,
 
     DisplayAdded = sys::events::SDL_EVENT_DISPLAY_ADDED.0,
     DisplayRemoved = sys::events::SDL_EVENT_DISPLAY_REMOVED.0,
@@ -302,17 +354,40 @@ pub enum EventType {
     WindowHitTest = sys::events::SDL_EVENT_WINDOW_HIT_TEST.0,
     WindowICCProfileChanged = sys::events::SDL_EVENT_WINDOW_ICCPROF_CHANGED.0,
     WindowDisplayChanged = sys::events::SDL_EVENT_WINDOW_DISPLAY_CHANGED.0,
+    WindowMetalViewResized = sys::events::SDL_EVENT_WINDOW_METAL_VIEW_RESIZED.0,
+    WindowDisplayScaleChanged = sys::events::SDL_EVENT_WINDOW_DISPLAY_SCALE_CHANGED.0,
+    WindowSafeAreaChanged = sys::events::SDL_EVENT_WINDOW_SAFE_AREA_CHANGED.0,
+    WindowOccluded = sys::events::SDL_EVENT_WINDOW_OCCLUDED.0,
+    WindowEnterFullscreen = sys::events::SDL_EVENT_WINDOW_ENTER_FULLSCREEN.0,
+    WindowLeaveFullscreen = sys::events::SDL_EVENT_WINDOW_LEAVE_FULLSCREEN.0,
+    WindowDestroyed = sys::events::SDL_EVENT_WINDOW_DESTROYED.0,
+    WindowHDRStateChanged = sys::events::SDL_EVENT_WINDOW_HDR_STATE_CHANGED.0,
 
     // TODO: SysWM = sys::events::SDL_EVENT_SYSWM .0,
     KeyDown = sys::events::SDL_EVENT_KEY_DOWN.0,
     KeyUp = sys::events::SDL_EVENT_KEY_UP.0,
     TextEditing = sys::events::SDL_EVENT_TEXT_EDITING.0,
     TextInput = sys::events::SDL_EVENT_TEXT_INPUT.0,
+    TextEditingCandidates = sys::events::SDL_EVENT_TEXT_EDITING_CANDIDATES.0,
+    KeymapChanged = sys::events::SDL_EVENT_KEYMAP_CHANGED.0,
+    KeyboardAdded = sys::events::SDL_EVENT_KEYBOARD_ADDED.0,
+    KeyboardRemoved = sys::events::SDL_EVENT_KEYBOARD_REMOVED.0,
 
     MouseMotion = sys::events::SDL_EVENT_MOUSE_MOTION.0,
     MouseButtonDown = sys::events::SDL_EVENT_MOUSE_BUTTON_DOWN.0,
     MouseButtonUp = sys::events::SDL_EVENT_MOUSE_BUTTON_UP.0,
     MouseWheel = sys::events::SDL_EVENT_MOUSE_WHEEL.0,
+    MouseAdded = sys::events::SDL_EVENT_MOUSE_ADDED.0,
+    MouseRemoved = sys::events::SDL_EVENT_MOUSE_REMOVED.0,
+
+    PenProximityIn = sys::events::SDL_EVENT_PEN_PROXIMITY_IN.0,
+    PenProximityOut = sys::events::SDL_EVENT_PEN_PROXIMITY_OUT.0,
+    PenDown = sys::events::SDL_EVENT_PEN_DOWN.0,
+    PenUp = sys::events::SDL_EVENT_PEN_UP.0,
+    PenButtonDown = sys::events::SDL_EVENT_PEN_BUTTON_DOWN.0,
+    PenButtonUp = sys::events::SDL_EVENT_PEN_BUTTON_UP.0,
+    PenMotion = sys::events::SDL_EVENT_PEN_MOTION.0,
+    PenAxis = sys::events::SDL_EVENT_PEN_AXIS.0,
 
     JoyAxisMotion = sys::events::SDL_EVENT_JOYSTICK_AXIS_MOTION.0,
     JoyHatMotion = sys::events::SDL_EVENT_JOYSTICK_HAT_MOTION.0,
@@ -342,9 +417,11 @@ pub enum EventType {
     DropText = sys::events::SDL_EVENT_DROP_TEXT.0,
     DropBegin = sys::events::SDL_EVENT_DROP_BEGIN.0,
     DropComplete = sys::events::SDL_EVENT_DROP_COMPLETE.0,
+    DropPosition = sys::events::SDL_EVENT_DROP_POSITION.0,
 
     AudioDeviceAdded = sys::events::SDL_EVENT_AUDIO_DEVICE_ADDED.0,
     AudioDeviceRemoved = sys::events::SDL_EVENT_AUDIO_DEVICE_REMOVED.0,
+    AudioDeviceFormatChanged = sys::events::SDL_EVENT_AUDIO_DEVICE_FORMAT_CHANGED.0,
 
     RenderTargetsReset = sys::events::SDL_EVENT_RENDER_TARGETS_RESET.0,
     RenderDeviceReset = sys::events::SDL_EVENT_RENDER_DEVICE_RESET.0,
@@ -381,6 +458,8 @@ impl TryFrom<u32> for EventType {
             SDL_EVENT_DID_ENTER_BACKGROUND => AppDidEnterBackground,
             SDL_EVENT_WILL_ENTER_FOREGROUND => AppWillEnterForeground,
             SDL_EVENT_DID_ENTER_FOREGROUND => AppDidEnterForeground,
+            SDL_EVENT_LOCALE_CHANGED => LocaleChanged,
+            SDL_EVENT_SYSTEM_THEME_CHANGED => SystemThemeChanged,
 
             SDL_EVENT_DISPLAY_ADDED => DisplayAdded,
             SDL_EVENT_DISPLAY_REMOVED => DisplayRemoved,
@@ -391,6 +470,8 @@ impl TryFrom<u32> for EventType {
             SDL_EVENT_WINDOW_EXPOSED => WindowExposed,
             SDL_EVENT_WINDOW_MOVED => WindowMoved,
             SDL_EVENT_WINDOW_RESIZED => WindowResized,
+            SDL_EVENT_WINDOW_PIXEL_SIZE_CHANGED => WindowPixelSizeChanged,
+            SDL_EVENT_WINDOW_METAL_VIEW_RESIZED => WindowMetalViewResized,
             SDL_EVENT_WINDOW_MINIMIZED => WindowMinimized,
             SDL_EVENT_WINDOW_MAXIMIZED => WindowMaximized,
             SDL_EVENT_WINDOW_RESTORED => WindowRestored,
@@ -399,16 +480,41 @@ impl TryFrom<u32> for EventType {
             SDL_EVENT_WINDOW_FOCUS_GAINED => WindowFocusGained,
             SDL_EVENT_WINDOW_FOCUS_LOST => WindowFocusLost,
             SDL_EVENT_WINDOW_CLOSE_REQUESTED => WindowCloseRequested,
+            SDL_EVENT_WINDOW_HIT_TEST => WindowHitTest,
+            SDL_EVENT_WINDOW_ICCPROF_CHANGED => WindowICCProfileChanged,
+            SDL_EVENT_WINDOW_DISPLAY_CHANGED => WindowDisplayChanged,
+            SDL_EVENT_WINDOW_DISPLAY_SCALE_CHANGED => WindowDisplayScaleChanged,
+            SDL_EVENT_WINDOW_SAFE_AREA_CHANGED => WindowSafeAreaChanged,
+            SDL_EVENT_WINDOW_OCCLUDED => WindowOccluded,
+            SDL_EVENT_WINDOW_ENTER_FULLSCREEN => WindowEnterFullscreen,
+            SDL_EVENT_WINDOW_LEAVE_FULLSCREEN => WindowLeaveFullscreen,
+            SDL_EVENT_WINDOW_DESTROYED => WindowDestroyed,
+            SDL_EVENT_WINDOW_HDR_STATE_CHANGED => WindowHDRStateChanged,
 
             SDL_EVENT_KEY_DOWN => KeyDown,
             SDL_EVENT_KEY_UP => KeyUp,
             SDL_EVENT_TEXT_EDITING => TextEditing,
             SDL_EVENT_TEXT_INPUT => TextInput,
+            SDL_EVENT_TEXT_EDITING_CANDIDATES => TextEditingCandidates,
+            SDL_EVENT_KEYMAP_CHANGED => KeymapChanged,
+            SDL_EVENT_KEYBOARD_ADDED => KeyboardAdded,
+            SDL_EVENT_KEYBOARD_REMOVED => KeyboardRemoved,
 
             SDL_EVENT_MOUSE_MOTION => MouseMotion,
             SDL_EVENT_MOUSE_BUTTON_DOWN => MouseButtonDown,
             SDL_EVENT_MOUSE_BUTTON_UP => MouseButtonUp,
             SDL_EVENT_MOUSE_WHEEL => MouseWheel,
+            SDL_EVENT_MOUSE_ADDED => MouseAdded,
+            SDL_EVENT_MOUSE_REMOVED => MouseRemoved,
+
+            SDL_EVENT_PEN_PROXIMITY_IN => PenProximityIn,
+            SDL_EVENT_PEN_PROXIMITY_OUT => PenProximityOut,
+            SDL_EVENT_PEN_DOWN => PenDown,
+            SDL_EVENT_PEN_UP => PenUp,
+            SDL_EVENT_PEN_BUTTON_DOWN => PenButtonDown,
+            SDL_EVENT_PEN_BUTTON_UP => PenButtonUp,
+            SDL_EVENT_PEN_MOTION => PenMotion,
+            SDL_EVENT_PEN_AXIS => PenAxis,
 
             SDL_EVENT_JOYSTICK_AXIS_MOTION => JoyAxisMotion,
             SDL_EVENT_JOYSTICK_HAT_MOTION => JoyHatMotion,
@@ -438,9 +544,11 @@ impl TryFrom<u32> for EventType {
             SDL_EVENT_DROP_TEXT => DropText,
             SDL_EVENT_DROP_BEGIN => DropBegin,
             SDL_EVENT_DROP_COMPLETE => DropComplete,
+            SDL_EVENT_DROP_POSITION => DropPosition,
 
             SDL_EVENT_AUDIO_DEVICE_ADDED => AudioDeviceAdded,
             SDL_EVENT_AUDIO_DEVICE_REMOVED => AudioDeviceRemoved,
+            SDL_EVENT_AUDIO_DEVICE_FORMAT_CHANGED => AudioDeviceFormatChanged,
 
             SDL_EVENT_RENDER_TARGETS_RESET => RenderTargetsReset,
             SDL_EVENT_RENDER_DEVICE_RESET => RenderDeviceReset,
@@ -513,7 +621,10 @@ pub enum WindowEvent {
     Exposed,
     Moved(i32, i32),
     Resized(i32, i32),
+    /// The pixel size of the window changed, carrying the new width and height in pixels
     PixelSizeChanged(i32, i32),
+    /// The pixel size of a Metal view associated with the window changed
+    MetalViewResized,
     Minimized,
     Maximized,
     Restored,
@@ -524,7 +635,22 @@ pub enum WindowEvent {
     CloseRequested,
     HitTest(i32, i32),
     ICCProfChanged,
-    DisplayChanged(i32),
+    /// The window moved to the display with the given id
+    DisplayChanged(SDL_DisplayID),
+    /// The window's display scale changed
+    DisplayScaleChanged,
+    /// The window's safe area changed
+    SafeAreaChanged,
+    /// The window has been occluded
+    Occluded,
+    /// The window has entered fullscreen mode
+    EnterFullscreen,
+    /// The window has left fullscreen mode
+    LeaveFullscreen,
+    /// The window with the associated id is being or has been destroyed
+    Destroyed,
+    /// The window's HDR state changed, carrying whether HDR is now enabled
+    HDRStateChanged(bool),
 }
 
 impl WindowEvent {
@@ -538,6 +664,7 @@ impl WindowEvent {
                 EventType::WindowMoved => WindowEvent::Moved(data1, data2),
                 EventType::WindowResized => WindowEvent::Resized(data1, data2),
                 EventType::WindowPixelSizeChanged => WindowEvent::PixelSizeChanged(data1, data2),
+                EventType::WindowMetalViewResized => WindowEvent::MetalViewResized,
                 EventType::WindowMinimized => WindowEvent::Minimized,
                 EventType::WindowMaximized => WindowEvent::Maximized,
                 EventType::WindowRestored => WindowEvent::Restored,
@@ -548,7 +675,16 @@ impl WindowEvent {
                 EventType::WindowCloseRequested => WindowEvent::CloseRequested,
                 EventType::WindowHitTest => WindowEvent::HitTest(data1, data2),
                 EventType::WindowICCProfileChanged => WindowEvent::ICCProfChanged,
-                EventType::WindowDisplayChanged => WindowEvent::DisplayChanged(data1),
+                EventType::WindowDisplayChanged => {
+                    WindowEvent::DisplayChanged(data1 as SDL_DisplayID)
+                }
+                EventType::WindowDisplayScaleChanged => WindowEvent::DisplayScaleChanged,
+                EventType::WindowSafeAreaChanged => WindowEvent::SafeAreaChanged,
+                EventType::WindowOccluded => WindowEvent::Occluded,
+                EventType::WindowEnterFullscreen => WindowEvent::EnterFullscreen,
+                EventType::WindowLeaveFullscreen => WindowEvent::LeaveFullscreen,
+                EventType::WindowDestroyed => WindowEvent::Destroyed,
+                EventType::WindowHDRStateChanged => WindowEvent::HDRStateChanged(data1 != 0),
                 _ => WindowEvent::None,
             },
             Err(_) => WindowEvent::None,
@@ -564,6 +700,7 @@ impl WindowEvent {
             WindowEvent::Moved(d1, d2) => (EventType::WindowMoved, d1, d2),
             WindowEvent::Resized(d1, d2) => (EventType::WindowResized, d1, d2),
             WindowEvent::PixelSizeChanged(d1, d2) => (EventType::WindowPixelSizeChanged, d1, d2),
+            WindowEvent::MetalViewResized => (EventType::WindowMetalViewResized, 0, 0),
             WindowEvent::Minimized => (EventType::WindowMinimized, 0, 0),
             WindowEvent::Maximized => (EventType::WindowMaximized, 0, 0),
             WindowEvent::Restored => (EventType::WindowRestored, 0, 0),
@@ -574,7 +711,16 @@ impl WindowEvent {
             WindowEvent::CloseRequested => (EventType::WindowCloseRequested, 0, 0),
             WindowEvent::HitTest(d1, d2) => (EventType::WindowHitTest, d1, d2),
             WindowEvent::ICCProfChanged => (EventType::WindowICCProfileChanged, 0, 0),
-            WindowEvent::DisplayChanged(d1) => (EventType::WindowDisplayChanged, d1, 0),
+            WindowEvent::DisplayChanged(id) => (EventType::WindowDisplayChanged, id as i32, 0),
+            WindowEvent::DisplayScaleChanged => (EventType::WindowDisplayScaleChanged, 0, 0),
+            WindowEvent::SafeAreaChanged => (EventType::WindowSafeAreaChanged, 0, 0),
+            WindowEvent::Occluded => (EventType::WindowOccluded, 0, 0),
+            WindowEvent::EnterFullscreen => (EventType::WindowEnterFullscreen, 0, 0),
+            WindowEvent::LeaveFullscreen => (EventType::WindowLeaveFullscreen, 0, 0),
+            WindowEvent::Destroyed => (EventType::WindowDestroyed, 0, 0),
+            WindowEvent::HDRStateChanged(enabled) => {
+                (EventType::WindowHDRStateChanged, enabled as i32, 0)
+            }
         }
     }
 
@@ -588,6 +734,7 @@ impl WindowEvent {
                 | (Self::Moved(_, _), Self::Moved(_, _))
                 | (Self::Resized(_, _), Self::Resized(_, _))
                 | (Self::PixelSizeChanged(_, _), Self::PixelSizeChanged(_, _))
+                | (Self::MetalViewResized, Self::MetalViewResized)
                 | (Self::Minimized, Self::Minimized)
                 | (Self::Maximized, Self::Maximized)
                 | (Self::Restored, Self::Restored)
@@ -599,6 +746,13 @@ impl WindowEvent {
                 | (Self::HitTest(_, _), Self::HitTest(_, _))
                 | (Self::ICCProfChanged, Self::ICCProfChanged)
                 | (Self::DisplayChanged(_), Self::DisplayChanged(_))
+                | (Self::DisplayScaleChanged, Self::DisplayScaleChanged)
+                | (Self::SafeAreaChanged, Self::SafeAreaChanged)
+                | (Self::Occluded, Self::Occluded)
+                | (Self::EnterFullscreen, Self::EnterFullscreen)
+                | (Self::LeaveFullscreen, Self::LeaveFullscreen)
+                | (Self::Destroyed, Self::Destroyed)
+                | (Self::HDRStateChanged(_), Self::HDRStateChanged(_))
         )
     }
 }
@@ -627,6 +781,14 @@ pub enum Event {
     AppDidEnterForeground {
         timestamp: u64,
     },
+    /// The user's preferred locales changed.
+    LocaleChanged {
+        timestamp: u64,
+    },
+    /// The system theme (light or dark mode) changed.
+    SystemThemeChanged {
+        timestamp: u64,
+    },
 
     Window {
         timestamp: u64,
@@ -642,7 +804,7 @@ pub enum Event {
         scancode: Option<Scancode>,
         keymod: Mod,
         repeat: bool,
-        which: u32,
+        which: KeyboardId,
         raw: Uint16,
     },
     KeyUp {
@@ -652,7 +814,7 @@ pub enum Event {
         scancode: Option<Scancode>,
         keymod: Mod,
         repeat: bool,
-        which: u32,
+        which: KeyboardId,
         raw: Uint16,
     },
 
@@ -670,10 +832,37 @@ pub enum Event {
         text: String,
     },
 
+    /// The IME candidate list changed. An empty list means the candidates should be hidden.
+    TextEditingCandidates {
+        timestamp: u64,
+        window_id: u32,
+        candidates: Vec<String>,
+        /// Index of the selected candidate, or -1 if none is selected.
+        selected: i32,
+        /// Whether the candidates should be laid out horizontally.
+        horizontal: bool,
+    },
+
+    /// The keymap changed, for example because the input language or keyboard layout was
+    /// switched. Keycodes obtained earlier may no longer match the same keys.
+    KeymapChanged {
+        timestamp: u64,
+    },
+
+    /// A keyboard was connected. Enumerate current keyboards with `KeyboardUtil::keyboards`.
+    KeyboardAdded {
+        timestamp: u64,
+        which: KeyboardId,
+    },
+    KeyboardRemoved {
+        timestamp: u64,
+        which: KeyboardId,
+    },
+
     MouseMotion {
         timestamp: u64,
         window_id: u32,
-        which: u32,
+        which: MouseId,
         mousestate: MouseState,
         x: f32,
         y: f32,
@@ -684,7 +873,7 @@ pub enum Event {
     MouseButtonDown {
         timestamp: u64,
         window_id: u32,
-        which: u32,
+        which: MouseId,
         mouse_btn: MouseButton,
         clicks: u8,
         x: f32,
@@ -693,7 +882,7 @@ pub enum Event {
     MouseButtonUp {
         timestamp: u64,
         window_id: u32,
-        which: u32,
+        which: MouseId,
         mouse_btn: MouseButton,
         clicks: u8,
         x: f32,
@@ -703,7 +892,7 @@ pub enum Event {
     MouseWheel {
         timestamp: u64,
         window_id: u32,
-        which: u32,
+        which: MouseId,
         x: f32,
         y: f32,
         direction: MouseWheelDirection,
@@ -711,6 +900,88 @@ pub enum Event {
         mouse_y: f32,
     },
 
+    /// A mouse was connected. Enumerate current mice with `MouseUtil::mice`.
+    MouseAdded {
+        timestamp: u64,
+        which: MouseId,
+    },
+    MouseRemoved {
+        timestamp: u64,
+        which: MouseId,
+    },
+
+    /// A pen came within range of the tablet.
+    PenProximityIn {
+        timestamp: u64,
+        window_id: u32,
+        which: PenId,
+    },
+    /// A pen left the range of the tablet. Its ID may be reused afterwards.
+    PenProximityOut {
+        timestamp: u64,
+        window_id: u32,
+        which: PenId,
+    },
+    /// The pen tip (or the eraser, if `eraser` is set) touched the tablet.
+    PenDown {
+        timestamp: u64,
+        window_id: u32,
+        which: PenId,
+        pen_state: PenInputFlags,
+        x: f32,
+        y: f32,
+        eraser: bool,
+    },
+    /// The pen tip (or the eraser, if `eraser` is set) left the tablet.
+    PenUp {
+        timestamp: u64,
+        window_id: u32,
+        which: PenId,
+        pen_state: PenInputFlags,
+        x: f32,
+        y: f32,
+        eraser: bool,
+    },
+    /// A pen button was pressed. Buttons are numbered from 1.
+    PenButtonDown {
+        timestamp: u64,
+        window_id: u32,
+        which: PenId,
+        pen_state: PenInputFlags,
+        x: f32,
+        y: f32,
+        button: u8,
+    },
+    /// A pen button was released. Buttons are numbered from 1.
+    PenButtonUp {
+        timestamp: u64,
+        window_id: u32,
+        which: PenId,
+        pen_state: PenInputFlags,
+        x: f32,
+        y: f32,
+        button: u8,
+    },
+    PenMotion {
+        timestamp: u64,
+        window_id: u32,
+        which: PenId,
+        pen_state: PenInputFlags,
+        x: f32,
+        y: f32,
+    },
+    /// A pen axis such as pressure or tilt changed.
+    PenAxis {
+        timestamp: u64,
+        window_id: u32,
+        which: PenId,
+        pen_state: PenInputFlags,
+        x: f32,
+        y: f32,
+        axis: PenAxis,
+        value: f32,
+    },
+
     JoyAxisMotion {
         timestamp: u64,
         /// The joystick's `id`
@@ -897,19 +1168,34 @@ pub enum Event {
         num_fingers: u16,
     },
 
+    /// The clipboard or primary selection changed.
     ClipboardUpdate {
         timestamp: u64,
+        /// `true` if this application owns the clipboard contents.
+        owner: bool,
+        /// The MIME types now available on the clipboard.
+        mime_types: Vec<String>,
     },
 
+    /// A file was dropped at `x`, `y` (relative to the window).
+    ///
+    /// `source` is the application the drop came from, if the platform reports it.
     DropFile {
         timestamp: u64,
         window_id: u32,
         filename: String,
+        x: f32,
+        y: f32,
+        source: Option<String>,
     },
+    /// Text was dropped at `x`, `y` (relative to the window); `filename` holds the text.
     DropText {
         timestamp: u64,
         window_id: u32,
         filename: String,
+        x: f32,
+        y: f32,
+        source: Option<String>,
     },
     DropBegin {
         timestamp: u64,
@@ -919,16 +1205,31 @@ pub enum Event {
         timestamp: u64,
         window_id: u32,
     },
+    /// Something is being dragged over the window at `x`, `y`, for drag-over feedback.
+    DropPosition {
+        timestamp: u64,
+        window_id: u32,
+        x: f32,
+        y: f32,
+        source: Option<String>,
+    },
 
     AudioDeviceAdded {
         timestamp: u64,
-        which: u32,
-        iscapture: bool,
+        which: AudioDeviceID,
+        direction: AudioDirection,
     },
     AudioDeviceRemoved {
         timestamp: u64,
-        which: u32,
-        iscapture: bool,
+        which: AudioDeviceID,
+        direction: AudioDirection,
+    },
+    /// The format of an audio device changed, for example because the default
+    /// device it was following was switched.
+    AudioDeviceFormatChanged {
+        timestamp: u64,
+        which: AudioDeviceID,
+        direction: AudioDirection,
     },
 
     RenderTargetsReset {
@@ -947,6 +1248,17 @@ pub enum Event {
         data2: *mut c_void,
     },
 
+    /// An event of a type registered with `EventSubsystem::register_custom_event`.
+    ///
+    /// Take the payload out with `CustomEventType::take`.
+    Custom {
+        timestamp: u64,
+        window_id: u32,
+        type_: u32,
+        code: i32,
+        payload: CustomPayload,
+    },
+
     Unknown {
         timestamp: u64,
         type_: u32,
@@ -978,14 +1290,14 @@ impl Event {
         match *self {
             Event::User {
                 window_id,
-                type_: _,
+                type_,
                 code,
                 data1,
                 data2,
                 timestamp,
             } => {
                 let event = sys::events::SDL_UserEvent {
-                    r#type: sys::events::SDL_EVENT_USER.into(),
+                    r#type: type_,
                     timestamp,
                     windowID: window_id,
                     code,
@@ -1003,6 +1315,93 @@ impl Event {
                 }
             }
 
+            Event::DropBegin {
+                timestamp,
+                window_id,
+            }
+            | Event::DropComplete {
+                timestamp,
+                window_id,
+            } => {
+                let event = sys::events::SDL_DropEvent {
+                    r#type: if matches!(self, Event::DropBegin { .. }) {
+                        sys::events::SDL_EVENT_DROP_BEGIN
+                    } else {
+                        sys::events::SDL_EVENT_DROP_COMPLETE
+                    },
+                    reserved: 0,
+                    timestamp,
+                    windowID: window_id,
+                    x: 0.0,
+                    y: 0.0,
+                    source: ptr::null(),
+                    data: ptr::null(),
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_DropEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
+            // SDL doesn't copy the strings of pushed events, so only a position
+            // without a source can be converted.
+            Event::DropPosition {
+                timestamp,
+                window_id,
+                x,
+                y,
+                source: None,
+            } => {
+                let event = sys::events::SDL_DropEvent {
+                    r#type: sys::events::SDL_EVENT_DROP_POSITION,
+                    reserved: 0,
+                    timestamp,
+                    windowID: window_id,
+                    x,
+                    y,
+                    source: ptr::null(),
+                    data: ptr::null(),
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_DropEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
+            Event::Custom {
+                timestamp,
+                window_id,
+                type_,
+                code,
+                ref payload,
+            } => {
+                let event = sys::events::SDL_UserEvent {
+                    r#type: type_,
+                    timestamp,
+                    windowID: window_id,
+                    code,
+                    data1: payload.key() as *mut c_void,
+                    data2: ptr::null_mut(),
+                    reserved: 0,
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_UserEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
             Event::Quit { timestamp } => {
                 let event = sys::events::SDL_QuitEvent {
                     r#type: sys::events::SDL_EVENT_QUIT,
@@ -1019,6 +1418,39 @@ impl Event {
                 }
             }
 
+            Event::LocaleChanged { timestamp } => {
+                common_to_ll(sys::events::SDL_EVENT_LOCALE_CHANGED, timestamp)
+            }
+            Event::SystemThemeChanged { timestamp } => {
+                common_to_ll(sys::events::SDL_EVENT_SYSTEM_THEME_CHANGED, timestamp)
+            }
+            Event::KeymapChanged { timestamp } => {
+                common_to_ll(sys::events::SDL_EVENT_KEYMAP_CHANGED, timestamp)
+            }
+            // The MIME type strings would have to outlive the queued event.
+            Event::ClipboardUpdate {
+                timestamp,
+                owner,
+                ref mime_types,
+            } if mime_types.is_empty() => {
+                let event = sys::events::SDL_ClipboardEvent {
+                    r#type: sys::events::SDL_EVENT_CLIPBOARD_UPDATE,
+                    reserved: 0,
+                    timestamp,
+                    owner,
+                    num_mime_types: 0,
+                    mime_types: ptr::null_mut(),
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_ClipboardEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
             Event::Window {
                 timestamp,
                 window_id,
@@ -1499,6 +1931,295 @@ impl Event {
                 }
             }
 
+            Event::AudioDeviceAdded {
+                timestamp,
+                which,
+                direction,
+            }
+            | Event::AudioDeviceRemoved {
+                timestamp,
+                which,
+                direction,
+            }
+            | Event::AudioDeviceFormatChanged {
+                timestamp,
+                which,
+                direction,
+            } => {
+                let event_type = match self {
+                    Event::AudioDeviceAdded { .. } => sys::events::SDL_EVENT_AUDIO_DEVICE_ADDED,
+                    Event::AudioDeviceRemoved { .. } => sys::events::SDL_EVENT_AUDIO_DEVICE_REMOVED,
+                    _ => sys::events::SDL_EVENT_AUDIO_DEVICE_FORMAT_CHANGED,
+                };
+                let event = sys::events::SDL_AudioDeviceEvent {
+                    r#type: event_type,
+                    reserved: 0,
+                    timestamp,
+                    which: which.id(),
+                    recording: direction.is_recording(),
+                    padding1: 0,
+                    padding2: 0,
+                    padding3: 0,
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_AudioDeviceEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
+            Event::KeyboardAdded { timestamp, which } => {
+                let event = sys::events::SDL_KeyboardDeviceEvent {
+                    r#type: sys::events::SDL_EVENT_KEYBOARD_ADDED,
+                    reserved: 0,
+                    timestamp,
+                    which,
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_KeyboardDeviceEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
+            Event::KeyboardRemoved { timestamp, which } => {
+                let event = sys::events::SDL_KeyboardDeviceEvent {
+                    r#type: sys::events::SDL_EVENT_KEYBOARD_REMOVED,
+                    reserved: 0,
+                    timestamp,
+                    which,
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_KeyboardDeviceEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
+            Event::MouseAdded { timestamp, which } => {
+                let event = sys::events::SDL_MouseDeviceEvent {
+                    r#type: sys::events::SDL_EVENT_MOUSE_ADDED,
+                    reserved: 0,
+                    timestamp,
+                    which,
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_MouseDeviceEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
+            Event::MouseRemoved { timestamp, which } => {
+                let event = sys::events::SDL_MouseDeviceEvent {
+                    r#type: sys::events::SDL_EVENT_MOUSE_REMOVED,
+                    reserved: 0,
+                    timestamp,
+                    which,
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_MouseDeviceEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
+            Event::PenProximityIn {
+                timestamp,
+                window_id,
+                which,
+            }
+            | Event::PenProximityOut {
+                timestamp,
+                window_id,
+                which,
+            } => {
+                let event = sys::events::SDL_PenProximityEvent {
+                    r#type: if matches!(self, Event::PenProximityIn { .. }) {
+                        sys::events::SDL_EVENT_PEN_PROXIMITY_IN
+                    } else {
+                        sys::events::SDL_EVENT_PEN_PROXIMITY_OUT
+                    },
+                    reserved: 0,
+                    timestamp,
+                    windowID: window_id,
+                    which,
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_PenProximityEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
+            Event::PenDown {
+                timestamp,
+                window_id,
+                which,
+                pen_state,
+                x,
+                y,
+                eraser,
+            }
+            | Event::PenUp {
+                timestamp,
+                window_id,
+                which,
+                pen_state,
+                x,
+                y,
+                eraser,
+            } => {
+                let down = matches!(self, Event::PenDown { .. });
+                let event = sys::events::SDL_PenTouchEvent {
+                    r#type: if down {
+                        sys::events::SDL_EVENT_PEN_DOWN
+                    } else {
+                        sys::events::SDL_EVENT_PEN_UP
+                    },
+                    reserved: 0,
+                    timestamp,
+                    windowID: window_id,
+                    which,
+                    pen_state: pen_state.bits(),
+                    x,
+                    y,
+                    eraser,
+                    down,
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_PenTouchEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
+            Event::PenButtonDown {
+                timestamp,
+                window_id,
+                which,
+                pen_state,
+                x,
+                y,
+                button,
+            }
+            | Event::PenButtonUp {
+                timestamp,
+                window_id,
+                which,
+                pen_state,
+                x,
+                y,
+                button,
+            } => {
+                let down = matches!(self, Event::PenButtonDown { .. });
+                let event = sys::events::SDL_PenButtonEvent {
+                    r#type: if down {
+                        sys::events::SDL_EVENT_PEN_BUTTON_DOWN
+                    } else {
+                        sys::events::SDL_EVENT_PEN_BUTTON_UP
+                    },
+                    reserved: 0,
+                    timestamp,
+                    windowID: window_id,
+                    which,
+                    pen_state: pen_state.bits(),
+                    x,
+                    y,
+                    button,
+                    down,
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_PenButtonEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
+            Event::PenMotion {
+                timestamp,
+                window_id,
+                which,
+                pen_state,
+                x,
+                y,
+            } => {
+                let event = sys::events::SDL_PenMotionEvent {
+                    r#type: sys::events::SDL_EVENT_PEN_MOTION,
+                    reserved: 0,
+                    timestamp,
+                    windowID: window_id,
+                    which,
+                    pen_state: pen_state.bits(),
+                    x,
+                    y,
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_PenMotionEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
+            Event::PenAxis {
+                timestamp,
+                window_id,
+                which,
+                pen_state,
+                x,
+                y,
+                axis,
+                value,
+            } => {
+                let event = sys::events::SDL_PenAxisEvent {
+                    r#type: sys::events::SDL_EVENT_PEN_AXIS,
+                    reserved: 0,
+                    timestamp,
+                    windowID: window_id,
+                    which,
+                    pen_state: pen_state.bits(),
+                    x,
+                    y,
+                    axis: axis.to_ll(),
+                    value,
+                };
+                unsafe {
+                    ptr::copy(
+                        &event,
+                        ret.as_mut_ptr() as *mut sys::events::SDL_PenAxisEvent,
+                        1,
+                    );
+                    Some(ret.assume_init())
+                }
+            }
+
             Event::FingerDown { .. }
             | Event::FingerUp { .. }
             | Event::FingerMotion { .. }
@@ -1508,6 +2229,7 @@ impl Event {
             | Event::DropFile { .. }
             | Event::TextEditing { .. }
             | Event::TextInput { .. }
+            | Event::TextEditingCandidates { .. }
             | Event::Unknown { .. }
             | _ => {
                 // don't know how to convert!
@@ -1539,7 +2261,15 @@ impl Event {
                 | EventType::WindowCloseRequested
                 | EventType::WindowHitTest
                 | EventType::WindowICCProfileChanged
-                | EventType::WindowDisplayChanged => {
+                | EventType::WindowDisplayChanged
+                | EventType::WindowMetalViewResized
+                | EventType::WindowDisplayScaleChanged
+                | EventType::WindowSafeAreaChanged
+                | EventType::WindowOccluded
+                | EventType::WindowEnterFullscreen
+                | EventType::WindowLeaveFullscreen
+                | EventType::WindowDestroyed
+                | EventType::WindowHDRStateChanged => {
                     let event = raw.window;
                     Event::Window {
                         timestamp: event.timestamp,
@@ -1594,6 +2324,18 @@ impl Event {
                         timestamp: event.timestamp,
                     }
                 }
+                EventType::LocaleChanged => {
+                    let event = raw.common;
+                    Event::LocaleChanged {
+                        timestamp: event.timestamp,
+                    }
+                }
+                EventType::SystemThemeChanged => {
+                    let event = raw.common;
+                    Event::SystemThemeChanged {
+                        timestamp: event.timestamp,
+                    }
+                }
 
                 EventType::DisplayOrientation
                 | EventType::DisplayAdded
@@ -1654,6 +2396,22 @@ impl Event {
                         length: event.length,
                     }
                 }
+                EventType::TextEditingCandidates => {
+                    let event = raw.edit_candidates;
+                    Event::TextEditingCandidates {
+                        timestamp: event.timestamp,
+                        window_id: event.windowID,
+                        candidates: c_str_array(event.candidates, event.num_candidates),
+                        selected: event.selected_candidate,
+                        horizontal: event.horizontal,
+                    }
+                }
+                EventType::KeymapChanged => {
+                    let event = raw.common;
+                    Event::KeymapChanged {
+                        timestamp: event.timestamp,
+                    }
+                }
                 EventType::TextInput => {
                     let event = raw.text;
 
@@ -1726,6 +2484,131 @@ impl Event {
                     }
                 }
 
+                EventType::KeyboardAdded => {
+                    let event = raw.kdevice;
+                    Event::KeyboardAdded {
+                        timestamp: event.timestamp,
+                        which: event.which,
+                    }
+                }
+                EventType::KeyboardRemoved => {
+                    let event = raw.kdevice;
+                    Event::KeyboardRemoved {
+                        timestamp: event.timestamp,
+                        which: event.which,
+                    }
+                }
+                EventType::MouseAdded => {
+                    let event = raw.mdevice;
+                    Event::MouseAdded {
+                        timestamp: event.timestamp,
+                        which: event.which,
+                    }
+                }
+                EventType::MouseRemoved => {
+                    let event = raw.mdevice;
+                    Event::MouseRemoved {
+                        timestamp: event.timestamp,
+                        which: event.which,
+                    }
+                }
+
+                EventType::PenProximityIn => {
+                    let event = raw.pproximity;
+                    Event::PenProximityIn {
+                        timestamp: event.timestamp,
+                        window_id: event.windowID,
+                        which: event.which,
+                    }
+                }
+                EventType::PenProximityOut => {
+                    let event = raw.pproximity;
+                    Event::PenProximityOut {
+                        timestamp: event.timestamp,
+                        window_id: event.windowID,
+                        which: event.which,
+                    }
+                }
+                EventType::PenDown => {
+                    let event = raw.ptouch;
+                    Event::PenDown {
+                        timestamp: event.timestamp,
+                        window_id: event.windowID,
+                        which: event.which,
+                        pen_state: PenInputFlags::from_bits_retain(event.pen_state),
+                        x: event.x,
+                        y: event.y,
+                        eraser: event.eraser,
+                    }
+                }
+                EventType::PenUp => {
+                    let event = raw.ptouch;
+                    Event::PenUp {
+                        timestamp: event.timestamp,
+                        window_id: event.windowID,
+                        which: event.which,
+                        pen_state: PenInputFlags::from_bits_retain(event.pen_state),
+                        x: event.x,
+                        y: event.y,
+                        eraser: event.eraser,
+                    }
+                }
+                EventType::PenButtonDown => {
+                    let event = raw.pbutton;
+                    Event::PenButtonDown {
+                        timestamp: event.timestamp,
+                        window_id: event.windowID,
+                        which: event.which,
+                        pen_state: PenInputFlags::from_bits_retain(event.pen_state),
+                        x: event.x,
+                        y: event.y,
+                        button: event.button,
+                    }
+                }
+                EventType::PenButtonUp => {
+                    let event = raw.pbutton;
+                    Event::PenButtonUp {
+                        timestamp: event.timestamp,
+                        window_id: event.windowID,
+                        which: event.which,
+                        pen_state: PenInputFlags::from_bits_retain(event.pen_state),
+                        x: event.x,
+                        y: event.y,
+                        button: event.button,
+                    }
+                }
+                EventType::PenMotion => {
+                    let event = raw.pmotion;
+                    Event::PenMotion {
+                        timestamp: event.timestamp,
+                        window_id: event.windowID,
+                        which: event.which,
+                        pen_state: PenInputFlags::from_bits_retain(event.pen_state),
+                        x: event.x,
+                        y: event.y,
+                    }
+                }
+                EventType::PenAxis => {
+                    let event = raw.paxis;
+                    match PenAxis::from_ll(event.axis) {
+                        Some(axis) => Event::PenAxis {
+                            timestamp: event.timestamp,
+                            window_id: event.windowID,
+                            which: event.which,
+                            pen_state: PenInputFlags::from_bits_retain(event.pen_state),
+                            x: event.x,
+                            y: event.y,
+                            axis,
+                            value: event.value,
+                        },
+                        // An axis added in a newer SDL version.
+                        None => Event::Unknown {
+                            timestamp: event.timestamp,
+                            type_: event.r#type.into(),
+                        },
+                    }
+                }
+
                 EventType::JoyAxisMotion => {
                     let event = raw.jaxis;
                     Event::JoyAxisMotion {
@@ -1915,9 +2798,11 @@ impl Event {
                 }
 
                 EventType::ClipboardUpdate => {
-                    let event = raw.common;
+                    let event = raw.clipboard;
                     Event::ClipboardUpdate {
                         timestamp: event.timestamp,
+                        owner: event.owner,
+                        mime_types: c_str_array(event.mime_types, event.num_mime_types),
                     }
                 }
                 EventType::DropFile => {
@@ -1930,6 +2815,9 @@ impl Event {
                         timestamp: event.timestamp,
                         window_id: event.windowID,
                         filename: text,
+                        x: event.x,
+                        y: event.y,
+                        source: drop_source(event.source),
                     }
                 }
                 EventType::DropText => {
@@ -1942,6 +2830,20 @@ impl Event {
                         timestamp: event.timestamp,
                         window_id: event.windowID,
                         filename: text,
+                        x: event.x,
+                        y: event.y,
+                        source: drop_source(event.source),
+                    }
+                }
+                EventType::DropPosition => {
+                    let event = raw.drop;
+
+                    Event::DropPosition {
+                        timestamp: event.timestamp,
+                        window_id: event.windowID,
+                        x: event.x,
+                        y: event.y,
+                        source: drop_source(event.source),
                     }
                 }
                 EventType::DropBegin => {
@@ -1964,18 +2866,24 @@ impl Event {
                     let event = raw.adevice;
                     Event::AudioDeviceAdded {
                         timestamp: event.timestamp,
-                        which: event.which,
-                        // false if an audio output device, true if an audio capture device
-                        iscapture: event.recording,
+                        which: AudioDeviceID::Device(event.which),
+                        direction: AudioDirection::from_recording(event.recording),
                     }
                 }
                 EventType::AudioDeviceRemoved => {
                     let event = raw.adevice;
                     Event::AudioDeviceRemoved {
                         timestamp: event.timestamp,
-                        which: event.which,
-                        // false if an audio output device, true if an audio capture device
-                        iscapture: event.recording,
+                        which: AudioDeviceID::Device(event.which),
+                        direction: AudioDirection::from_recording(event.recording),
+                    }
+                }
+                EventType::AudioDeviceFormatChanged => {
+                    let event = raw.adevice;
+                    Event::AudioDeviceFormatChanged {
+                        timestamp: event.timestamp,
+                        which: AudioDeviceID::Device(event.which),
+                        direction: AudioDirection::from_recording(event.recording),
                     }
                 }
 
@@ -2004,6 +2912,16 @@ impl Event {
                     } else {
                         let event = raw.user;
 
+                        if let Some(payload) = custom::lookup(raw_type, event.data1) {
+                            return Event::Custom {
+                                timestamp: event.timestamp,
+                                window_id: event.windowID,
+                                type_: raw_type,
+                                code: event.code,
+                                payload,
+                            };
+                        }
+
                         Event::User {
                             timestamp: event.timestamp,
                             window_id: event.windowID,
@@ -2019,34 +2937,16 @@ impl Event {
     }
 
     pub fn is_user_event(&self) -> bool {
-        matches!(*self, Event::User { .. })
+        matches!(*self, Event::User { .. } | Event::Custom { .. })
     }
 
+    /// Takes the payload out of a custom event if it is of type `T`.
+    #[deprecated(note = "use `CustomEventType::take` with the token from `register_custom_event`")]
     pub fn as_user_event_type<T: ::std::any::Any>(&self) -> Option<T> {
-        use std::any::TypeId;
-        let type_id = TypeId::of::<Box<T>>();
-
-        let (event_id, event_box_ptr) = match *self {
-            Event::User { type_, data1, .. } => (type_, data1),
-            _ => return None,
-        };
-
-        let cet = CUSTOM_EVENT_TYPES.lock().unwrap();
-
-        let event_type_id = match cet.sdl_id_to_type_id.get(&event_id) {
-            Some(id) => id,
-            None => {
-                panic!("internal error; could not find typeid")
-            }
-        };
-
-        if &type_id != event_type_id {
-            return None;
+        match *self {
+            Event::Custom { ref payload, .. } => custom::take_payload(payload),
+            _ => None,
         }
-
-        let event_box: Box<T> = unsafe { Box::from_raw(event_box_ptr as *mut T) };
-
-        Some(*event_box)
     }
 
     /// Returns `true` if they are the same "kind" of events.
@@ -2079,16 +2979,32 @@ impl Event {
             | (Self::AppDidEnterBackground { .. }, Self::AppDidEnterBackground { .. })
             | (Self::AppWillEnterForeground { .. }, Self::AppWillEnterForeground { .. })
             | (Self::AppDidEnterForeground { .. }, Self::AppDidEnterForeground { .. })
+            | (Self::LocaleChanged { .. }, Self::LocaleChanged { .. })
+            | (Self::SystemThemeChanged { .. }, Self::SystemThemeChanged { .. })
             | (Self::Display { .. }, Self::Display { .. })
             | (Self::Window { .. }, Self::Window { .. })
             | (Self::KeyDown { .. }, Self::KeyDown { .. })
             | (Self::KeyUp { .. }, Self::KeyUp { .. })
             | (Self::TextEditing { .. }, Self::TextEditing { .. })
             | (Self::TextInput { .. }, Self::TextInput { .. })
+            | (Self::TextEditingCandidates { .. }, Self::TextEditingCandidates { .. })
+            | (Self::KeymapChanged { .. }, Self::KeymapChanged { .. })
             | (Self::MouseMotion { .. }, Self::MouseMotion { .. })
             | (Self::MouseButtonDown { .. }, Self::MouseButtonDown { .. })
             | (Self::MouseButtonUp { .. }, Self::MouseButtonUp { .. })
             | (Self::MouseWheel { .. }, Self::MouseWheel { .. })
+            | (Self::KeyboardAdded { .. }, Self::KeyboardAdded { .. })
+            | (Self::KeyboardRemoved { .. }, Self::KeyboardRemoved { .. })
+            | (Self::MouseAdded { .. }, Self::MouseAdded { .. })
+            | (Self::MouseRemoved { .. }, Self::MouseRemoved { .. })
+            | (Self::PenProximityIn { .. }, Self::PenProximityIn { .. })
+            | (Self::PenProximityOut { .. }, Self::PenProximityOut { .. })
+            | (Self::PenDown { .. }, Self::PenDown { .. })
+            | (Self::PenUp { .. }, Self::PenUp { .. })
+            | (Self::PenButtonDown { .. }, Self::PenButtonDown { .. })
+            | (Self::PenButtonUp { .. }, Self::PenButtonUp { .. })
+            | (Self::PenMotion { .. }, Self::PenMotion { .. })
+            | (Self::PenAxis { .. }, Self::PenAxis { .. })
             | (Self::JoyAxisMotion { .. }, Self::JoyAxisMotion { .. })
             | (Self::JoyHatMotion { .. }, Self::JoyHatMotion { .. })
             | (Self::JoyButtonDown { .. }, Self::JoyButtonDown { .. })
@@ -2111,11 +3027,14 @@ impl Event {
             | (Self::DropText { .. }, Self::DropText { .. })
             | (Self::DropBegin { .. }, Self::DropBegin { .. })
             | (Self::DropComplete { .. }, Self::DropComplete { .. })
+            | (Self::DropPosition { .. }, Self::DropPosition { .. })
             | (Self::AudioDeviceAdded { .. }, Self::AudioDeviceAdded { .. })
             | (Self::AudioDeviceRemoved { .. }, Self::AudioDeviceRemoved { .. })
+            | (Self::AudioDeviceFormatChanged { .. }, Self::AudioDeviceFormatChanged { .. })
             | (Self::RenderTargetsReset { .. }, Self::RenderTargetsReset { .. })
             | (Self::RenderDeviceReset { .. }, Self::RenderDeviceReset { .. })
             | (Self::User { .. }, Self::User { .. })
+            | (Self::Custom { .. }, Self::Custom { .. })
             | (Self::Unknown { .. }, Self::Unknown { .. }) => true,
             #[cfg(feature = "hidapi")]
             (Self::ControllerSensorUpdated { .. }, Self::ControllerSensorUpdated { .. }) => true,
@@ -2147,15 +3066,31 @@ impl Event {
             Self::AppDidEnterBackground { timestamp, .. } => timestamp,
             Self::AppWillEnterForeground { timestamp, .. } => timestamp,
             Self::AppDidEnterForeground { timestamp, .. } => timestamp,
+            Self::LocaleChanged { timestamp, .. } => timestamp,
+            Self::SystemThemeChanged { timestamp, .. } => timestamp,
             Self::Display { timestamp, .. } => timestamp,
             Self::KeyDown { timestamp, .. } => timestamp,
             Self::KeyUp { timestamp, .. } => timestamp,
             Self::TextEditing { timestamp, .. } => timestamp,
             Self::TextInput { timestamp, .. } => timestamp,
+            Self::TextEditingCandidates { timestamp, .. } => timestamp,
+            Self::KeymapChanged { timestamp, .. } => timestamp,
             Self::MouseMotion { timestamp, .. } => timestamp,
             Self::MouseButtonDown { timestamp, .. } => timestamp,
             Self::MouseButtonUp { timestamp, .. } => timestamp,
             Self::MouseWheel { timestamp, .. } => timestamp,
+            Self::KeyboardAdded { timestamp, .. } => timestamp,
+            Self::KeyboardRemoved { timestamp, .. } => timestamp,
+            Self::MouseAdded { timestamp, .. } => timestamp,
+            Self::MouseRemoved { timestamp, .. } => timestamp,
+            Self::PenProximityIn { timestamp, .. } => timestamp,
+            Self::PenProximityOut { timestamp, .. } => timestamp,
+            Self::PenDown { timestamp, .. } => timestamp,
+            Self::PenUp { timestamp, .. } => timestamp,
+            Self::PenButtonDown { timestamp, .. } => timestamp,
+            Self::PenButtonUp { timestamp, .. } => timestamp,
+            Self::PenMotion { timestamp, .. } => timestamp,
+            Self::PenAxis { timestamp, .. } => timestamp,
             Self::JoyAxisMotion { timestamp, .. } => timestamp,
             Self::JoyHatMotion { timestamp, .. } => timestamp,
             Self::JoyButtonDown { timestamp, .. } => timestamp,
@@ -2183,11 +3118,14 @@ impl Event {
             Self::DropText { timestamp, .. } => timestamp,
             Self::DropBegin { timestamp, .. } => timestamp,
             Self::DropComplete { timestamp, .. } => timestamp,
+            Self::DropPosition { timestamp, .. } => timestamp,
             Self::AudioDeviceAdded { timestamp, .. } => timestamp,
             Self::AudioDeviceRemoved { timestamp, .. } => timestamp,
+            Self::AudioDeviceFormatChanged { timestamp, .. } => timestamp,
             Self::RenderTargetsReset { timestamp, .. } => timestamp,
             Self::RenderDeviceReset { timestamp, .. } => timestamp,
             Self::User { timestamp, .. } => timestamp,
+            Self::Custom { timestamp, .. } => timestamp,
             Self::Unknown { timestamp, .. } => timestamp,
         }
     }
@@ -2219,15 +3157,26 @@ impl Event {
             Self::KeyUp { window_id, .. } => Some(*window_id),
             Self::TextEditing { window_id, .. } => Some(*window_id),
             Self::TextInput { window_id, .. } => Some(*window_id),
+            Self::TextEditingCandidates { window_id, .. } => Some(*window_id),
             Self::MouseMotion { window_id, .. } => Some(*window_id),
             Self::MouseButtonDown { window_id, .. } => Some(*window_id),
             Self::MouseButtonUp { window_id, .. } => Some(*window_id),
             Self::MouseWheel { window_id, .. } => Some(*window_id),
+            Self::PenProximityIn { window_id, .. } => Some(*window_id),
+            Self::PenProximityOut { window_id, .. } => Some(*window_id),
+            Self::PenDown { window_id, .. } => Some(*window_id),
+            Self::PenUp { window_id, .. } => Some(*window_id),
+            Self::PenButtonDown { window_id, .. } => Some(*window_id),
+            Self::PenButtonUp { window_id, .. } => Some(*window_id),
+            Self::PenMotion { window_id, .. } => Some(*window_id),
+            Self::PenAxis { window_id, .. } => Some(*window_id),
             Self::DropFile { window_id, .. } => Some(*window_id),
             Self::DropText { window_id, .. } => Some(*window_id),
             Self::DropBegin { window_id, .. } => Some(*window_id),
             Self::DropComplete { window_id, .. } => Some(*window_id),
+            Self::DropPosition { window_id, .. } => Some(*window_id),
             Self::User { window_id, .. } => Some(*window_id),
+            Self::Custom { window_id, .. } => Some(*window_id),
             _ => None,
         }
     }
@@ -2266,6 +3215,8 @@ impl Event {
                 | Self::AppDidEnterBackground { .. }
                 | Self::AppWillEnterForeground { .. }
                 | Self::AppDidEnterForeground { .. }
+                | Self::LocaleChanged { .. }
+                | Self::SystemThemeChanged { .. }
                 | Self::Window { .. }
         )
     }
@@ -2296,7 +3247,14 @@ impl Event {
     /// assert!(another_ev.is_keyboard() == false); // Not a keyboard event!
     /// ```
     pub fn is_keyboard(&self) -> bool {
-        matches!(self, Self::KeyDown { .. } | Self::KeyUp { .. })
+        matches!(
+            self,
+            Self::KeyDown { .. }
+                | Self::KeyUp { .. }
+                | Self::KeyboardAdded { .. }
+                | Self::KeyboardRemoved { .. }
+                | Self::KeymapChanged { .. }
+        )
     }
 
     /// Returns `true` if this is a text event.
@@ -2319,7 +3277,10 @@ impl Event {
     /// assert!(another_ev.is_text() == false); // Not a text event!
     /// ```
     pub fn is_text(&self) -> bool {
-        matches!(self, Self::TextEditing { .. } | Self::TextInput { .. })
+        matches!(
+            self,
+            Self::TextEditing { .. } | Self::TextInput { .. } | Self::TextEditingCandidates { .. }
+        )
     }
 
     /// Returns `true` if this is a mouse event.
@@ -2354,6 +3315,47 @@ impl Event {
                 | Self::MouseButtonDown { .. }
                 | Self::MouseButtonUp { .. }
                 | Self::MouseWheel { .. }
+                | Self::MouseAdded { .. }
+                | Self::MouseRemoved { .. }
+        )
+    }
+
+    /// Returns `true` if this is a pen event.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sdl3::event::Event;
+    /// use sdl3::pen::{PenAxis, PenInputFlags};
+    ///
+    /// let ev = Event::PenAxis {
+    ///     timestamp: 0,
+    ///     window_id: 0,
+    ///     which: 1,
+    ///     pen_state: PenInputFlags::DOWN,
+    ///     x: 0.0,
+    ///     y: 0.0,
+    ///     axis: PenAxis::Pressure,
+    ///     value: 0.5,
+    /// };
+    /// assert!(ev.is_pen());
+    ///
+    /// let another_ev = Event::Quit {
+    ///     timestamp: 0,
+    /// };
+    /// assert!(another_ev.is_pen() == false); // Not a pen event!
+    /// ```
+    pub fn is_pen(&self) -> bool {
+        matches!(
+            self,
+            Self::PenProximityIn { .. }
+                | Self::PenProximityOut { .. }
+                | Self::PenDown { .. }
+                | Self::PenUp { .. }
+                | Self::PenButtonDown { .. }
+                | Self::PenButtonUp { .. }
+                | Self::PenMotion { .. }
+                | Self::PenAxis { .. }
         )
     }
 
@@ -2474,6 +3476,7 @@ impl Event {
                 | Self::DropText { .. }
                 | Self::DropBegin { .. }
                 | Self::DropComplete { .. }
+                | Self::DropPosition { .. }
         )
     }
 
@@ -2482,12 +3485,13 @@ impl Event {
     /// # Example
     ///
     /// ```
+    /// use sdl3::audio::{AudioDeviceID, AudioDirection};
     /// use sdl3::event::Event;
     ///
     /// let ev = Event::AudioDeviceAdded {
     ///     timestamp: 0,
-    ///     which: 3,
-    ///     iscapture: false,
+    ///     which: AudioDeviceID::Device(3),
+    ///     direction: AudioDirection::Playback,
     /// };
     /// assert!(ev.is_audio());
     ///
@@ -2499,7 +3503,9 @@ impl Event {
     pub fn is_audio(&self) -> bool {
         matches!(
             self,
-            Self::AudioDeviceAdded { .. } | Self::AudioDeviceRemoved { .. }
+            Self::AudioDeviceAdded { .. }
+                | Self::AudioDeviceRemoved { .. }
+                | Self::AudioDeviceFormatChanged { .. }
         )
     }
 
@@ -2550,7 +3556,7 @@ impl Event {
     /// assert!(another_ev.is_user() == false); // Not a user event!
     /// ```
     pub fn is_user(&self) -> bool {
-        matches!(self, Self::User { .. })
+        matches!(self, Self::User { .. } | Self::Custom { .. })
     }
 
     /// Returns `true` if this is an unknown event.
@@ -2576,12 +3582,64 @@ impl Event {
     }
 }
 
+/// Converts a C array of `len` strings, which may be null if `len` is 0.
+unsafe fn c_str_array(strings: *const *const libc::c_char, len: i32) -> Vec<String> {
+    if strings.is_null() {
+        return Vec::new();
+    }
+    (0..len as usize)
+        .map(|i| {
+            CStr::from_ptr(*strings.add(i))
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect()
+}
+
+/// Builds an event that carries nothing but its type and timestamp.
+fn common_to_ll(
+    type_: sys::events::SDL_EventType,
+    timestamp: u64,
+) -> Option<sys::events::SDL_Event> {
+    let mut ret = mem::MaybeUninit::uninit();
+    let event = sys::events::SDL_CommonEvent {
+        r#type: type_.into(),
+        reserved: 0,
+        timestamp,
+    };
+    unsafe {
+        ptr::copy(
+            &event,
+            ret.as_mut_ptr() as *mut sys::events::SDL_CommonEvent,
+            1,
+        );
+        Some(ret.assume_init())
+    }
+}
+
+unsafe fn drop_source(source: *const libc::c_char) -> Option<String> {
+    if source.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(source).to_string_lossy().into_owned())
+    }
+}
+
+/// Called whenever events leave the queue, so pushes waiting for room can retry.
+fn queue_drained() {
+    #[cfg(feature = "async-events")]
+    stream::wake_queue_waiters();
+}
+
 unsafe fn poll_event() -> Option<Event> {
     let mut raw = mem::MaybeUninit::uninit();
     let has_pending = sys::events::SDL_PollEvent(raw.as_mut_ptr());
 
     if has_pending {
-        Some(Event::from_ll(raw.assume_init()))
+        let event = Event::from_ll(raw.assume_init());
+        custom::dequeued(&event);
+        queue_drained();
+        Some(event)
     } else {
         None
     }
@@ -2592,7 +3650,10 @@ unsafe fn wait_event() -> Event {
     let success = sys::events::SDL_WaitEvent(raw.as_mut_ptr());
 
     if success {
-        Event::from_ll(raw.assume_init())
+        let event = Event::from_ll(raw.assume_init());
+        custom::dequeued(&event);
+        queue_drained();
+        event
     } else {
         panic!("{}", get_error())
     }
@@ -2603,7 +3664,10 @@ unsafe fn wait_event_timeout(timeout: u32) -> Option<Event> {
     let success = sys::events::SDL_WaitEventTimeout(raw.as_mut_ptr(), timeout as c_int);
 
     if success {
-        Some(Event::from_ll(raw.assume_init()))
+        let event = Event::from_ll(raw.assume_init());
+        custom::dequeued(&event);
+        queue_drained();
+        Some(event)
     } else {
         None
     }
@@ -2736,10 +3800,12 @@ impl Iterator for EventWaitTimeoutIterator<'_> {
 
 #[cfg(test)]
 mod test {
+    use super::super::audio::{AudioDeviceID, AudioDirection};
     use super::super::gamepad::{Axis, Button};
     use super::super::joystick::HatState;
     use super::super::keyboard::{Keycode, Mod, Scancode};
     use super::super::mouse::{MouseButton, MouseState, MouseWheelDirection};
+    use super::super::pen::{PenAxis, PenInputFlags};
     use super::super::video::Orientation;
     use super::DisplayEvent;
     use super::Event;
@@ -2772,6 +3838,121 @@ mod test {
             let e2 = Event::from_ll(e.clone().to_ll().unwrap());
             assert_eq!(e, e2);
         }
+        for win_event in [
+            WindowEvent::DisplayChanged(3),
+            WindowEvent::Occluded,
+            WindowEvent::EnterFullscreen,
+            WindowEvent::LeaveFullscreen,
+            WindowEvent::HDRStateChanged(true),
+        ] {
+            let e = Event::Window {
+                timestamp: 0,
+                window_id: 1,
+                win_event,
+            };
+            let e2 = Event::from_ll(e.clone().to_ll().unwrap());
+            assert_eq!(e, e2);
+        }
+        for e in [
+            Event::AudioDeviceAdded {
+                timestamp: 0,
+                which: AudioDeviceID::Device(4),
+                direction: AudioDirection::Recording,
+            },
+            Event::AudioDeviceRemoved {
+                timestamp: 0,
+                which: AudioDeviceID::Device(5),
+                direction: AudioDirection::Playback,
+            },
+            Event::AudioDeviceFormatChanged {
+                timestamp: 0,
+                which: AudioDeviceID::Device(6),
+                direction: AudioDirection::Recording,
+            },
+            Event::DropBegin {
+                timestamp: 0,
+                window_id: 3,
+            },
+            Event::DropPosition {
+                timestamp: 0,
+                window_id: 3,
+                x: 12.5,
+                y: 40.0,
+                source: None,
+            },
+            Event::DropComplete {
+                timestamp: 0,
+                window_id: 3,
+            },
+            Event::KeyboardAdded {
+                timestamp: 0,
+                which: 11,
+            },
+            Event::KeyboardRemoved {
+                timestamp: 0,
+                which: 11,
+            },
+            Event::MouseAdded {
+                timestamp: 0,
+                which: 12,
+            },
+            Event::MouseRemoved {
+                timestamp: 0,
+                which: 12,
+            },
+            Event::LocaleChanged { timestamp: 0 },
+            Event::SystemThemeChanged { timestamp: 0 },
+            Event::KeymapChanged { timestamp: 0 },
+            Event::ClipboardUpdate {
+                timestamp: 0,
+                owner: true,
+                mime_types: Vec::new(),
+            },
+            Event::PenProximityIn {
+                timestamp: 0,
+                window_id: 1,
+                which: 2,
+            },
+            Event::PenDown {
+                timestamp: 0,
+                window_id: 1,
+                which: 2,
+                pen_state: PenInputFlags::DOWN | PenInputFlags::ERASER_TIP,
+                x: 3.0,
+                y: 4.0,
+                eraser: true,
+            },
+            Event::PenButtonUp {
+                timestamp: 0,
+                window_id: 1,
+                which: 2,
+                pen_state: PenInputFlags::BUTTON_2,
+                x: 3.0,
+                y: 4.0,
+                button: 2,
+            },
+            Event::PenMotion {
+                timestamp: 0,
+                window_id: 1,
+                which: 2,
+                pen_state: PenInputFlags::empty(),
+                x: 5.5,
+                y: 6.5,
+            },
+            Event::PenAxis {
+                timestamp: 0,
+                window_id: 1,
+                which: 2,
+                pen_state: PenInputFlags::DOWN,
+                x: 5.5,
+                y: 6.5,
+                axis: PenAxis::XTilt,
+                value: -30.0,
+            },
+        ] {
+            let e2 = Event::from_ll(e.clone().to_ll().unwrap());
+            assert_eq!(e, e2);
+        }
         {
             let e = Event::KeyDown {
                 timestamp: 0,
@@ -3026,9 +4207,16 @@ impl EventSender {
     /// Pushes an event to the event queue.
     #[doc(alias = "SDL_PushEvent")]
     pub fn push_event(&self, event: Event) -> Result<(), Error> {
+        let ticket = match event {
+            Event::Custom { ref payload, .. } => Some(custom::begin_push(payload)),
+            _ => None,
+        };
         match event.to_ll() {
             Some(mut raw_event) => {
                 let ok = unsafe { sys::events::SDL_PushEvent(&mut raw_event) };
+                if let Some(ticket) = ticket {
+                    custom::end_push(ticket, ok);
+                }
                 if ok {
                     Ok(())
                 } else {
@@ -3041,64 +4229,72 @@ impl EventSender {
         }
     }
 
-    /// Push a custom event
-    ///
-    /// If the event type ``T`` was not registered using
+    /// Push a custom event of a type registered with
     /// [EventSubsystem::register_custom_event]
-    /// (../struct.EventSubsystem.html#method.register_custom_event),
-    /// this method will panic.
-    ///
-    /// # Example: pushing and receiving a custom event
-    /// ```
-    /// struct SomeCustomEvent {
-    ///     a: i32
-    /// }
-    ///
-    /// let sdl = sdl3::init().unwrap();
-    /// let ev = sdl.event().unwrap();
-    /// let mut ep = sdl.event_pump().unwrap();
-    ///
-    /// ev.register_custom_event::<SomeCustomEvent>().unwrap();
-    ///
-    /// let event = SomeCustomEvent { a: 42 };
-    ///
-    /// ev.push_custom_event(event);
+    /// (../struct.EventSubsystem.html#method.register_custom_event).
     ///
-    /// let received = ep.poll_event().unwrap(); // or within a for event in ep.poll_iter()
-    /// if received.is_user_event() {
-    ///     let e2 = received.as_user_event_type::<SomeCustomEvent>().unwrap();
-    ///     assert_eq!(e2.a, 42);
-    /// }
-    /// ```
-    pub fn push_custom_event<T: ::std::any::Any>(&self, event: T) -> Result<(), Error> {
-        use std::any::TypeId;
-        let cet = CUSTOM_EVENT_TYPES.lock().unwrap();
-        let type_id = TypeId::of::<Box<T>>();
-
-        let user_event_id = *match cet.type_id_to_sdl_id.get(&type_id) {
-            Some(id) => id,
-            None => {
-                return Err(Error(
-                    "Type is not registered as a custom event type!".to_owned(),
-                ));
-            }
-        };
+    /// See [EventSubsystem::push_custom_event]
+    /// (../struct.EventSubsystem.html#method.push_custom_event) for an example.
+    pub fn push_custom_event<T: ::std::any::Any + Send>(
+        &self,
+        event_type: &CustomEventType<T>,
+        payload: T,
+    ) -> Result<(), Error> {
+        self.push_event(event_type.event(payload))
+    }
+}
 
-        let event_box = Box::new(event);
-        let event = Event::User {
-            timestamp: 0,
-            window_id: 0,
-            type_: user_event_id,
-            code: 0,
-            data1: Box::into_raw(event_box) as *mut c_void,
-            data2: ::std::ptr::null_mut(),
+/// Guard for the filter installed by [`EventSubsystem::set_event_filter`].
+///
+/// Dropping it removes the filter, unless another filter has been set in the meantime.
+/// The filter that was active before is not reinstated, as its guard may already be gone.
+pub struct EventFilter<'a, F: FnMut(&Event) -> bool + Send + 'a> {
+    callback: Box<F>,
+    _phantom: PhantomData<&'a F>,
+}
+
+impl<'a, F: FnMut(&Event) -> bool + Send + 'a> EventFilter<'a, F> {
+    fn set(callback: F) -> EventFilter<'a, F> {
+        let mut filter = EventFilter {
+            callback: Box::new(callback),
+            _phantom: PhantomData,
         };
-        drop(cet);
+        // Installing the filter runs it over the queued events.
+        custom::filtering_queue(|| unsafe {
+            sys::events::SDL_SetEventFilter(Some(event_filter_marshall::<F>), filter.userdata())
+        });
+        filter
+    }
+
+    fn userdata(&mut self) -> *mut c_void {
+        &mut *self.callback as *mut _ as *mut c_void
+    }
+}
 
-        self.push_event(event)?;
+impl<'a, F: FnMut(&Event) -> bool + Send + 'a> Drop for EventFilter<'a, F> {
+    fn drop(&mut self) {
+        let mut current: SDL_EventFilter = None;
+        let mut userdata = ptr::null_mut();
+        unsafe {
+            let installed = sys::events::SDL_GetEventFilter(&mut current, &mut userdata);
+            if installed && userdata == self.userdata() {
+                sys::events::SDL_SetEventFilter(None, ptr::null_mut());
+            }
+        }
+    }
+}
 
-        Ok(())
+extern "C" fn event_filter_marshall<F: FnMut(&Event) -> bool>(
+    user_data: *mut c_void,
+    event: *mut sdl3_sys::events::SDL_Event,
+) -> bool {
+    let f: &mut F = unsafe { &mut *(user_data as *mut _) };
+    let event = Event::from_ll(unsafe { *event });
+    let keep = f(&event);
+    if !keep {
+        custom::filter_rejected(&event);
     }
+    keep
 }
 
 /// A callback trait for [`EventSubsystem::add_event_watch`].
@@ -0,0 +1,804 @@
+//! Recording of input sessions and deterministic replay.
+//!
+//! An [`EventRecorder`] writes events to any [`io::Write`], typically an
+//! [`IOStream`](crate::iostream::IOStream) opened on a file, and an [`EventPlayer`] reads
+//! them back and pushes them into the event queue with their original spacing.
+//!
+//! # Format
+//!
+//! A recording starts with the magic bytes `SDLEVREC` and a little endian `u16` format
+//! version. Each event follows as its SDL event type (`u32`), timestamp in nanoseconds
+//! (`u64`), the length of its data (`u16`) and the data itself: the fields of the matching
+//! SDL event structure in declaration order, little endian, leaving out the header and
+//! padding. Pointers in user events are stored as `u64` addresses.
+
+use std::ffi::c_void;
+use std::io::{self, Read, Write};
+use std::mem;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{Event, EventType};
+use crate::sys;
+use crate::sys::events::SDL_Event;
+use crate::Error;
+
+const MAGIC: &[u8; 8] = b"SDLEVREC";
+
+/// The format version written by this crate.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// A field of an SDL event structure as stored in a recording.
+trait Field: Sized {
+    fn put(self, out: &mut Vec<u8>);
+    fn get(input: &mut &[u8]) -> Result<Self, Error>;
+}
+
+fn take<const N: usize>(input: &mut &[u8]) -> Result<[u8; N], Error> {
+    if input.len() < N {
+        return Err(Error("Recorded event is truncated".to_owned()));
+    }
+    let (bytes, rest) = input.split_at(N);
+    *input = rest;
+    Ok(bytes.try_into().unwrap())
+}
+
+macro_rules! impl_field {
+    ($($ty:ty),*) => {
+        $(
+            impl Field for $ty {
+                fn put(self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn get(input: &mut &[u8]) -> Result<Self, Error> {
+                    Ok(<$ty>::from_le_bytes(take(input)?))
+                }
+            }
+        )*
+    };
+}
+
+impl_field!(u8, u16, u32, u64, i16, i32, f32);
+
+impl Field for bool {
+    fn put(self, out: &mut Vec<u8>) {
+        out.push(self as u8);
+    }
+
+    fn get(input: &mut &[u8]) -> Result<Self, Error> {
+        Ok(u8::get(input)? != 0)
+    }
+}
+
+impl Field for *mut c_void {
+    fn put(self, out: &mut Vec<u8>) {
+        (self as usize as u64).put(out);
+    }
+
+    fn get(input: &mut &[u8]) -> Result<Self, Error> {
+        Ok(u64::get(input)? as usize as *mut c_void)
+    }
+}
+
+macro_rules! impl_newtype_field {
+    ($($ty:ty),*) => {
+        $(
+            impl Field for $ty {
+                fn put(self, out: &mut Vec<u8>) {
+                    self.0.put(out);
+                }
+
+                fn get(input: &mut &[u8]) -> Result<Self, Error> {
+                    Ok(Self(Field::get(input)?))
+                }
+            }
+        )*
+    };
+}
+
+impl_newtype_field!(
+    sys::scancode::SDL_Scancode,
+    sys::mouse::SDL_MouseWheelDirection,
+    sys::pen::SDL_PenAxis
+);
+
+/// Generates the `Layout` enum along with the functions that copy the listed fields of
+/// each `SDL_Event` member to and from a recording.
+macro_rules! layouts {
+    ($($layout:ident => $member:ident { $($field:ident),* },)*) => {
+        #[derive(Copy, Clone, Debug)]
+        enum Layout {
+            $($layout,)*
+        }
+
+        unsafe fn encode_fields(layout: Layout, raw: &SDL_Event, out: &mut Vec<u8>) {
+            match layout {
+                $(Layout::$layout => {
+                    $(raw.$member.$field.put(out);)*
+                })*
+            }
+        }
+
+        unsafe fn decode_fields(
+            layout: Layout,
+            raw: &mut SDL_Event,
+            input: &mut &[u8],
+        ) -> Result<(), Error> {
+            match layout {
+                $(Layout::$layout => {
+                    $(raw.$member.$field = Field::get(input)?;)*
+                })*
+            }
+            Ok(())
+        }
+    };
+}
+
+layouts! {
+    Common => common {},
+    Clipboard => clipboard { owner },
+    Window => window { windowID, data1, data2 },
+    Key => key { windowID, which, scancode, key, r#mod, raw, down, repeat },
+    KeyboardDevice => kdevice { which },
+    MouseMotion => motion { windowID, which, state, x, y, xrel, yrel },
+    MouseButton => button { windowID, which, button, down, clicks, x, y },
+    MouseWheel => wheel { windowID, which, x, y, direction, mouse_x, mouse_y },
+    MouseDevice => mdevice { which },
+    PenProximity => pproximity { windowID, which },
+    PenTouch => ptouch { windowID, which, pen_state, x, y, eraser, down },
+    PenButton => pbutton { windowID, which, pen_state, x, y, button, down },
+    PenMotion => pmotion { windowID, which, pen_state, x, y },
+    PenAxis => paxis { windowID, which, pen_state, x, y, axis, value },
+    JoyAxis => jaxis { which, axis, value },
+    JoyHat => jhat { which, hat, value },
+    JoyButton => jbutton { which, button, down },
+    JoyDevice => jdevice { which },
+    GamepadAxis => gaxis { which, axis, value },
+    GamepadButton => gbutton { which, button, down },
+    GamepadDevice => gdevice { which },
+    AudioDevice => adevice { which, recording },
+    Drop => drop { windowID, x, y },
+    User => user { windowID, code, data1, data2 },
+}
+
+/// Returns how events of the given SDL type are stored, or `None` if they can't be.
+fn layout(raw_type: u32) -> Option<Layout> {
+    if (sys::events::SDL_EVENT_WINDOW_FIRST.0..=sys::events::SDL_EVENT_WINDOW_LAST.0)
+        .contains(&raw_type)
+    {
+        return Some(Layout::Window);
+    }
+    let event_type = match EventType::try_from(raw_type) {
+        Ok(event_type) => event_type,
+        Err(()) if raw_type >= sys::events::SDL_EVENT_USER.0 => EventType::User,
+        Err(()) => return None,
+    };
+    let layout = match event_type {
+        EventType::Quit
+        | EventType::LocaleChanged
+        | EventType::SystemThemeChanged
+        | EventType::KeymapChanged => Layout::Common,
+        EventType::ClipboardUpdate => Layout::Clipboard,
+        EventType::KeyDown | EventType::KeyUp => Layout::Key,
+        EventType::KeyboardAdded | EventType::KeyboardRemoved => Layout::KeyboardDevice,
+        EventType::MouseMotion => Layout::MouseMotion,
+        EventType::MouseButtonDown | EventType::MouseButtonUp => Layout::MouseButton,
+        EventType::MouseWheel => Layout::MouseWheel,
+        EventType::MouseAdded | EventType::MouseRemoved => Layout::MouseDevice,
+        EventType::PenProximityIn | EventType::PenProximityOut => Layout::PenProximity,
+        EventType::PenDown | EventType::PenUp => Layout::PenTouch,
+        EventType::PenButtonDown | EventType::PenButtonUp => Layout::PenButton,
+        EventType::PenMotion => Layout::PenMotion,
+        EventType::PenAxis => Layout::PenAxis,
+        EventType::JoyAxisMotion => Layout::JoyAxis,
+        EventType::JoyHatMotion => Layout::JoyHat,
+        EventType::JoyButtonDown | EventType::JoyButtonUp => Layout::JoyButton,
+        EventType::JoyDeviceAdded | EventType::JoyDeviceRemoved => Layout::JoyDevice,
+        EventType::ControllerAxisMotion => Layout::GamepadAxis,
+        EventType::ControllerButtonDown | EventType::ControllerButtonUp => Layout::GamepadButton,
+        EventType::ControllerDeviceAdded
+        | EventType::ControllerDeviceRemoved
+        | EventType::ControllerDeviceRemapped => Layout::GamepadDevice,
+        EventType::AudioDeviceAdded
+        | EventType::AudioDeviceRemoved
+        | EventType::AudioDeviceFormatChanged => Layout::AudioDevice,
+        EventType::DropBegin | EventType::DropComplete | EventType::DropPosition => Layout::Drop,
+        EventType::User => Layout::User,
+        _ => return None,
+    };
+    Some(layout)
+}
+
+fn io_error(error: io::Error) -> Error {
+    Error(format!("IO error: {}", error))
+}
+
+/// Writes events to a recording.
+///
+/// Only events that can be pushed back into the queue are recorded: those
+/// [`EventSubsystem::push_event`](crate::EventSubsystem::push_event) accepts, except
+/// [`Event::Custom`], whose payload can't be serialized.
+///
+/// # Example
+/// ```no_run
+/// use sdl3::event::record::EventRecorder;
+/// use sdl3::iostream::IOStream;
+///
+/// let sdl = sdl3::init().unwrap();
+/// let mut event_pump = sdl.event_pump().unwrap();
+/// let file = IOStream::from_file("session.rec", "wb").unwrap();
+/// let mut recorder = EventRecorder::new(file).unwrap();
+///
+/// for event in event_pump.wait_iter() {
+///     recorder.record(&event).unwrap();
+///     if let sdl3::event::Event::Quit { .. } = event {
+///         break;
+///     }
+/// }
+/// recorder.finish().unwrap();
+/// ```
+pub struct EventRecorder<W: Write> {
+    writer: W,
+    recorded: usize,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> EventRecorder<W> {
+    /// Starts a recording by writing the format header.
+    pub fn new(mut writer: W) -> Result<EventRecorder<W>, Error> {
+        writer.write_all(MAGIC).map_err(io_error)?;
+        writer
+            .write_all(&FORMAT_VERSION.to_le_bytes())
+            .map_err(io_error)?;
+        Ok(EventRecorder {
+            writer,
+            recorded: 0,
+            buf: Vec::new(),
+        })
+    }
+
+    /// Appends an event to the recording.
+    ///
+    /// Returns `Ok(false)` without writing anything if the event can't be recorded.
+    pub fn record(&mut self, event: &Event) -> Result<bool, Error> {
+        if let Event::Custom { .. } = event {
+            return Ok(false);
+        }
+        let raw = match event.to_ll() {
+            Some(raw) => raw,
+            None => return Ok(false),
+        };
+        let raw_type = unsafe { raw.r#type };
+        let layout = match layout(raw_type) {
+            Some(layout) => layout,
+            None => return Ok(false),
+        };
+
+        self.buf.clear();
+        raw_type.put(&mut self.buf);
+        unsafe { raw.common.timestamp }.put(&mut self.buf);
+        0u16.put(&mut self.buf);
+        let header_len = self.buf.len();
+        unsafe { encode_fields(layout, &raw, &mut self.buf) };
+        let body_len = (self.buf.len() - header_len) as u16;
+        self.buf[header_len - 2..header_len].copy_from_slice(&body_len.to_le_bytes());
+
+        self.writer.write_all(&self.buf).map_err(io_error)?;
+        self.recorded += 1;
+        Ok(true)
+    }
+
+    /// Returns the number of events recorded so far.
+    pub fn recorded(&self) -> usize {
+        self.recorded
+    }
+
+    /// Flushes the recording and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, Error> {
+        self.writer.flush().map_err(io_error)?;
+        Ok(self.writer)
+    }
+}
+
+struct Record {
+    raw_type: u32,
+    timestamp: u64,
+    body: Vec<u8>,
+}
+
+impl Record {
+    fn decode(&self, timestamp: u64) -> Result<Event, Error> {
+        let layout = layout(self.raw_type).ok_or_else(|| {
+            Error(format!(
+                "Recording contains unsupported event type {:#x}",
+                self.raw_type
+            ))
+        })?;
+        let mut raw: SDL_Event = unsafe { mem::zeroed() };
+        let mut input = self.body.as_slice();
+        unsafe {
+            raw.common.r#type = self.raw_type;
+            raw.common.timestamp = timestamp;
+            decode_fields(layout, &mut raw, &mut input)?;
+        }
+        if !input.is_empty() {
+            return Err(Error("Recorded event has trailing data".to_owned()));
+        }
+        Ok(Event::from_ll(raw))
+    }
+}
+
+/// Replays a recording made with an [`EventRecorder`].
+///
+/// Events are pushed with [`EventSubsystem::push_event`](crate::EventSubsystem::push_event)
+/// in the order they were recorded. The first event is due as soon as playback starts and
+/// every later one after the same delay as in the recording, divided by the
+/// [speed](EventPlayer::set_speed). Pushed events get a fresh timestamp from SDL.
+///
+/// # Example
+/// ```no_run
+/// use sdl3::event::record::EventPlayer;
+/// use sdl3::iostream::IOStream;
+///
+/// let sdl = sdl3::init().unwrap();
+/// let ev = sdl.event().unwrap();
+/// let mut event_pump = sdl.event_pump().unwrap();
+/// let file = IOStream::from_file("session.rec", "rb").unwrap();
+/// let mut player = EventPlayer::new(file).unwrap();
+///
+/// while !player.is_finished() {
+///     player.play_due(&ev).unwrap();
+///     for event in event_pump.poll_iter() {
+///         println!("{:?}", event);
+///     }
+///     std::thread::sleep(std::time::Duration::from_millis(1));
+/// }
+/// ```
+pub struct EventPlayer<R: Read> {
+    reader: R,
+    speed: f64,
+    next: Option<Record>,
+    finished: bool,
+    /// When playback started, and the timestamp of the first event.
+    start: Option<(Instant, u64)>,
+}
+
+impl<R: Read> EventPlayer<R> {
+    /// Opens a recording, checking its header.
+    pub fn new(mut reader: R) -> Result<EventPlayer<R>, Error> {
+        let mut header = [0; 10];
+        reader.read_exact(&mut header).map_err(io_error)?;
+        if &header[..8] != MAGIC {
+            return Err(Error("Not an event recording".to_owned()));
+        }
+        let version = u16::from_le_bytes([header[8], header[9]]);
+        if version != FORMAT_VERSION {
+            return Err(Error(format!(
+                "Unsupported event recording version {}",
+                version
+            )));
+        }
+        Ok(EventPlayer {
+            reader,
+            speed: 1.0,
+            next: None,
+            finished: false,
+            start: None,
+        })
+    }
+
+    /// Sets the playback speed; 2.0 replays twice as fast as recorded.
+    ///
+    /// A speed of `f64::INFINITY` makes every event due immediately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `speed` isn't positive.
+    pub fn set_speed(&mut self, speed: f64) {
+        assert!(speed > 0.0, "playback speed must be positive");
+        self.speed = speed;
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Returns `true` once every event of the recording has been read.
+    pub fn is_finished(&self) -> bool {
+        self.finished && self.next.is_none()
+    }
+
+    fn peek(&mut self) -> Result<Option<&Record>, Error> {
+        if self.next.is_none() && !self.finished {
+            self.next = self.read_record()?;
+            self.finished = self.next.is_none();
+        }
+        Ok(self.next.as_ref())
+    }
+
+    fn read_record(&mut self) -> Result<Option<Record>, Error> {
+        let mut header = [0; 14];
+        let mut filled = 0;
+        while filled < header.len() {
+            match self.reader.read(&mut header[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(Error("Recorded event is truncated".to_owned())),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(io_error(e)),
+            }
+        }
+        let mut input = &header[..];
+        let raw_type = u32::get(&mut input)?;
+        let timestamp = u64::get(&mut input)?;
+        let mut body = vec![0; u16::get(&mut input)? as usize];
+        self.reader.read_exact(&mut body).map_err(io_error)?;
+        Ok(Some(Record {
+            raw_type,
+            timestamp,
+            body,
+        }))
+    }
+
+    /// Reads the next event with its recorded timestamp, ignoring timing.
+    ///
+    /// Returns `Ok(None)` at the end of the recording.
+    pub fn next_event(&mut self) -> Result<Option<Event>, Error> {
+        self.peek()?;
+        match self.next.take() {
+            Some(record) => record.decode(record.timestamp).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// How long after the start of playback the event recorded at `timestamp` is due.
+    fn offset(&self, first: u64, timestamp: u64) -> Duration {
+        let nanos = timestamp.saturating_sub(first) as f64 / self.speed;
+        Duration::from_nanos(nanos as u64)
+    }
+
+    /// Pushes every event that is due without blocking and returns how many were pushed.
+    ///
+    /// Playback starts with the first call. Call this once per frame from the main loop.
+    pub fn play_due(&mut self, events: &crate::EventSubsystem) -> Result<usize, Error> {
+        self.play_until(events, false)
+    }
+
+    /// Pushes the rest of the recording, sleeping until each event is due.
+    ///
+    /// Returns the number of events pushed. Nothing drains the queue meanwhile, so this
+    /// suits short recordings or event watches; otherwise use
+    /// [`play_due`](Self::play_due).
+    pub fn play(&mut self, events: &crate::EventSubsystem) -> Result<usize, Error> {
+        self.play_until(events, true)
+    }
+
+    fn play_until(&mut self, events: &crate::EventSubsystem, wait: bool) -> Result<usize, Error> {
+        let mut pushed = 0;
+        while let Some(timestamp) = self.peek()?.map(|record| record.timestamp) {
+            let (started, first) = *self
+                .start
+                .get_or_insert_with(|| (Instant::now(), timestamp));
+            let due = started + self.offset(first, timestamp);
+            let now = Instant::now();
+            if due > now {
+                if !wait {
+                    break;
+                }
+                thread::sleep(due - now);
+            }
+            let record = self.next.take().unwrap();
+            events.push_event(record.decode(0)?)?;
+            pushed += 1;
+        }
+        Ok(pushed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EventPlayer, EventRecorder};
+    use crate::audio::{AudioDeviceID, AudioDirection};
+    use crate::event::{Event, WindowEvent};
+    use crate::gamepad::{Axis, Button};
+    use crate::joystick::HatState;
+    use crate::keyboard::{Keycode, Mod, Scancode};
+    use crate::mouse::{MouseButton, MouseState, MouseWheelDirection};
+    use crate::pen::{PenAxis, PenInputFlags};
+
+    fn events() -> Vec<Event> {
+        vec![
+            Event::Quit { timestamp: 1 },
+            Event::LocaleChanged { timestamp: 2 },
+            Event::ClipboardUpdate {
+                timestamp: 3,
+                owner: true,
+                mime_types: Vec::new(),
+            },
+            Event::Window {
+                timestamp: 4,
+                window_id: 2,
+                win_event: WindowEvent::Resized(640, 480),
+            },
+            Event::KeyDown {
+                timestamp: 5,
+                window_id: 2,
+                keycode: Some(Keycode::A),
+                scancode: Some(Scancode::A),
+                keymod: Mod::LSHIFTMOD,
+                repeat: true,
+                which: 3,
+                raw: 38,
+            },
+            Event::KeyboardAdded {
+                timestamp: 6,
+                which: 3,
+            },
+            Event::MouseMotion {
+                timestamp: 7,
+                window_id: 2,
+                which: 4,
+                mousestate: MouseState::from_sdl_state(1),
+                x: 10.5,
+                y: 20.25,
+                xrel: -1.0,
+                yrel: 2.0,
+            },
+            Event::MouseButtonUp {
+                timestamp: 8,
+                window_id: 2,
+                which: 4,
+                mouse_btn: MouseButton::Right,
+                clicks: 2,
+                x: 1.0,
+                y: 2.0,
+            },
+            Event::MouseWheel {
+                timestamp: 9,
+                window_id: 2,
+                which: 4,
+                x: 0.0,
+                y: -3.0,
+                direction: MouseWheelDirection::Flipped,
+                mouse_x: 5.0,
+                mouse_y: 6.0,
+            },
+            Event::PenDown {
+                timestamp: 10,
+                window_id: 2,
+                which: 5,
+                pen_state: PenInputFlags::DOWN,
+                x: 3.0,
+                y: 4.0,
+                eraser: true,
+            },
+            Event::PenAxis {
+                timestamp: 11,
+                window_id: 2,
+                which: 5,
+                pen_state: PenInputFlags::empty(),
+                x: 3.0,
+                y: 4.0,
+                axis: PenAxis::Pressure,
+                value: 0.5,
+            },
+            Event::JoyHatMotion {
+                timestamp: 12,
+                which: 6,
+                hat_idx: 1,
+                state: HatState::LeftUp,
+            },
+            Event::ControllerAxisMotion {
+                timestamp: 13,
+                which: 6,
+                axis: Axis::TriggerLeft,
+                value: -200,
+            },
+            Event::ControllerButtonDown {
+                timestamp: 14,
+                which: 6,
+                button: Button::South,
+            },
+            Event::AudioDeviceAdded {
+                timestamp: 15,
+                which: AudioDeviceID::Device(7),
+                direction: AudioDirection::Recording,
+            },
+            Event::DropPosition {
+                timestamp: 16,
+                window_id: 2,
+                x: 8.0,
+                y: 9.0,
+                source: None,
+            },
+            Event::KeyUp {
+                timestamp: 18,
+                window_id: 2,
+                keycode: Some(Keycode::Escape),
+                scancode: Some(Scancode::Escape),
+                keymod: Mod::NOMOD,
+                repeat: false,
+                which: 3,
+                raw: 9,
+            },
+            Event::KeyboardRemoved {
+                timestamp: 19,
+                which: 3,
+            },
+            Event::KeymapChanged { timestamp: 20 },
+            Event::SystemThemeChanged { timestamp: 21 },
+            Event::MouseButtonDown {
+                timestamp: 22,
+                window_id: 2,
+                which: 4,
+                mouse_btn: MouseButton::Left,
+                clicks: 1,
+                x: 7.5,
+                y: 8.5,
+            },
+            Event::MouseAdded {
+                timestamp: 23,
+                which: 4,
+            },
+            Event::MouseRemoved {
+                timestamp: 24,
+                which: 4,
+            },
+            Event::JoyAxisMotion {
+                timestamp: 25,
+                which: 6,
+                axis_idx: 2,
+                value: 1234,
+            },
+            Event::JoyButtonDown {
+                timestamp: 26,
+                which: 6,
+                button_idx: 3,
+            },
+            Event::JoyButtonUp {
+                timestamp: 27,
+                which: 6,
+                button_idx: 3,
+            },
+            Event::JoyDeviceAdded {
+                timestamp: 28,
+                which: 6,
+            },
+            Event::JoyDeviceRemoved {
+                timestamp: 29,
+                which: 6,
+            },
+            Event::ControllerButtonUp {
+                timestamp: 30,
+                which: 6,
+                button: Button::Start,
+            },
+            Event::ControllerDeviceAdded {
+                timestamp: 31,
+                which: 6,
+            },
+            Event::ControllerDeviceRemoved {
+                timestamp: 32,
+                which: 6,
+            },
+            Event::ControllerDeviceRemapped {
+                timestamp: 33,
+                which: 6,
+            },
+            Event::AudioDeviceRemoved {
+                timestamp: 34,
+                which: AudioDeviceID::Device(8),
+                direction: AudioDirection::Playback,
+            },
+            Event::AudioDeviceFormatChanged {
+                timestamp: 35,
+                which: AudioDeviceID::Device(7),
+                direction: AudioDirection::Recording,
+            },
+            Event::PenProximityIn {
+                timestamp: 36,
+                window_id: 2,
+                which: 5,
+            },
+            Event::PenProximityOut {
+                timestamp: 37,
+                window_id: 2,
+                which: 5,
+            },
+            Event::PenUp {
+                timestamp: 38,
+                window_id: 2,
+                which: 5,
+                pen_state: PenInputFlags::ERASER_TIP,
+                x: 1.5,
+                y: 2.5,
+                eraser: false,
+            },
+            Event::PenButtonDown {
+                timestamp: 39,
+                window_id: 2,
+                which: 5,
+                pen_state: PenInputFlags::BUTTON_1,
+                x: 1.5,
+                y: 2.5,
+                button: 1,
+            },
+            Event::PenButtonUp {
+                timestamp: 40,
+                window_id: 2,
+                which: 5,
+                pen_state: PenInputFlags::empty(),
+                x: 1.5,
+                y: 2.5,
+                button: 1,
+            },
+            Event::PenMotion {
+                timestamp: 41,
+                window_id: 2,
+                which: 5,
+                pen_state: PenInputFlags::DOWN,
+                x: 6.0,
+                y: 7.0,
+            },
+            Event::DropBegin {
+                timestamp: 42,
+                window_id: 2,
+            },
+            Event::DropComplete {
+                timestamp: 43,
+                window_id: 2,
+            },
+            Event::User {
+                timestamp: 17,
+                window_id: 2,
+                type_: crate::sys::events::SDL_EVENT_USER.0 + 3,
+                code: 42,
+                data1: 0x10 as *mut _,
+                data2: std::ptr::null_mut(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut recorder = EventRecorder::new(Vec::new()).unwrap();
+        for event in events() {
+            assert!(recorder.record(&event).unwrap(), "{:?}", event);
+        }
+        assert!(!recorder
+            .record(&Event::TextInput {
+                timestamp: 44,
+                window_id: 2,
+                text: "skipped".to_owned(),
+            })
+            .unwrap());
+        assert_eq!(recorder.recorded(), events().len());
+        let data = recorder.finish().unwrap();
+
+        let mut player = EventPlayer::new(data.as_slice()).unwrap();
+        let mut replayed = Vec::new();
+        while let Some(event) = player.next_event().unwrap() {
+            replayed.push(event);
+        }
+        assert!(player.is_finished());
+        assert_eq!(replayed, events());
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        assert!(EventPlayer::new(&b"NOTEVENTS\x01\x00"[..]).is_err());
+        assert!(EventPlayer::new(&b"SDLEVREC\x02\x00"[..]).is_err());
+
+        let mut recorder = EventRecorder::new(Vec::new()).unwrap();
+        recorder.record(&Event::Quit { timestamp: 0 }).unwrap();
+        let mut data = recorder.finish().unwrap();
+        data.extend_from_slice(&[1, 2, 3]);
+        let mut player = EventPlayer::new(data.as_slice()).unwrap();
+        assert_eq!(
+            player.next_event().unwrap(),
+            Some(Event::Quit { timestamp: 0 })
+        );
+        assert!(player.next_event().is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! `futures_core::Stream` adapter for the SDL event queue.
+//!
+//! SDL only gathers OS events on the main thread, so an [`EventStream`] never pumps on its
+//! own. The main thread keeps calling [`EventPump::pump_event_streams`](crate::EventPump)
+//! while the stream is polled from any executor; events reach the stream through an event
+//! watch as soon as they are queued, including events pushed from other threads with an
+//! [`EventSender`](super::EventSender).
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use futures_core::Stream;
+
+use super::{custom, CustomEventType, Event, EventSender, EventWatch};
+use crate::sys;
+use crate::Error;
+
+/// Number of live `EventStream`s; the pump hook only drains the queue while there are any.
+static LIVE_STREAMS: AtomicUsize = AtomicUsize::new(0);
+
+/// SDL drops events once this many are queued (`SDL_MAX_QUEUED_EVENTS`).
+const MAX_QUEUED_EVENTS: i32 = 65535;
+
+/// Wakers of `PushCustomEvent`s waiting for room in the full event queue.
+static QUEUE_WAITERS: Mutex<Vec<Waker>> = Mutex::new(Vec::new());
+/// Set while `QUEUE_WAITERS` is non-empty, so dequeuing doesn't lock it for nothing.
+static HAS_QUEUE_WAITERS: AtomicBool = AtomicBool::new(false);
+
+/// Wakes the pushes waiting for room, called whenever events leave the queue.
+pub(super) fn wake_queue_waiters() {
+    if !HAS_QUEUE_WAITERS.load(Ordering::SeqCst) {
+        return;
+    }
+    let waiters = {
+        let mut waiters = QUEUE_WAITERS.lock().unwrap();
+        HAS_QUEUE_WAITERS.store(false, Ordering::SeqCst);
+        std::mem::take(&mut *waiters)
+    };
+    for waker in waiters {
+        waker.wake();
+    }
+}
+
+fn queued_events() -> i32 {
+    unsafe {
+        sys::events::SDL_PeepEvents(
+            std::ptr::null_mut(),
+            0,
+            sys::events::SDL_PEEKEVENT,
+            sys::events::SDL_EVENT_FIRST.into(),
+            sys::events::SDL_EVENT_LAST.into(),
+        )
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    events: VecDeque<Event>,
+    waker: Option<Waker>,
+}
+
+type WatchCallback = Box<dyn FnMut(Event) + Send + Sync>;
+
+/// A stream of every event added to the SDL event queue.
+///
+/// Created with [`EventSubsystem::event_stream`](crate::EventSubsystem::event_stream).
+/// Each stream receives its own copy of every event queued after it was created.
+/// The stream never ends; drop it to stop receiving events.
+///
+/// # Example
+/// ```no_run
+/// let sdl = sdl3::init().unwrap();
+/// let ev = sdl.event().unwrap();
+/// let mut pump = sdl.event_pump().unwrap();
+///
+/// let mut events = ev.event_stream();
+/// // Hand `events` to the async runtime; it implements `futures_core::Stream<Item = Event>`.
+///
+/// // Meanwhile the main thread keeps gathering OS events.
+/// loop {
+///     pump.pump_event_streams();
+///     std::thread::sleep(std::time::Duration::from_millis(5));
+/// #   break;
+/// }
+/// ```
+pub struct EventStream {
+    shared: Arc<Mutex<Shared>>,
+    _watch: EventWatch<'static, WatchCallback>,
+}
+
+impl EventStream {
+    fn new() -> EventStream {
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let sink = Arc::clone(&shared);
+        let callback: WatchCallback = Box::new(move |event: Event| {
+            if let Event::Unknown { type_, .. } = event {
+                if type_ == sys::events::SDL_EVENT_POLL_SENTINEL.0 {
+                    return;
+                }
+            }
+            let mut shared = sink.lock().unwrap();
+            shared.events.push_back(event);
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+        });
+        LIVE_STREAMS.fetch_add(1, Ordering::SeqCst);
+        EventStream {
+            shared,
+            _watch: EventWatch::add(callback),
+        }
+    }
+
+    /// Takes the next event without waiting, if one has been received.
+    pub fn try_next(&mut self) -> Option<Event> {
+        self.shared.lock().unwrap().events.pop_front()
+    }
+
+    /// Returns the number of received events that haven't been taken yet.
+    pub fn pending(&self) -> usize {
+        self.shared.lock().unwrap().events.len()
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        LIVE_STREAMS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.events.pop_front() {
+            Some(event) => Poll::Ready(Some(event)),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.pending(), None)
+    }
+}
+
+impl crate::EventSubsystem {
+    /// Creates a stream of all events queued from now on.
+    ///
+    /// The main thread must keep calling
+    /// [`EventPump::pump_event_streams`](crate::EventPump::pump_event_streams) for OS events
+    /// to arrive. See [`EventStream`].
+    pub fn event_stream(&self) -> EventStream {
+        EventStream::new()
+    }
+}
+
+impl crate::EventPump {
+    /// Main-thread hook for [`EventStream`]s.
+    ///
+    /// Gathers events from the input devices, which delivers them to every live stream, then
+    /// drains the SDL event queue so it doesn't fill up. While no stream is alive this only
+    /// pumps, leaving the queue to `poll_event` and friends.
+    #[doc(alias = "SDL_PumpEvents")]
+    pub fn pump_event_streams(&mut self) {
+        self.pump_events();
+        if LIVE_STREAMS.load(Ordering::SeqCst) > 0 {
+            custom::flush(
+                sys::events::SDL_EVENT_FIRST.into(),
+                sys::events::SDL_EVENT_LAST.into(),
+            );
+            wake_queue_waiters();
+        }
+    }
+}
+
+/// Future returned by [`EventSender::push_custom_event_async`].
+#[must_use = "futures do nothing unless awaited"]
+pub struct PushCustomEvent<T> {
+    sender: EventSender,
+    event_type: CustomEventType<T>,
+    payload: Option<T>,
+}
+
+impl<T> Unpin for PushCustomEvent<T> {}
+
+impl<T: Any + Send> Future for PushCustomEvent<T> {
+    type Output = Result<(), Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if queued_events() >= MAX_QUEUED_EVENTS {
+            {
+                let mut waiters = QUEUE_WAITERS.lock().unwrap();
+                if !waiters.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    waiters.push(cx.waker().clone());
+                }
+                HAS_QUEUE_WAITERS.store(true, Ordering::SeqCst);
+            }
+            // Check again in case the queue drained before the waker was registered.
+            if queued_events() >= MAX_QUEUED_EVENTS {
+                return Poll::Pending;
+            }
+        }
+        let payload = self
+            .payload
+            .take()
+            .expect("PushCustomEvent polled after completion");
+        Poll::Ready(self.sender.push_custom_event(&self.event_type, payload))
+    }
+}
+
+impl EventSender {
+    /// Pushes a custom event, waiting for room if the event queue is full.
+    ///
+    /// Unlike [`push_custom_event`](Self::push_custom_event), which fails once SDL's queue
+    /// holds its maximum of 65535 events, the returned future stays pending until the event
+    /// fits. It can be awaited from any thread.
+    ///
+    /// The future is woken when events are taken out of the queue through
+    /// [`EventPump`](crate::EventPump) (polling, waiting or
+    /// [`pump_event_streams`](crate::EventPump::pump_event_streams)) or flushed through
+    /// [`EventSubsystem`](crate::EventSubsystem).
+    pub fn push_custom_event_async<T: Any + Send>(
+        &self,
+        event_type: &CustomEventType<T>,
+        payload: T,
+    ) -> PushCustomEvent<T> {
+        PushCustomEvent {
+            sender: EventSender { _priv: () },
+            event_type: *event_type,
+            payload: Some(payload),
+        }
+    }
+}
@@ -0,0 +1,623 @@
+//! Typed gamepad bindings and SDL mapping strings.
+//!
+//! A mapping string such as
+//! `03000000de280000ff11000001000000,Steam Virtual Gamepad,a:b0,b:b1,leftx:a0,dpup:h0.1,platform:Windows,`
+//! starts with the joystick GUID and a name, followed by `output:input` bindings that route
+//! joystick buttons (`b0`), axes (`a0`) and hat directions (`h0.1`) to gamepad buttons and axes.
+//! [`GamepadMapping`] parses and serializes this format without going through SDL, so
+//! mappings can be built and edited before they are passed to
+//! [`GamepadSubsystem::add_mapping`](crate::GamepadSubsystem::add_mapping).
+
+use super::{Axis, Button};
+use crate::guid::Guid;
+use crate::sys;
+use crate::Error;
+use std::fmt;
+use std::str::FromStr;
+use sys::gamepad::{SDL_GamepadBinding, SDL_GamepadBindingType};
+
+/// Button names used by mapping strings, in the same order as SDL's own table.
+const BUTTON_NAMES: [(&str, Button); 21] = [
+    ("a", Button::South),
+    ("b", Button::East),
+    ("x", Button::West),
+    ("y", Button::North),
+    ("back", Button::Back),
+    ("guide", Button::Guide),
+    ("start", Button::Start),
+    ("leftstick", Button::LeftStick),
+    ("rightstick", Button::RightStick),
+    ("leftshoulder", Button::LeftShoulder),
+    ("rightshoulder", Button::RightShoulder),
+    ("dpup", Button::DPadUp),
+    ("dpdown", Button::DPadDown),
+    ("dpleft", Button::DPadLeft),
+    ("dpright", Button::DPadRight),
+    ("misc1", Button::Misc1),
+    ("paddle1", Button::RightPaddle1),
+    ("paddle2", Button::LeftPaddle1),
+    ("paddle3", Button::RightPaddle2),
+    ("paddle4", Button::LeftPaddle2),
+    ("touchpad", Button::Touchpad),
+];
+
+const AXIS_NAMES: [(&str, Axis); 6] = [
+    ("leftx", Axis::LeftX),
+    ("lefty", Axis::LeftY),
+    ("rightx", Axis::RightX),
+    ("righty", Axis::RightY),
+    ("lefttrigger", Axis::TriggerLeft),
+    ("righttrigger", Axis::TriggerRight),
+];
+
+/// Which part of an axis a binding covers.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum AxisRange {
+    /// The whole axis, written without a prefix.
+    #[default]
+    Full,
+    /// The positive half, written with a `+` prefix.
+    Positive,
+    /// The negative half, written with a `-` prefix.
+    Negative,
+}
+
+impl AxisRange {
+    fn prefix(self) -> &'static str {
+        match self {
+            AxisRange::Full => "",
+            AxisRange::Positive => "+",
+            AxisRange::Negative => "-",
+        }
+    }
+
+    fn split_prefix(s: &str) -> (AxisRange, &str) {
+        if let Some(rest) = s.strip_prefix('+') {
+            (AxisRange::Positive, rest)
+        } else if let Some(rest) = s.strip_prefix('-') {
+            (AxisRange::Negative, rest)
+        } else {
+            (AxisRange::Full, s)
+        }
+    }
+
+    /// The range of an axis whose values run from `min` to `max`, where one end is 0 for
+    /// half axes.
+    fn from_bounds(min: i32, max: i32) -> AxisRange {
+        let end = if min == 0 {
+            max
+        } else if max == 0 {
+            min
+        } else {
+            return AxisRange::Full;
+        };
+
+        if end > 0 {
+            AxisRange::Positive
+        } else {
+            AxisRange::Negative
+        }
+    }
+}
+
+/// The joystick side of a [`GamepadBinding`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum BindingInput {
+    /// A joystick button, written `b<button>`.
+    Button(u32),
+    /// A joystick axis, written `a<axis>`, with a `~` suffix if it is inverted.
+    Axis {
+        axis: u32,
+        range: AxisRange,
+        inverted: bool,
+    },
+    /// A joystick hat direction, written `h<hat>.<mask>`, where `mask` is 1 for up,
+    /// 2 for right, 4 for down and 8 for left.
+    Hat { hat: u32, mask: u8 },
+}
+
+impl fmt::Display for BindingInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BindingInput::Button(button) => write!(f, "b{}", button),
+            BindingInput::Axis {
+                axis,
+                range,
+                inverted,
+            } => write!(
+                f,
+                "{}a{}{}",
+                range.prefix(),
+                axis,
+                if inverted { "~" } else { "" }
+            ),
+            BindingInput::Hat { hat, mask } => write!(f, "h{}.{}", hat, mask),
+        }
+    }
+}
+
+impl FromStr for BindingInput {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<BindingInput, Error> {
+        let invalid = || Error(format!("Invalid binding input '{}'", s));
+
+        let (range, rest) = AxisRange::split_prefix(s);
+        let (inverted, rest) = match rest.strip_suffix('~') {
+            Some(rest) => (true, rest),
+            None => (false, rest),
+        };
+
+        if let Some(axis) = rest.strip_prefix('a') {
+            let axis = parse_index(axis).ok_or_else(invalid)?;
+            return Ok(BindingInput::Axis {
+                axis,
+                range,
+                inverted,
+            });
+        }
+        if range != AxisRange::Full || inverted {
+            return Err(invalid());
+        }
+
+        if let Some(button) = rest.strip_prefix('b') {
+            parse_index(button).map(BindingInput::Button)
+        } else if let Some(hat) = rest.strip_prefix('h') {
+            hat.split_once('.').and_then(|(hat, mask)| {
+                Some(BindingInput::Hat {
+                    hat: parse_index(hat)?,
+                    mask: parse_index(mask)?.try_into().ok()?,
+                })
+            })
+        } else {
+            None
+        }
+        .ok_or_else(invalid)
+    }
+}
+
+/// The gamepad side of a [`GamepadBinding`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum BindingOutput {
+    Button(Button),
+    /// A gamepad axis. A half range means the input only drives that half of the axis.
+    Axis {
+        axis: Axis,
+        range: AxisRange,
+    },
+}
+
+impl BindingOutput {
+    /// Parse an output name, returning `Ok(None)` for names that aren't gamepad buttons
+    /// or axes.
+    fn from_name(s: &str) -> Result<Option<BindingOutput>, Error> {
+        let (range, name) = AxisRange::split_prefix(s);
+
+        if let Some(&(_, axis)) = AXIS_NAMES.iter().find(|(n, _)| *n == name) {
+            return Ok(Some(BindingOutput::Axis { axis, range }));
+        }
+        if range != AxisRange::Full {
+            return Err(Error(format!("Invalid binding output '{}'", s)));
+        }
+
+        Ok(BUTTON_NAMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|&(_, button)| BindingOutput::Button(button)))
+    }
+}
+
+impl fmt::Display for BindingOutput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BindingOutput::Button(button) => {
+                let (name, _) = BUTTON_NAMES.iter().find(|(_, b)| *b == button).unwrap();
+                f.write_str(name)
+            }
+            BindingOutput::Axis { axis, range } => {
+                let (name, _) = AXIS_NAMES.iter().find(|(_, a)| *a == axis).unwrap();
+                write!(f, "{}{}", range.prefix(), name)
+            }
+        }
+    }
+}
+
+/// One joystick input routed to a gamepad button or axis, written `output:input` in
+/// mapping strings, e.g. `a:b0` or `+leftx:h0.2`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GamepadBinding {
+    pub input: BindingInput,
+    pub output: BindingOutput,
+}
+
+impl GamepadBinding {
+    pub fn new(input: BindingInput, output: BindingOutput) -> GamepadBinding {
+        GamepadBinding { input, output }
+    }
+
+    /// Returns `None` if either side is unbound or refers to a button this crate
+    /// doesn't know.
+    pub fn from_ll(raw: &SDL_GamepadBinding) -> Option<GamepadBinding> {
+        let input = match raw.input_type {
+            SDL_GamepadBindingType::BUTTON => {
+                BindingInput::Button(u32::try_from(unsafe { raw.input.button }).ok()?)
+            }
+            SDL_GamepadBindingType::AXIS => {
+                let axis = unsafe { raw.input.axis };
+                let (min, max) = (axis.axis_min, axis.axis_max);
+                BindingInput::Axis {
+                    axis: u32::try_from(axis.axis).ok()?,
+                    range: AxisRange::from_bounds(min, max),
+                    // SDL swaps the bounds of inverted axes, which moves the 0 end of a
+                    // half axis to `axis_max`.
+                    inverted: if min == 0 || max == 0 {
+                        max == 0
+                    } else {
+                        min > max
+                    },
+                }
+            }
+            SDL_GamepadBindingType::HAT => {
+                let hat = unsafe { raw.input.hat };
+                BindingInput::Hat {
+                    hat: u32::try_from(hat.hat).ok()?,
+                    mask: u8::try_from(hat.hat_mask).ok()?,
+                }
+            }
+            _ => return None,
+        };
+
+        let output = match raw.output_type {
+            SDL_GamepadBindingType::BUTTON => {
+                BindingOutput::Button(Button::from_ll(unsafe { raw.output.button })?)
+            }
+            SDL_GamepadBindingType::AXIS => {
+                let axis = unsafe { raw.output.axis };
+                let output_axis = Axis::from_ll(axis.axis)?;
+                // Triggers always cover 0..=AXIS_MAX, which is how they are written
+                // without a prefix.
+                let range = match output_axis {
+                    Axis::TriggerLeft | Axis::TriggerRight => AxisRange::Full,
+                    _ => AxisRange::from_bounds(axis.axis_min, axis.axis_max),
+                };
+                BindingOutput::Axis {
+                    axis: output_axis,
+                    range,
+                }
+            }
+            _ => return None,
+        };
+
+        Some(GamepadBinding { input, output })
+    }
+}
+
+impl fmt::Display for GamepadBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.output, self.input)
+    }
+}
+
+impl FromStr for GamepadBinding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<GamepadBinding, Error> {
+        let (output, input) = s
+            .split_once(':')
+            .ok_or_else(|| Error(format!("Invalid binding '{}'", s)))?;
+
+        let output = BindingOutput::from_name(output)?
+            .ok_or_else(|| Error(format!("Unknown binding output '{}'", output)))?;
+
+        Ok(GamepadBinding {
+            input: input.parse()?,
+            output,
+        })
+    }
+}
+
+/// A gamepad mapping in SDL's mapping string format.
+///
+/// Serializing writes the bindings in order, then the extra fields, then the platform,
+/// with the trailing comma used by `gamecontrollerdb.txt`.
+///
+/// # Example
+/// ```
+/// use sdl3::gamepad::{BindingInput, BindingOutput, Button, GamepadMapping};
+///
+/// let mut mapping: GamepadMapping =
+///     "03000000de280000ff11000001000000,Steam Virtual Gamepad,a:b0,b:b1,platform:Windows,"
+///         .parse()
+///         .unwrap();
+/// mapping.set_binding(BindingOutput::Button(Button::South), BindingInput::Button(1));
+/// mapping.set_binding(BindingOutput::Button(Button::East), BindingInput::Button(0));
+/// assert_eq!(
+///     mapping.to_string(),
+///     "03000000de280000ff11000001000000,Steam Virtual Gamepad,a:b1,b:b0,platform:Windows,"
+/// );
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct GamepadMapping {
+    /// The joystick GUID as a hex string, or `xinput` or `*` for SDL's generic mappings.
+    pub guid: String,
+    pub name: String,
+    pub bindings: Vec<GamepadBinding>,
+    /// The `platform` field, such as `Windows`, `Mac OS X` or `Linux`.
+    pub platform: Option<String>,
+    /// Fields that aren't bindings, such as `crc` or `hint`, and bindings to gamepad
+    /// buttons this crate doesn't know, kept verbatim as key/value pairs.
+    pub extra: Vec<(String, String)>,
+}
+
+impl GamepadMapping {
+    /// Create an empty mapping for the joystick with `guid`.
+    pub fn new(guid: Guid, name: &str) -> GamepadMapping {
+        GamepadMapping {
+            guid: guid.string(),
+            name: name.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Iterate over the inputs bound to `output`.
+    pub fn inputs_for(&self, output: BindingOutput) -> impl Iterator<Item = BindingInput> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.output == output)
+            .map(|binding| binding.input)
+    }
+
+    /// Bind `input` to `output`, replacing any inputs bound to it before.
+    ///
+    /// A binding that replaces an existing one keeps its position.
+    pub fn set_binding(&mut self, output: BindingOutput, input: BindingInput) {
+        let binding = GamepadBinding { input, output };
+
+        match self.bindings.iter().position(|b| b.output == output) {
+            Some(index) => {
+                self.bindings[index] = binding;
+                let mut rest = self.bindings.split_off(index + 1);
+                rest.retain(|b| b.output != output);
+                self.bindings.append(&mut rest);
+            }
+            None => self.bindings.push(binding),
+        }
+    }
+
+    /// Remove every input bound to `output`.
+    pub fn remove_binding(&mut self, output: BindingOutput) {
+        self.bindings.retain(|binding| binding.output != output);
+    }
+}
+
+impl fmt::Display for GamepadMapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},", self.guid, self.name)?;
+        for binding in &self.bindings {
+            write!(f, "{},", binding)?;
+        }
+        for (key, value) in &self.extra {
+            write!(f, "{}:{},", key, value)?;
+        }
+        if let Some(platform) = &self.platform {
+            write!(f, "platform:{},", platform)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GamepadMapping {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<GamepadMapping, Error> {
+        let mut fields = s.trim().split(',');
+        let guid = fields.next().unwrap_or_default();
+        let name = fields
+            .next()
+            .ok_or_else(|| Error(format!("Mapping '{}' has no name", s)))?;
+        if guid.is_empty() {
+            return Err(Error(format!("Mapping '{}' has no GUID", s)));
+        }
+
+        let mut mapping = GamepadMapping {
+            guid: guid.to_owned(),
+            name: name.to_owned(),
+            ..Default::default()
+        };
+
+        for field in fields.filter(|field| !field.is_empty()) {
+            let (key, value) = field
+                .split_once(':')
+                .ok_or_else(|| Error(format!("Invalid mapping field '{}'", field)))?;
+
+            if key == "platform" {
+                mapping.platform = Some(value.to_owned());
+            } else if let Some(output) = BindingOutput::from_name(key)? {
+                mapping.bindings.push(GamepadBinding {
+                    input: value.parse()?,
+                    output,
+                });
+            } else {
+                mapping.extra.push((key.to_owned(), value.to_owned()));
+            }
+        }
+
+        Ok(mapping)
+    }
+}
+
+/// Parse a decimal index, rejecting the signs `u32::from_str` accepts.
+fn parse_index(s: &str) -> Option<u32> {
+    if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sys::gamepad::{SDL_GamepadAxis, SDL_GamepadButton};
+
+    const AXIS_MIN: i32 = sys::joystick::SDL_JOYSTICK_AXIS_MIN as i32;
+    const AXIS_MAX: i32 = sys::joystick::SDL_JOYSTICK_AXIS_MAX as i32;
+
+    // Entries in the format of SDL_GameControllerDB's gamecontrollerdb.txt.
+    const MAPPINGS: [&str; 4] = [
+        "030000004c050000cc09000000000000,PS4 Controller,a:b1,b:b2,back:b8,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b12,leftshoulder:b4,leftstick:b10,lefttrigger:a3,leftx:a0,lefty:a1,rightshoulder:b5,rightstick:b11,righttrigger:a4,rightx:a2,righty:a5,start:b9,touchpad:b13,x:b0,y:b3,platform:Windows,",
+        "030000005e0400008e02000010010000,Xbox 360 Controller,a:b0,b:b1,back:b6,dpdown:h0.4,dpleft:h0.8,dpright:h0.2,dpup:h0.1,guide:b8,leftshoulder:b4,leftstick:b9,lefttrigger:a2,leftx:a0,lefty:a1,rightshoulder:b5,rightstick:b10,righttrigger:a5,rightx:a3,righty:a4,start:b7,x:b2,y:b3,platform:Linux,",
+        "03000000790000000600000000000000,Generic USB Joystick,a:b2,b:b1,dpdown:+a1,dpleft:-a0,dpright:+a0,dpup:-a1,lefttrigger:b6,leftx:a0,lefty:a1~,-rightx:-a2,+rightx:+a2~,paddle1:b10,platform:Mac OS X,",
+        "xinput,XInput Controller,a:b0,b:b1,-leftx:h0.8,+leftx:h0.2,",
+    ];
+
+    #[test]
+    fn test_round_trip() {
+        for line in MAPPINGS {
+            let mapping: GamepadMapping = line.parse().unwrap();
+            assert!(mapping.extra.is_empty());
+            assert_eq!(mapping.to_string(), line);
+        }
+
+        let mapping: GamepadMapping = MAPPINGS[2].parse().unwrap();
+        assert_eq!(mapping.platform.as_deref(), Some("Mac OS X"));
+        assert_eq!(
+            mapping.bindings[8],
+            GamepadBinding::new(
+                BindingInput::Axis {
+                    axis: 1,
+                    range: AxisRange::Full,
+                    inverted: true,
+                },
+                BindingOutput::Axis {
+                    axis: Axis::LeftY,
+                    range: AxisRange::Full,
+                },
+            )
+        );
+        assert_eq!(
+            mapping
+                .inputs_for(BindingOutput::Button(Button::RightPaddle1))
+                .collect::<Vec<_>>(),
+            [BindingInput::Button(10)]
+        );
+
+        // Unknown fields survive, but are written after the bindings.
+        let line = "03000000d62000000228000000000000,Pad,crc:ac52,a:b0,misc2:b9,leftx:a0,hint:!SDL_GAMECONTROLLER_USE_BUTTON_LABELS:=1,platform:Windows";
+        let mapping: GamepadMapping = line.parse().unwrap();
+        assert_eq!(
+            mapping.extra,
+            [
+                ("crc".to_owned(), "ac52".to_owned()),
+                ("misc2".to_owned(), "b9".to_owned()),
+                (
+                    "hint".to_owned(),
+                    "!SDL_GAMECONTROLLER_USE_BUTTON_LABELS:=1".to_owned()
+                ),
+            ]
+        );
+        assert_eq!(
+            mapping.to_string().parse::<GamepadMapping>().unwrap(),
+            mapping
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        for line in [
+            "030000004c050000cc09000000000000",
+            ",PS4 Controller,a:b1,",
+            "guid,Pad,a,",
+            "guid,Pad,a:x1,",
+            "guid,Pad,a:+b1,",
+            "guid,Pad,a:b1~,",
+            "guid,Pad,a:b+1,",
+            "guid,Pad,+a:b1,",
+            "guid,Pad,dpup:h0,",
+            "guid,Pad,dpup:h0.256,",
+        ] {
+            assert!(line.parse::<GamepadMapping>().is_err(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_set_binding() {
+        let mut mapping: GamepadMapping = "guid,Pad,a:b0,b:b1,a:b2,x:b3,".parse().unwrap();
+        mapping.set_binding(
+            BindingOutput::Button(Button::South),
+            BindingInput::Button(5),
+        );
+        assert_eq!(mapping.to_string(), "guid,Pad,a:b5,b:b1,x:b3,");
+
+        mapping.set_binding(
+            BindingOutput::Axis {
+                axis: Axis::LeftX,
+                range: AxisRange::Negative,
+            },
+            BindingInput::Hat { hat: 0, mask: 8 },
+        );
+        mapping.remove_binding(BindingOutput::Button(Button::East));
+        assert_eq!(mapping.to_string(), "guid,Pad,a:b5,x:b3,-leftx:h0.8,");
+    }
+
+    fn axis_binding(input: (i32, i32), output: (SDL_GamepadAxis, i32, i32)) -> SDL_GamepadBinding {
+        let mut raw = SDL_GamepadBinding {
+            input_type: SDL_GamepadBindingType::AXIS,
+            output_type: SDL_GamepadBindingType::AXIS,
+            ..Default::default()
+        };
+        raw.input.axis.axis = 2;
+        raw.input.axis.axis_min = input.0;
+        raw.input.axis.axis_max = input.1;
+        raw.output.axis.axis = output.0;
+        raw.output.axis.axis_min = output.1;
+        raw.output.axis.axis_max = output.2;
+        raw
+    }
+
+    #[test]
+    fn test_from_ll() {
+        // The bounds SDL stores for each form of axis binding.
+        let leftx = SDL_GamepadAxis::LEFTX;
+        let trigger = SDL_GamepadAxis::LEFT_TRIGGER;
+        let cases = [
+            (
+                (AXIS_MIN, AXIS_MAX),
+                (leftx, AXIS_MIN, AXIS_MAX),
+                "leftx:a2",
+            ),
+            (
+                (AXIS_MAX, AXIS_MIN),
+                (leftx, AXIS_MIN, AXIS_MAX),
+                "leftx:a2~",
+            ),
+            ((0, AXIS_MAX), (leftx, 0, AXIS_MAX), "+leftx:+a2"),
+            ((0, AXIS_MIN), (leftx, 0, AXIS_MIN), "-leftx:-a2"),
+            ((AXIS_MAX, 0), (leftx, AXIS_MIN, AXIS_MAX), "leftx:+a2~"),
+            ((AXIS_MIN, 0), (leftx, AXIS_MIN, AXIS_MAX), "leftx:-a2~"),
+            (
+                (AXIS_MIN, AXIS_MAX),
+                (trigger, 0, AXIS_MAX),
+                "lefttrigger:a2",
+            ),
+        ];
+        for (input, output, expected) in cases {
+            let binding = GamepadBinding::from_ll(&axis_binding(input, output)).unwrap();
+            assert_eq!(binding.to_string(), expected);
+        }
+
+        let mut raw = SDL_GamepadBinding {
+            input_type: SDL_GamepadBindingType::HAT,
+            output_type: SDL_GamepadBindingType::BUTTON,
+            ..Default::default()
+        };
+        raw.input.hat.hat = 1;
+        raw.input.hat.hat_mask = 4;
+        raw.output.button = SDL_GamepadButton::DPAD_DOWN;
+        let binding = GamepadBinding::from_ll(&raw).unwrap();
+        assert_eq!(binding.to_string(), "dpdown:h1.4");
+
+        raw.input_type = SDL_GamepadBindingType::NONE;
+        assert_eq!(GamepadBinding::from_ll(&raw), None);
+    }
+}
@@ -12,14 +12,20 @@ use crate::sensor::SensorType;
 #[cfg(feature = "hidapi")]
 use std::convert::TryInto;
 
-use crate::common::IntegerOrSdlError;
+use crate::clear_error;
+use crate::common::{validate_int, IntegerOrSdlError};
 use crate::get_error;
 use crate::guid::Guid;
+use crate::joystick::{ConnectionState, PowerInfo, PowerLevel};
 use crate::sys;
 use crate::Error;
 use crate::GamepadSubsystem;
 use std::mem::transmute;
 use sys::joystick::SDL_GetJoystickID;
+use sys::power::SDL_PowerState;
+
+mod mapping;
+pub use self::mapping::{AxisRange, BindingInput, BindingOutput, GamepadBinding, GamepadMapping};
 
 #[derive(Debug, Clone)]
 pub enum AddMappingError {
@@ -388,6 +394,129 @@ impl From<Button> for u8 {
     }
 }
 
+/// The family a gamepad belongs to, as far as SDL can tell.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(i32)]
+pub enum GamepadType {
+    Unknown = sys::gamepad::SDL_GAMEPAD_TYPE_UNKNOWN.0,
+    Standard = sys::gamepad::SDL_GAMEPAD_TYPE_STANDARD.0,
+    Xbox360 = sys::gamepad::SDL_GAMEPAD_TYPE_XBOX360.0,
+    XboxOne = sys::gamepad::SDL_GAMEPAD_TYPE_XBOXONE.0,
+    PS3 = sys::gamepad::SDL_GAMEPAD_TYPE_PS3.0,
+    PS4 = sys::gamepad::SDL_GAMEPAD_TYPE_PS4.0,
+    PS5 = sys::gamepad::SDL_GAMEPAD_TYPE_PS5.0,
+    NintendoSwitchPro = sys::gamepad::SDL_GAMEPAD_TYPE_NINTENDO_SWITCH_PRO.0,
+    NintendoSwitchJoyconLeft = sys::gamepad::SDL_GAMEPAD_TYPE_NINTENDO_SWITCH_JOYCON_LEFT.0,
+    NintendoSwitchJoyconRight = sys::gamepad::SDL_GAMEPAD_TYPE_NINTENDO_SWITCH_JOYCON_RIGHT.0,
+    NintendoSwitchJoyconPair = sys::gamepad::SDL_GAMEPAD_TYPE_NINTENDO_SWITCH_JOYCON_PAIR.0,
+}
+
+impl GamepadType {
+    /// Return the GamepadType from a string description in the same format
+    /// used by the game controller mapping strings, such as `"ps5"`.
+    #[doc(alias = "SDL_GetGamepadTypeFromString")]
+    pub fn from_string(gamepad_type: &str) -> GamepadType {
+        let raw = match CString::new(gamepad_type) {
+            Ok(gamepad_type) => unsafe {
+                sys::gamepad::SDL_GetGamepadTypeFromString(gamepad_type.as_ptr() as *const c_char)
+            },
+            // string contains a nul byte - it won't match anything.
+            Err(_) => sys::gamepad::SDL_GAMEPAD_TYPE_UNKNOWN,
+        };
+
+        GamepadType::from_ll(raw)
+    }
+
+    /// Return a string for a given type in the same format used by
+    /// the game controller mapping strings
+    #[doc(alias = "SDL_GetGamepadStringForType")]
+    pub fn string(self) -> String {
+        let string = unsafe { sys::gamepad::SDL_GetGamepadStringForType(self.to_ll()) };
+
+        c_str_to_string(string)
+    }
+
+    /// Get the label `button` has on gamepads of this type.
+    #[doc(alias = "SDL_GetGamepadButtonLabelForType")]
+    pub fn button_label(self, button: Button) -> ButtonLabel {
+        ButtonLabel::from_ll(unsafe {
+            sys::gamepad::SDL_GetGamepadButtonLabelForType(self.to_ll(), button.to_ll())
+        })
+    }
+
+    pub fn from_ll(raw: sys::gamepad::SDL_GamepadType) -> GamepadType {
+        match raw {
+            sys::gamepad::SDL_GAMEPAD_TYPE_STANDARD => GamepadType::Standard,
+            sys::gamepad::SDL_GAMEPAD_TYPE_XBOX360 => GamepadType::Xbox360,
+            sys::gamepad::SDL_GAMEPAD_TYPE_XBOXONE => GamepadType::XboxOne,
+            sys::gamepad::SDL_GAMEPAD_TYPE_PS3 => GamepadType::PS3,
+            sys::gamepad::SDL_GAMEPAD_TYPE_PS4 => GamepadType::PS4,
+            sys::gamepad::SDL_GAMEPAD_TYPE_PS5 => GamepadType::PS5,
+            sys::gamepad::SDL_GAMEPAD_TYPE_NINTENDO_SWITCH_PRO => GamepadType::NintendoSwitchPro,
+            sys::gamepad::SDL_GAMEPAD_TYPE_NINTENDO_SWITCH_JOYCON_LEFT => {
+                GamepadType::NintendoSwitchJoyconLeft
+            }
+            sys::gamepad::SDL_GAMEPAD_TYPE_NINTENDO_SWITCH_JOYCON_RIGHT => {
+                GamepadType::NintendoSwitchJoyconRight
+            }
+            sys::gamepad::SDL_GAMEPAD_TYPE_NINTENDO_SWITCH_JOYCON_PAIR => {
+                GamepadType::NintendoSwitchJoyconPair
+            }
+            _ => GamepadType::Unknown,
+        }
+    }
+
+    pub fn to_ll(self) -> sys::gamepad::SDL_GamepadType {
+        sys::gamepad::SDL_GamepadType(self as i32)
+    }
+}
+
+/// The label printed on a gamepad's face button.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(i32)]
+pub enum ButtonLabel {
+    Unknown = sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_UNKNOWN.0,
+    A = sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_A.0,
+    B = sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_B.0,
+    X = sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_X.0,
+    Y = sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_Y.0,
+    Cross = sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_CROSS.0,
+    Circle = sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_CIRCLE.0,
+    Square = sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_SQUARE.0,
+    Triangle = sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_TRIANGLE.0,
+}
+
+impl ButtonLabel {
+    pub fn from_ll(raw: sys::gamepad::SDL_GamepadButtonLabel) -> ButtonLabel {
+        match raw {
+            sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_A => ButtonLabel::A,
+            sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_B => ButtonLabel::B,
+            sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_X => ButtonLabel::X,
+            sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_Y => ButtonLabel::Y,
+            sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_CROSS => ButtonLabel::Cross,
+            sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_CIRCLE => ButtonLabel::Circle,
+            sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_SQUARE => ButtonLabel::Square,
+            sys::gamepad::SDL_GAMEPAD_BUTTON_LABEL_TRIANGLE => ButtonLabel::Triangle,
+            _ => ButtonLabel::Unknown,
+        }
+    }
+
+    pub fn to_ll(self) -> sys::gamepad::SDL_GamepadButtonLabel {
+        sys::gamepad::SDL_GamepadButtonLabel(self as i32)
+    }
+}
+
+/// The state of one finger on a gamepad touchpad.
+///
+/// `x` and `y` are normalized to the 0..1 range, with the origin in the upper left.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TouchpadFinger {
+    pub down: bool,
+    pub x: f32,
+    pub y: f32,
+    pub pressure: f32,
+}
+
 /// Possible return values for `add_mapping`
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum MappingStatus {
@@ -425,6 +554,25 @@ impl Gamepad {
         c_str_to_string(mapping)
     }
 
+    /// Return how the controller's buttons and axes are bound to joystick
+    /// inputs, in the order they appear in its mapping.
+    #[doc(alias = "SDL_GetGamepadBindings")]
+    pub fn bindings(&self) -> Result<Vec<GamepadBinding>, Error> {
+        let mut count: libc::c_int = 0;
+        let bindings = unsafe { sys::gamepad::SDL_GetGamepadBindings(self.raw, &mut count) };
+        if bindings.is_null() {
+            return Err(get_error());
+        }
+
+        let result = unsafe { std::slice::from_raw_parts(bindings, count as usize) }
+            .iter()
+            .filter_map(|&binding| GamepadBinding::from_ll(unsafe { &*binding }))
+            .collect();
+        unsafe { sys::stdinc::SDL_free(bindings as *mut c_void) };
+
+        Ok(result)
+    }
+
     /// Return true if the controller has been opened and currently
     /// connected.
     #[doc(alias = "SDL_GamepadConnected")]
@@ -474,6 +622,187 @@ impl Gamepad {
         unsafe { sys::gamepad::SDL_GetGamepadButton(self.raw, raw_button) }
     }
 
+    /// Return the type of this controller, which may have been overridden
+    /// by a mapping or hint.
+    #[doc(alias = "SDL_GetGamepadType")]
+    pub fn gamepad_type(&self) -> GamepadType {
+        GamepadType::from_ll(unsafe { sys::gamepad::SDL_GetGamepadType(self.raw) })
+    }
+
+    /// Return the type of this controller, ignoring any mapping override.
+    #[doc(alias = "SDL_GetRealGamepadType")]
+    pub fn real_gamepad_type(&self) -> GamepadType {
+        GamepadType::from_ll(unsafe { sys::gamepad::SDL_GetRealGamepadType(self.raw) })
+    }
+
+    /// Get the label `button` has on this controller, e.g. `Cross` for
+    /// `Button::South` on a PlayStation controller.
+    #[doc(alias = "SDL_GetGamepadButtonLabel")]
+    pub fn button_label(&self, button: Button) -> ButtonLabel {
+        ButtonLabel::from_ll(unsafe {
+            sys::gamepad::SDL_GetGamepadButtonLabel(self.raw, button.to_ll())
+        })
+    }
+
+    /// Return the number of touchpads on this controller
+    #[doc(alias = "SDL_GetNumGamepadTouchpads")]
+    pub fn num_touchpads(&self) -> u32 {
+        let result = unsafe { sys::gamepad::SDL_GetNumGamepadTouchpads(self.raw) };
+
+        result.max(0) as u32
+    }
+
+    /// Return the number of simultaneous fingers `touchpad` supports
+    #[doc(alias = "SDL_GetNumGamepadTouchpadFingers")]
+    pub fn num_touchpad_fingers(&self, touchpad: u32) -> Result<u32, IntegerOrSdlError> {
+        let touchpad = validate_int(touchpad, "touchpad")?;
+        let result = unsafe { sys::gamepad::SDL_GetNumGamepadTouchpadFingers(self.raw, touchpad) };
+
+        Ok(result.max(0) as u32)
+    }
+
+    /// Get the current state of `finger` on `touchpad`
+    #[doc(alias = "SDL_GetGamepadTouchpadFinger")]
+    pub fn touchpad_finger(
+        &self,
+        touchpad: u32,
+        finger: u32,
+    ) -> Result<TouchpadFinger, IntegerOrSdlError> {
+        let touchpad = validate_int(touchpad, "touchpad")?;
+        let finger = validate_int(finger, "finger")?;
+
+        let mut state = TouchpadFinger {
+            down: false,
+            x: 0.0,
+            y: 0.0,
+            pressure: 0.0,
+        };
+        let result = unsafe {
+            sys::gamepad::SDL_GetGamepadTouchpadFinger(
+                self.raw,
+                touchpad,
+                finger,
+                &mut state.down,
+                &mut state.x,
+                &mut state.y,
+                &mut state.pressure,
+            )
+        };
+
+        if result {
+            Ok(state)
+        } else {
+            Err(IntegerOrSdlError::SdlError(get_error()))
+        }
+    }
+
+    /// Return the player index assigned to this controller, or `None`
+    /// if it has none.
+    #[doc(alias = "SDL_GetGamepadPlayerIndex")]
+    pub fn player_index(&self) -> Option<u32> {
+        let result = unsafe { sys::gamepad::SDL_GetGamepadPlayerIndex(self.raw) };
+
+        if result < 0 {
+            None
+        } else {
+            Some(result as u32)
+        }
+    }
+
+    /// Assign a player index to this controller, which also sets the player
+    /// LEDs on controllers that have them. `None` clears the index.
+    #[doc(alias = "SDL_SetGamepadPlayerIndex")]
+    pub fn set_player_index(&mut self, player_index: Option<u32>) -> Result<(), IntegerOrSdlError> {
+        let player_index = match player_index {
+            Some(player_index) => validate_int(player_index, "player_index")?,
+            None => -1,
+        };
+
+        let result = unsafe { sys::gamepad::SDL_SetGamepadPlayerIndex(self.raw, player_index) };
+
+        if result {
+            Ok(())
+        } else {
+            Err(IntegerOrSdlError::SdlError(get_error()))
+        }
+    }
+
+    /// Return the serial number of this controller, if available.
+    #[doc(alias = "SDL_GetGamepadSerial")]
+    pub fn serial(&self) -> Option<String> {
+        let serial = unsafe { sys::gamepad::SDL_GetGamepadSerial(self.raw) };
+
+        if serial.is_null() {
+            None
+        } else {
+            Some(c_str_to_string(serial))
+        }
+    }
+
+    /// Return the USB vendor ID of this controller, or 0 if unavailable.
+    #[doc(alias = "SDL_GetGamepadVendor")]
+    pub fn vendor_id(&self) -> u16 {
+        unsafe { sys::gamepad::SDL_GetGamepadVendor(self.raw) }
+    }
+
+    /// Return the USB product ID of this controller, or 0 if unavailable.
+    #[doc(alias = "SDL_GetGamepadProduct")]
+    pub fn product_id(&self) -> u16 {
+        unsafe { sys::gamepad::SDL_GetGamepadProduct(self.raw) }
+    }
+
+    /// Return the product version of this controller, or 0 if unavailable.
+    #[doc(alias = "SDL_GetGamepadProductVersion")]
+    pub fn product_version(&self) -> u16 {
+        unsafe { sys::gamepad::SDL_GetGamepadProductVersion(self.raw) }
+    }
+
+    /// Return the firmware version of this controller, or 0 if unavailable.
+    #[doc(alias = "SDL_GetGamepadFirmwareVersion")]
+    pub fn firmware_version(&self) -> u16 {
+        unsafe { sys::gamepad::SDL_GetGamepadFirmwareVersion(self.raw) }
+    }
+
+    /// Return the Steam Input handle of this controller, or 0 if it isn't
+    /// driven by Steam Input.
+    #[doc(alias = "SDL_GetGamepadSteamHandle")]
+    pub fn steam_handle(&self) -> u64 {
+        unsafe { sys::gamepad::SDL_GetGamepadSteamHandle(self.raw) }
+    }
+
+    /// Retrieve the battery level of this controller
+    #[doc(alias = "SDL_GetGamepadPowerInfo")]
+    pub fn power_level(&self) -> Result<PowerInfo, IntegerOrSdlError> {
+        clear_error();
+
+        let mut power_pct: libc::c_int = 0;
+        let result = unsafe { sys::gamepad::SDL_GetGamepadPowerInfo(self.raw, &mut power_pct) };
+
+        let info = PowerInfo {
+            state: PowerLevel::from_ll(result),
+            percentage: power_pct,
+        };
+
+        if result != SDL_PowerState::UNKNOWN {
+            return Ok(info);
+        }
+
+        let err = get_error();
+        if err.is_empty() {
+            Ok(info)
+        } else {
+            Err(IntegerOrSdlError::SdlError(err))
+        }
+    }
+
+    /// Retrieve how this controller is connected to the system
+    #[doc(alias = "SDL_GetGamepadConnectionState")]
+    pub fn connection_state(&self) -> Result<ConnectionState, Error> {
+        let result = unsafe { sys::gamepad::SDL_GetGamepadConnectionState(self.raw) };
+
+        ConnectionState::from_ll(result).ok_or_else(get_error)
+    }
+
     /// Set the rumble motors to their specified intensities, if supported.
     /// Automatically resets back to zero after `duration_ms` milliseconds have passed.
     ///
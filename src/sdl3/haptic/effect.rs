@@ -0,0 +1,691 @@
+//! Typed haptic effect descriptions.
+//!
+//! Lengths and delays are in milliseconds; levels and magnitudes use the full range of
+//! their integer type.
+
+use std::mem;
+
+use crate::sys;
+use sys::haptic::SDL_HapticEffect;
+
+/// Play an effect forever, as `length` or as the iteration count of `Haptic::run_effect`.
+pub const HAPTIC_INFINITY: u32 = sys::haptic::SDL_HAPTIC_INFINITY;
+
+/// The direction an effect comes from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HapticDirection {
+    /// Hundredths of a degree clockwise from north (away from the user).
+    Polar(i32),
+    /// A vector on the X (right), Y (away from the user) and Z (up) axes.
+    Cartesian([i32; 3]),
+    /// Hundredths of a degree for up to two spherical angles.
+    Spherical([i32; 2]),
+    /// Along the first axis of a steering wheel.
+    SteeringAxis,
+}
+
+impl Default for HapticDirection {
+    fn default() -> HapticDirection {
+        HapticDirection::Polar(0)
+    }
+}
+
+impl HapticDirection {
+    fn to_ll(self) -> sys::haptic::SDL_HapticDirection {
+        let (r#type, dir) = match self {
+            HapticDirection::Polar(angle) => (sys::haptic::SDL_HAPTIC_POLAR, [angle, 0, 0]),
+            HapticDirection::Cartesian(dir) => (sys::haptic::SDL_HAPTIC_CARTESIAN, dir),
+            HapticDirection::Spherical([a, b]) => (sys::haptic::SDL_HAPTIC_SPHERICAL, [a, b, 0]),
+            HapticDirection::SteeringAxis => (sys::haptic::SDL_HAPTIC_STEERING_AXIS, [0; 3]),
+        };
+        sys::haptic::SDL_HapticDirection { r#type, dir }
+    }
+}
+
+/// Fades an effect in from `attack_level` and out to `fade_level`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HapticEnvelope {
+    pub attack_length: u16,
+    pub attack_level: u16,
+    pub fade_length: u16,
+    pub fade_level: u16,
+}
+
+/// When an effect plays; shared by all effects but [`LeftRightEffect`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HapticReplay {
+    pub direction: HapticDirection,
+    /// How long the effect plays, or [`HAPTIC_INFINITY`].
+    pub length: u32,
+    /// Delay before the effect starts.
+    pub delay: u16,
+    /// Button that triggers the effect, or 0.
+    pub button: u16,
+    /// Minimum time between two triggers by `button`.
+    pub interval: u16,
+}
+
+macro_rules! replay_builders {
+    ($($effect:ident),*) => {
+        $(
+            impl $effect {
+                pub fn direction(mut self, direction: HapticDirection) -> $effect {
+                    self.replay.direction = direction;
+                    self
+                }
+
+                pub fn length(mut self, length: u32) -> $effect {
+                    self.replay.length = length;
+                    self
+                }
+
+                pub fn delay(mut self, delay: u16) -> $effect {
+                    self.replay.delay = delay;
+                    self
+                }
+
+                /// Plays the effect when `button` is pressed, at most once per `interval`.
+                pub fn trigger(mut self, button: u16, interval: u16) -> $effect {
+                    self.replay.button = button;
+                    self.replay.interval = interval;
+                    self
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! envelope_builder {
+    ($($effect:ident),*) => {
+        $(
+            impl $effect {
+                pub fn envelope(mut self, envelope: HapticEnvelope) -> $effect {
+                    self.envelope = envelope;
+                    self
+                }
+            }
+        )*
+    };
+}
+
+replay_builders!(
+    ConstantEffect,
+    PeriodicEffect,
+    ConditionEffect,
+    RampEffect,
+    CustomEffect
+);
+envelope_builder!(ConstantEffect, PeriodicEffect, RampEffect, CustomEffect);
+
+/// A constant force.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConstantEffect {
+    pub replay: HapticReplay,
+    pub level: i16,
+    pub envelope: HapticEnvelope,
+}
+
+impl ConstantEffect {
+    pub fn new(level: i16, length: u32) -> ConstantEffect {
+        ConstantEffect::default().level(level).length(length)
+    }
+
+    pub fn level(mut self, level: i16) -> ConstantEffect {
+        self.level = level;
+        self
+    }
+}
+
+/// The wave shape of a [`PeriodicEffect`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Waveform {
+    #[default]
+    Sine,
+    Square,
+    Triangle,
+    SawtoothUp,
+    SawtoothDown,
+}
+
+/// A force that follows a wave.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PeriodicEffect {
+    pub replay: HapticReplay,
+    pub waveform: Waveform,
+    /// Duration of one wave.
+    pub period: u16,
+    /// Peak value; negative values flip the wave.
+    pub magnitude: i16,
+    /// Mean value of the wave.
+    pub offset: i16,
+    /// Horizontal shift in hundredths of a degree.
+    pub phase: u16,
+    pub envelope: HapticEnvelope,
+}
+
+impl PeriodicEffect {
+    pub fn new(waveform: Waveform, period: u16, magnitude: i16, length: u32) -> PeriodicEffect {
+        PeriodicEffect {
+            waveform,
+            period,
+            magnitude,
+            ..PeriodicEffect::default()
+        }
+        .length(length)
+    }
+
+    pub fn offset(mut self, offset: i16) -> PeriodicEffect {
+        self.offset = offset;
+        self
+    }
+
+    pub fn phase(mut self, phase: u16) -> PeriodicEffect {
+        self.phase = phase;
+        self
+    }
+}
+
+/// The kind of a [`ConditionEffect`], which depends on the device's own state.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Condition {
+    /// Based on the axis position.
+    #[default]
+    Spring,
+    /// Based on the axis velocity.
+    Damper,
+    /// Based on the axis acceleration.
+    Inertia,
+    /// Based on the axis movement.
+    Friction,
+}
+
+/// Parameters of a [`ConditionEffect`] for one axis.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ConditionAxis {
+    /// Level when the axis is all the way to the right.
+    pub right_sat: u16,
+    /// Level when the axis is all the way to the left.
+    pub left_sat: u16,
+    /// How fast the force grows to the right.
+    pub right_coeff: i16,
+    /// How fast the force grows to the left.
+    pub left_coeff: i16,
+    /// Size of the dead zone around `center`.
+    pub deadband: u16,
+    pub center: i16,
+}
+
+/// A force that depends on the axes' position, velocity or acceleration.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConditionEffect {
+    pub replay: HapticReplay,
+    pub condition: Condition,
+    /// Parameters for up to three axes.
+    pub axes: [ConditionAxis; 3],
+}
+
+impl ConditionEffect {
+    pub fn new(condition: Condition, length: u32) -> ConditionEffect {
+        ConditionEffect {
+            condition,
+            ..ConditionEffect::default()
+        }
+        .length(length)
+    }
+
+    /// Sets the parameters of the axis at `index`, which must be below 3.
+    pub fn axis(mut self, index: usize, axis: ConditionAxis) -> ConditionEffect {
+        self.axes[index] = axis;
+        self
+    }
+}
+
+/// A force that changes linearly from `start` to `end`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RampEffect {
+    pub replay: HapticReplay,
+    pub start: i16,
+    pub end: i16,
+    pub envelope: HapticEnvelope,
+}
+
+impl RampEffect {
+    pub fn new(start: i16, end: i16, length: u32) -> RampEffect {
+        RampEffect {
+            start,
+            end,
+            ..RampEffect::default()
+        }
+        .length(length)
+    }
+}
+
+/// Drives the large (low frequency) and small (high frequency) motors, like
+/// gamepad rumble.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LeftRightEffect {
+    pub length: u32,
+    pub large_magnitude: u16,
+    pub small_magnitude: u16,
+}
+
+impl LeftRightEffect {
+    pub fn new(large_magnitude: u16, small_magnitude: u16, length: u32) -> LeftRightEffect {
+        LeftRightEffect {
+            length,
+            large_magnitude,
+            small_magnitude,
+        }
+    }
+}
+
+/// A force defined sample by sample.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CustomEffect {
+    pub replay: HapticReplay,
+    /// Number of axes the samples cover.
+    pub channels: u8,
+    /// Time between samples.
+    pub period: u16,
+    /// Samples interleaved per channel.
+    pub samples: Vec<u16>,
+    pub envelope: HapticEnvelope,
+}
+
+impl CustomEffect {
+    pub fn new(channels: u8, period: u16, samples: Vec<u16>, length: u32) -> CustomEffect {
+        CustomEffect {
+            channels,
+            period,
+            samples,
+            ..CustomEffect::default()
+        }
+        .length(length)
+    }
+}
+
+/// A haptic effect, created on a device with `Haptic::new_effect`.
+///
+/// # Example
+/// ```
+/// use sdl3::haptic::{HapticDirection, HapticEffect, PeriodicEffect, Waveform};
+///
+/// let effect: HapticEffect = PeriodicEffect::new(Waveform::Sine, 100, 20_000, 1_000)
+///     .direction(HapticDirection::Polar(9_000))
+///     .delay(50)
+///     .into();
+/// assert!(matches!(effect, HapticEffect::Periodic(_)));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum HapticEffect {
+    Constant(ConstantEffect),
+    Periodic(PeriodicEffect),
+    Condition(ConditionEffect),
+    Ramp(RampEffect),
+    LeftRight(LeftRightEffect),
+    Custom(CustomEffect),
+}
+
+macro_rules! impl_from_effect {
+    ($($variant:ident($effect:ident)),*) => {
+        $(
+            impl From<$effect> for HapticEffect {
+                fn from(effect: $effect) -> HapticEffect {
+                    HapticEffect::$variant(effect)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_effect!(
+    Constant(ConstantEffect),
+    Periodic(PeriodicEffect),
+    Condition(ConditionEffect),
+    Ramp(RampEffect),
+    LeftRight(LeftRightEffect),
+    Custom(CustomEffect)
+);
+
+impl HapticEffect {
+    /// The `HapticFeatures` bit a device needs to play this effect.
+    pub(super) fn feature(&self) -> u16 {
+        match self {
+            HapticEffect::Constant(_) => sys::haptic::SDL_HAPTIC_CONSTANT,
+            HapticEffect::Periodic(effect) => match effect.waveform {
+                Waveform::Sine => sys::haptic::SDL_HAPTIC_SINE,
+                Waveform::Square => sys::haptic::SDL_HAPTIC_SQUARE,
+                Waveform::Triangle => sys::haptic::SDL_HAPTIC_TRIANGLE,
+                Waveform::SawtoothUp => sys::haptic::SDL_HAPTIC_SAWTOOTHUP,
+                Waveform::SawtoothDown => sys::haptic::SDL_HAPTIC_SAWTOOTHDOWN,
+            },
+            HapticEffect::Condition(effect) => match effect.condition {
+                Condition::Spring => sys::haptic::SDL_HAPTIC_SPRING,
+                Condition::Damper => sys::haptic::SDL_HAPTIC_DAMPER,
+                Condition::Inertia => sys::haptic::SDL_HAPTIC_INERTIA,
+                Condition::Friction => sys::haptic::SDL_HAPTIC_FRICTION,
+            },
+            HapticEffect::Ramp(_) => sys::haptic::SDL_HAPTIC_RAMP,
+            HapticEffect::LeftRight(_) => sys::haptic::SDL_HAPTIC_LEFTRIGHT,
+            HapticEffect::Custom(_) => sys::haptic::SDL_HAPTIC_CUSTOM,
+        }
+    }
+
+    /// Converts to the SDL representation. A custom effect's samples are borrowed, so the
+    /// result must not outlive `self`.
+    pub(super) fn to_ll(&self) -> SDL_HapticEffect {
+        let mut raw: SDL_HapticEffect = unsafe { mem::zeroed() };
+        let r#type = self.feature();
+        match self {
+            HapticEffect::Constant(effect) => {
+                let replay = &effect.replay;
+                let envelope = &effect.envelope;
+                raw.constant = sys::haptic::SDL_HapticConstant {
+                    r#type,
+                    direction: replay.direction.to_ll(),
+                    length: replay.length,
+                    delay: replay.delay,
+                    button: replay.button,
+                    interval: replay.interval,
+                    level: effect.level,
+                    attack_length: envelope.attack_length,
+                    attack_level: envelope.attack_level,
+                    fade_length: envelope.fade_length,
+                    fade_level: envelope.fade_level,
+                };
+            }
+            HapticEffect::Periodic(effect) => {
+                let replay = &effect.replay;
+                let envelope = &effect.envelope;
+                raw.periodic = sys::haptic::SDL_HapticPeriodic {
+                    r#type,
+                    direction: replay.direction.to_ll(),
+                    length: replay.length,
+                    delay: replay.delay,
+                    button: replay.button,
+                    interval: replay.interval,
+                    period: effect.period,
+                    magnitude: effect.magnitude,
+                    offset: effect.offset,
+                    phase: effect.phase,
+                    attack_length: envelope.attack_length,
+                    attack_level: envelope.attack_level,
+                    fade_length: envelope.fade_length,
+                    fade_level: envelope.fade_level,
+                };
+            }
+            HapticEffect::Condition(effect) => {
+                let replay = &effect.replay;
+                let axes = &effect.axes;
+                raw.condition = sys::haptic::SDL_HapticCondition {
+                    r#type,
+                    direction: replay.direction.to_ll(),
+                    length: replay.length,
+                    delay: replay.delay,
+                    button: replay.button,
+                    interval: replay.interval,
+                    right_sat: axes.map(|axis| axis.right_sat),
+                    left_sat: axes.map(|axis| axis.left_sat),
+                    right_coeff: axes.map(|axis| axis.right_coeff),
+                    left_coeff: axes.map(|axis| axis.left_coeff),
+                    deadband: axes.map(|axis| axis.deadband),
+                    center: axes.map(|axis| axis.center),
+                };
+            }
+            HapticEffect::Ramp(effect) => {
+                let replay = &effect.replay;
+                let envelope = &effect.envelope;
+                raw.ramp = sys::haptic::SDL_HapticRamp {
+                    r#type,
+                    direction: replay.direction.to_ll(),
+                    length: replay.length,
+                    delay: replay.delay,
+                    button: replay.button,
+                    interval: replay.interval,
+                    start: effect.start,
+                    end: effect.end,
+                    attack_length: envelope.attack_length,
+                    attack_level: envelope.attack_level,
+                    fade_length: envelope.fade_length,
+                    fade_level: envelope.fade_level,
+                };
+            }
+            HapticEffect::LeftRight(effect) => {
+                raw.leftright = sys::haptic::SDL_HapticLeftRight {
+                    r#type,
+                    length: effect.length,
+                    large_magnitude: effect.large_magnitude,
+                    small_magnitude: effect.small_magnitude,
+                };
+            }
+            HapticEffect::Custom(effect) => {
+                let replay = &effect.replay;
+                let envelope = &effect.envelope;
+                let samples = match effect.channels {
+                    0 => 0,
+                    channels => effect.samples.len() / channels as usize,
+                };
+                raw.custom = sys::haptic::SDL_HapticCustom {
+                    r#type,
+                    direction: replay.direction.to_ll(),
+                    length: replay.length,
+                    delay: replay.delay,
+                    button: replay.button,
+                    interval: replay.interval,
+                    channels: effect.channels,
+                    period: effect.period,
+                    samples: samples.min(u16::MAX as usize) as u16,
+                    data: effect.samples.as_ptr() as *mut u16,
+                    attack_length: envelope.attack_length,
+                    attack_level: envelope.attack_level,
+                    fade_length: envelope.fade_length,
+                    fade_level: envelope.fade_level,
+                };
+            }
+        }
+        raw
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_ll() {
+        let effect: HapticEffect = ConditionEffect::new(Condition::Damper, HAPTIC_INFINITY)
+            .axis(
+                1,
+                ConditionAxis {
+                    right_sat: 0xFFFF,
+                    center: -5,
+                    ..ConditionAxis::default()
+                },
+            )
+            .trigger(2, 100)
+            .into();
+        let raw = effect.to_ll();
+        unsafe {
+            assert_eq!(raw.r#type, sys::haptic::SDL_HAPTIC_DAMPER);
+            assert_eq!(raw.condition.length, HAPTIC_INFINITY);
+            assert_eq!(raw.condition.right_sat, [0, 0xFFFF, 0]);
+            assert_eq!(raw.condition.center, [0, -5, 0]);
+            assert_eq!((raw.condition.button, raw.condition.interval), (2, 100));
+        }
+
+        let effect: HapticEffect = CustomEffect::new(2, 10, vec![1, 2, 3, 4, 5, 6], 60)
+            .direction(HapticDirection::Cartesian([1, 0, 0]))
+            .into();
+        let raw = effect.to_ll();
+        unsafe {
+            assert_eq!(raw.r#type, sys::haptic::SDL_HAPTIC_CUSTOM);
+            assert_eq!(raw.custom.samples, 3);
+            assert_eq!(
+                raw.custom.direction.r#type,
+                sys::haptic::SDL_HAPTIC_CARTESIAN
+            );
+            assert_eq!(*raw.custom.data.add(5), 6);
+        }
+    }
+
+    #[test]
+    fn test_direction_to_ll() {
+        let cases = [
+            (
+                HapticDirection::Polar(18_000),
+                sys::haptic::SDL_HAPTIC_POLAR,
+                [18_000, 0, 0],
+            ),
+            (
+                HapticDirection::Cartesian([-1, 2, 3]),
+                sys::haptic::SDL_HAPTIC_CARTESIAN,
+                [-1, 2, 3],
+            ),
+            (
+                HapticDirection::Spherical([9_000, 4_500]),
+                sys::haptic::SDL_HAPTIC_SPHERICAL,
+                [9_000, 4_500, 0],
+            ),
+            (
+                HapticDirection::SteeringAxis,
+                sys::haptic::SDL_HAPTIC_STEERING_AXIS,
+                [0, 0, 0],
+            ),
+        ];
+        for (direction, r#type, dir) in cases {
+            let raw = direction.to_ll();
+            assert_eq!((raw.r#type, raw.dir), (r#type, dir), "{:?}", direction);
+        }
+        assert_eq!(HapticDirection::default(), HapticDirection::Polar(0));
+    }
+
+    #[test]
+    fn test_replay_and_envelope_to_ll() {
+        let envelope = HapticEnvelope {
+            attack_length: 10,
+            attack_level: 20,
+            fade_length: 30,
+            fade_level: 40,
+        };
+        let effect: HapticEffect = PeriodicEffect::new(Waveform::SawtoothDown, 50, -300, 1_000)
+            .offset(-7)
+            .phase(9_000)
+            .direction(HapticDirection::Spherical([100, 200]))
+            .delay(25)
+            .trigger(3, 500)
+            .envelope(envelope)
+            .into();
+        let raw = effect.to_ll();
+        unsafe {
+            let periodic = raw.periodic;
+            assert_eq!(periodic.r#type, sys::haptic::SDL_HAPTIC_SAWTOOTHDOWN);
+            assert_eq!(periodic.direction.r#type, sys::haptic::SDL_HAPTIC_SPHERICAL);
+            assert_eq!(
+                (
+                    periodic.length,
+                    periodic.delay,
+                    periodic.button,
+                    periodic.interval
+                ),
+                (1_000, 25, 3, 500)
+            );
+            assert_eq!(
+                (
+                    periodic.period,
+                    periodic.magnitude,
+                    periodic.offset,
+                    periodic.phase
+                ),
+                (50, -300, -7, 9_000)
+            );
+            assert_eq!(
+                (
+                    periodic.attack_length,
+                    periodic.attack_level,
+                    periodic.fade_length,
+                    periodic.fade_level
+                ),
+                (10, 20, 30, 40)
+            );
+        }
+
+        let effect: HapticEffect = ConstantEffect::new(-1_000, HAPTIC_INFINITY)
+            .envelope(envelope)
+            .into();
+        let raw = effect.to_ll();
+        unsafe {
+            assert_eq!(raw.r#type, sys::haptic::SDL_HAPTIC_CONSTANT);
+            assert_eq!(raw.constant.level, -1_000);
+            assert_eq!(raw.constant.length, HAPTIC_INFINITY);
+            assert_eq!(
+                (raw.constant.attack_length, raw.constant.fade_level),
+                (10, 40)
+            );
+        }
+
+        let effect: HapticEffect = RampEffect::new(-5, 5, 200).into();
+        let raw = effect.to_ll();
+        unsafe {
+            assert_eq!(raw.r#type, sys::haptic::SDL_HAPTIC_RAMP);
+            assert_eq!(
+                (raw.ramp.start, raw.ramp.end, raw.ramp.length),
+                (-5, 5, 200)
+            );
+            assert_eq!(raw.ramp.direction.r#type, sys::haptic::SDL_HAPTIC_POLAR);
+        }
+
+        // Left/right effects have no replay or envelope.
+        let effect: HapticEffect = LeftRightEffect::new(0xFFFF, 0x8000, 300).into();
+        let raw = effect.to_ll();
+        unsafe {
+            assert_eq!(raw.r#type, sys::haptic::SDL_HAPTIC_LEFTRIGHT);
+            assert_eq!(
+                (
+                    raw.leftright.length,
+                    raw.leftright.large_magnitude,
+                    raw.leftright.small_magnitude
+                ),
+                (300, 0xFFFF, 0x8000)
+            );
+        }
+    }
+
+    #[test]
+    fn test_condition_axes_to_ll() {
+        let effect: HapticEffect = ConditionEffect::new(Condition::Spring, 100)
+            .axis(
+                0,
+                ConditionAxis {
+                    right_sat: 1,
+                    left_sat: 2,
+                    right_coeff: 3,
+                    left_coeff: -4,
+                    deadband: 5,
+                    center: -6,
+                },
+            )
+            .axis(
+                2,
+                ConditionAxis {
+                    right_sat: 10,
+                    left_sat: 20,
+                    right_coeff: -30,
+                    left_coeff: 40,
+                    deadband: 50,
+                    center: 60,
+                },
+            )
+            .into();
+        let raw = effect.to_ll();
+        unsafe {
+            let condition = raw.condition;
+            assert_eq!(condition.r#type, sys::haptic::SDL_HAPTIC_SPRING);
+            assert_eq!(condition.right_sat, [1, 0, 10]);
+            assert_eq!(condition.left_sat, [2, 0, 20]);
+            assert_eq!(condition.right_coeff, [3, 0, -30]);
+            assert_eq!(condition.left_coeff, [-4, 0, 40]);
+            assert_eq!(condition.deadband, [5, 0, 50]);
+            assert_eq!(condition.center, [-6, 0, 60]);
+        }
+    }
+}
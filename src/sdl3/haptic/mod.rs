@@ -0,0 +1,442 @@
+//! Haptic Functions
+
+use crate::sys;
+use libc::{c_char, c_int, c_void};
+use std::collections::HashMap;
+use std::ffi::CStr;
+use sys::joystick::SDL_OpenJoystick;
+use sys::stdinc::SDL_free;
+
+use crate::common::IntegerOrSdlError;
+use crate::get_error;
+use crate::Error;
+use crate::HapticSubsystem;
+
+mod effect;
+pub use self::effect::{
+    Condition, ConditionAxis, ConditionEffect, ConstantEffect, CustomEffect, HapticDirection,
+    HapticEffect, HapticEnvelope, HapticReplay, LeftRightEffect, PeriodicEffect, RampEffect,
+    Waveform, HAPTIC_INFINITY,
+};
+
+/// The instance ID of a haptic device, valid while it is connected.
+pub type HapticId = sys::haptic::SDL_HapticID;
+
+pub struct HapticInstance {
+    pub id: HapticId,
+    pub name: String,
+}
+
+bitflags! {
+    /// What a haptic device supports, as returned by [`Haptic::features`].
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct HapticFeatures: u32 {
+        const CONSTANT = sys::haptic::SDL_HAPTIC_CONSTANT as u32;
+        const SINE = sys::haptic::SDL_HAPTIC_SINE as u32;
+        const SQUARE = sys::haptic::SDL_HAPTIC_SQUARE as u32;
+        const TRIANGLE = sys::haptic::SDL_HAPTIC_TRIANGLE as u32;
+        const SAWTOOTH_UP = sys::haptic::SDL_HAPTIC_SAWTOOTHUP as u32;
+        const SAWTOOTH_DOWN = sys::haptic::SDL_HAPTIC_SAWTOOTHDOWN as u32;
+        const RAMP = sys::haptic::SDL_HAPTIC_RAMP as u32;
+        const SPRING = sys::haptic::SDL_HAPTIC_SPRING as u32;
+        const DAMPER = sys::haptic::SDL_HAPTIC_DAMPER as u32;
+        const INERTIA = sys::haptic::SDL_HAPTIC_INERTIA as u32;
+        const FRICTION = sys::haptic::SDL_HAPTIC_FRICTION as u32;
+        const LEFT_RIGHT = sys::haptic::SDL_HAPTIC_LEFTRIGHT as u32;
+        const CUSTOM = sys::haptic::SDL_HAPTIC_CUSTOM as u32;
+        /// The overall strength can be set with [`Haptic::set_gain`].
+        const GAIN = sys::haptic::SDL_HAPTIC_GAIN;
+        /// Autocenter can be set with [`Haptic::set_autocenter`].
+        const AUTOCENTER = sys::haptic::SDL_HAPTIC_AUTOCENTER;
+        /// [`Haptic::effect_status`] is supported.
+        const STATUS = sys::haptic::SDL_HAPTIC_STATUS;
+        /// The device can be paused with [`Haptic::pause`].
+        const PAUSE = sys::haptic::SDL_HAPTIC_PAUSE;
+    }
+}
+
+impl HapticSubsystem {
+    /// Get the currently connected haptic devices.
+    #[doc(alias = "SDL_GetHaptics")]
+    pub fn haptics(&self) -> Result<Vec<HapticInstance>, Error> {
+        let mut count: c_int = 0;
+        unsafe {
+            let ids = sys::haptic::SDL_GetHaptics(&mut count);
+            if ids.is_null() {
+                return Err(get_error());
+            }
+            let instances = std::slice::from_raw_parts(ids, count as usize)
+                .iter()
+                .map(|&id| HapticInstance {
+                    id,
+                    name: c_str_to_string(sys::haptic::SDL_GetHapticNameForID(id)),
+                })
+                .collect();
+            SDL_free(ids as *mut c_void);
+            Ok(instances)
+        }
+    }
+
+    /// Open a haptic device by its instance ID.
+    #[doc(alias = "SDL_OpenHaptic")]
+    pub fn open(&self, id: HapticId) -> Result<Haptic, Error> {
+        let haptic = unsafe { sys::haptic::SDL_OpenHaptic(id) };
+        Haptic::from_ll(self, haptic).ok_or_else(get_error)
+    }
+
+    /// Return `true` if the mouse has haptic feedback.
+    #[doc(alias = "SDL_IsMouseHaptic")]
+    pub fn is_mouse_haptic(&self) -> bool {
+        unsafe { sys::haptic::SDL_IsMouseHaptic() }
+    }
+
+    /// Open the haptic device of the mouse.
+    #[doc(alias = "SDL_OpenHapticFromMouse")]
+    pub fn open_from_mouse(&self) -> Result<Haptic, Error> {
+        let haptic = unsafe { sys::haptic::SDL_OpenHapticFromMouse() };
+        Haptic::from_ll(self, haptic).ok_or_else(get_error)
+    }
+
+    /// Attempt to open the joystick at index `joystick_index` and return its haptic device.
+    #[doc(alias = "SDL_OpenJoystick")]
+    pub fn open_from_joystick_id(&self, joystick_index: u32) -> Result<Haptic, IntegerOrSdlError> {
+        use crate::common::IntegerOrSdlError::*;
+
+        let haptic = unsafe {
+            let joystick = SDL_OpenJoystick(joystick_index);
+            sys::haptic::SDL_OpenHapticFromJoystick(joystick)
+        };
+
+        let mut haptic = Haptic::from_ll(self, haptic).ok_or_else(|| SdlError(get_error()))?;
+        haptic.init_rumble();
+        Ok(haptic)
+    }
+}
+
+/// An effect uploaded to a haptic device with [`Haptic::new_effect`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HapticEffectId(c_int);
+
+/// Wrapper around the `SDL_Haptic` object
+pub struct Haptic {
+    subsystem: HapticSubsystem,
+    raw: *mut sys::haptic::SDL_Haptic,
+    /// Samples of custom effects, kept alive as SDL holds on to the pointer.
+    custom_samples: HashMap<c_int, Vec<u16>>,
+    rumble_initialized: bool,
+}
+
+impl Haptic {
+    fn from_ll(subsystem: &HapticSubsystem, raw: *mut sys::haptic::SDL_Haptic) -> Option<Haptic> {
+        if raw.is_null() {
+            None
+        } else {
+            Some(Haptic {
+                subsystem: subsystem.clone(),
+                raw,
+                custom_samples: HashMap::new(),
+                rumble_initialized: false,
+            })
+        }
+    }
+
+    #[inline]
+    #[doc(alias = "SDL_HapticRumblePlay")]
+    pub fn subsystem(&self) -> &HapticSubsystem {
+        &self.subsystem
+    }
+
+    #[doc(alias = "SDL_GetHapticID")]
+    pub fn id(&self) -> HapticId {
+        unsafe { sys::haptic::SDL_GetHapticID(self.raw) }
+    }
+
+    /// Return the name of the device or an empty string if no name is found.
+    #[doc(alias = "SDL_GetHapticName")]
+    pub fn name(&self) -> String {
+        c_str_to_string(unsafe { sys::haptic::SDL_GetHapticName(self.raw) })
+    }
+
+    #[doc(alias = "SDL_GetHapticFeatures")]
+    pub fn features(&self) -> HapticFeatures {
+        HapticFeatures::from_bits_truncate(unsafe { sys::haptic::SDL_GetHapticFeatures(self.raw) })
+    }
+
+    /// The number of effects the device can store.
+    #[doc(alias = "SDL_GetMaxHapticEffects")]
+    pub fn max_effects(&self) -> Result<u32, Error> {
+        let result = unsafe { sys::haptic::SDL_GetMaxHapticEffects(self.raw) };
+        u32::try_from(result).map_err(|_| get_error())
+    }
+
+    /// The number of effects the device can play at the same time.
+    #[doc(alias = "SDL_GetMaxHapticEffectsPlaying")]
+    pub fn max_effects_playing(&self) -> Result<u32, Error> {
+        let result = unsafe { sys::haptic::SDL_GetMaxHapticEffectsPlaying(self.raw) };
+        u32::try_from(result).map_err(|_| get_error())
+    }
+
+    /// The number of axes effect directions can refer to.
+    #[doc(alias = "SDL_GetNumHapticAxes")]
+    pub fn num_axes(&self) -> Result<u32, Error> {
+        let result = unsafe { sys::haptic::SDL_GetNumHapticAxes(self.raw) };
+        u32::try_from(result).map_err(|_| get_error())
+    }
+
+    #[doc(alias = "SDL_HapticEffectSupported")]
+    pub fn is_effect_supported(&self, effect: &HapticEffect) -> bool {
+        let raw = effect.to_ll();
+        unsafe { sys::haptic::SDL_HapticEffectSupported(self.raw, &raw) }
+    }
+
+    /// Upload an effect to the device. It plays when started with
+    /// [`run_effect`](Self::run_effect) or when its trigger button is pressed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use sdl3::haptic::{PeriodicEffect, Waveform, HAPTIC_INFINITY};
+    ///
+    /// let sdl = sdl3::init().unwrap();
+    /// let haptic_subsystem = sdl.haptic().unwrap();
+    /// let mut haptic = haptic_subsystem.open_from_mouse().unwrap();
+    ///
+    /// let effect = PeriodicEffect::new(Waveform::Triangle, 200, 16_000, HAPTIC_INFINITY);
+    /// let id = haptic.new_effect(&effect.into()).unwrap();
+    /// haptic.run_effect(id, 1).unwrap();
+    /// ```
+    #[doc(alias = "SDL_CreateHapticEffect")]
+    pub fn new_effect(&mut self, effect: &HapticEffect) -> Result<HapticEffectId, Error> {
+        let (raw, samples) = effect_to_ll(effect);
+        let id = unsafe { sys::haptic::SDL_CreateHapticEffect(self.raw, &raw) };
+        if id < 0 {
+            return Err(get_error());
+        }
+        self.keep_samples(id, samples);
+        Ok(HapticEffectId(id))
+    }
+
+    /// Change the parameters of an effect, which must be of the same kind. A playing
+    /// effect is updated in place.
+    #[doc(alias = "SDL_UpdateHapticEffect")]
+    pub fn update_effect(
+        &mut self,
+        effect_id: HapticEffectId,
+        effect: &HapticEffect,
+    ) -> Result<(), Error> {
+        let (raw, samples) = effect_to_ll(effect);
+        if unsafe { sys::haptic::SDL_UpdateHapticEffect(self.raw, effect_id.0, &raw) } {
+            self.keep_samples(effect_id.0, samples);
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
+    /// Store the samples an effect's data points at. Moving the `Vec` leaves its buffer
+    /// in place, so the pointer SDL kept stays valid until the effect is replaced or destroyed.
+    fn keep_samples(&mut self, id: c_int, samples: Option<Vec<u16>>) {
+        match samples {
+            Some(samples) => self.custom_samples.insert(id, samples),
+            None => self.custom_samples.remove(&id),
+        };
+    }
+
+    /// Play an effect `iterations` times in a row, or forever with [`HAPTIC_INFINITY`].
+    #[doc(alias = "SDL_RunHapticEffect")]
+    pub fn run_effect(&mut self, effect_id: HapticEffectId, iterations: u32) -> Result<(), Error> {
+        haptic_result(unsafe {
+            sys::haptic::SDL_RunHapticEffect(self.raw, effect_id.0, iterations)
+        })
+    }
+
+    #[doc(alias = "SDL_StopHapticEffect")]
+    pub fn stop_effect(&mut self, effect_id: HapticEffectId) -> Result<(), Error> {
+        haptic_result(unsafe { sys::haptic::SDL_StopHapticEffect(self.raw, effect_id.0) })
+    }
+
+    /// Stop and remove an effect from the device.
+    #[doc(alias = "SDL_DestroyHapticEffect")]
+    pub fn destroy_effect(&mut self, effect_id: HapticEffectId) {
+        unsafe { sys::haptic::SDL_DestroyHapticEffect(self.raw, effect_id.0) };
+        self.custom_samples.remove(&effect_id.0);
+    }
+
+    /// Return `true` if the effect is playing. Always `false` if the device lacks
+    /// [`HapticFeatures::STATUS`].
+    #[doc(alias = "SDL_GetHapticEffectStatus")]
+    pub fn effect_status(&self, effect_id: HapticEffectId) -> bool {
+        unsafe { sys::haptic::SDL_GetHapticEffectStatus(self.raw, effect_id.0) }
+    }
+
+    /// Stop all effects.
+    #[doc(alias = "SDL_StopHapticEffects")]
+    pub fn stop_effects(&mut self) -> Result<(), Error> {
+        haptic_result(unsafe { sys::haptic::SDL_StopHapticEffects(self.raw) })
+    }
+
+    /// Set the overall strength of the device, from 0 to 100 percent.
+    #[doc(alias = "SDL_SetHapticGain")]
+    pub fn set_gain(&mut self, gain: u8) -> Result<(), Error> {
+        haptic_result(unsafe { sys::haptic::SDL_SetHapticGain(self.raw, gain.min(100) as c_int) })
+    }
+
+    /// Set the strength of autocenter from 0 to 100 percent; 0 disables it.
+    #[doc(alias = "SDL_SetHapticAutocenter")]
+    pub fn set_autocenter(&mut self, autocenter: u8) -> Result<(), Error> {
+        haptic_result(unsafe {
+            sys::haptic::SDL_SetHapticAutocenter(self.raw, autocenter.min(100) as c_int)
+        })
+    }
+
+    /// Pause all effects. Effects can't be modified while paused.
+    #[doc(alias = "SDL_PauseHaptic")]
+    pub fn pause(&mut self) -> Result<(), Error> {
+        haptic_result(unsafe { sys::haptic::SDL_PauseHaptic(self.raw) })
+    }
+
+    #[doc(alias = "SDL_ResumeHaptic")]
+    pub fn resume(&mut self) -> Result<(), Error> {
+        haptic_result(unsafe { sys::haptic::SDL_ResumeHaptic(self.raw) })
+    }
+
+    #[doc(alias = "SDL_InitHapticRumble")]
+    fn init_rumble(&mut self) {
+        if !self.rumble_initialized {
+            self.rumble_initialized = unsafe { sys::haptic::SDL_InitHapticRumble(self.raw) };
+        }
+    }
+
+    /// Run a simple rumble effect on the haptic device. The rumble is set up on first use,
+    /// which takes one of the device's effect slots.
+    pub fn rumble_play(&mut self, strength: f32, duration: u32) {
+        self.init_rumble();
+        unsafe { sys::haptic::SDL_PlayHapticRumble(self.raw, strength, duration) };
+    }
+
+    /// Stop the simple rumble on the haptic device.
+    #[doc(alias = "SDL_HapticRumbleStop")]
+    pub fn rumble_stop(&mut self) {
+        unsafe { sys::haptic::SDL_StopHapticRumble(self.raw) };
+    }
+}
+
+impl Drop for Haptic {
+    #[doc(alias = "SDL_HapticClose")]
+    fn drop(&mut self) {
+        unsafe { sys::haptic::SDL_CloseHaptic(self.raw) }
+    }
+}
+
+/// Convert `effect`, pointing a custom effect's data at a copy of its samples that
+/// [`Haptic::keep_samples`] then stores for as long as SDL uses it.
+fn effect_to_ll(effect: &HapticEffect) -> (sys::haptic::SDL_HapticEffect, Option<Vec<u16>>) {
+    let mut raw = effect.to_ll();
+    let samples = match effect {
+        HapticEffect::Custom(custom) => {
+            let mut samples = custom.samples.clone();
+            raw.custom.data = samples.as_mut_ptr();
+            Some(samples)
+        }
+        _ => None,
+    };
+    (raw, samples)
+}
+
+fn haptic_result(result: bool) -> Result<(), Error> {
+    if result {
+        Ok(())
+    } else {
+        Err(get_error())
+    }
+}
+
+/// Convert C string `c_str` to a String. Return an empty string if
+/// `c_str` is NULL.
+fn c_str_to_string(c_str: *const c_char) -> String {
+    if c_str.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(c_str) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_effect_to_ll_owns_samples() {
+        let effect: HapticEffect = CustomEffect::new(1, 10, vec![7, 8, 9], 30).into();
+        let (raw, samples) = effect_to_ll(&effect);
+        let samples = samples.unwrap();
+        drop(effect);
+        let mut kept = HashMap::new();
+        kept.insert(0, samples);
+        unsafe {
+            assert_eq!(raw.custom.data, kept[&0].as_ptr() as *mut u16);
+            assert_eq!(*raw.custom.data.add(2), 9);
+        }
+
+        let effect: HapticEffect = PeriodicEffect::new(Waveform::Sine, 100, 1000, 50).into();
+        assert!(effect_to_ll(&effect).1.is_none());
+    }
+
+    #[test]
+    fn test_features() {
+        let bits = [
+            (HapticFeatures::CONSTANT, 1 << 0),
+            (HapticFeatures::SINE, 1 << 1),
+            (HapticFeatures::SQUARE, 1 << 2),
+            (HapticFeatures::TRIANGLE, 1 << 3),
+            (HapticFeatures::SAWTOOTH_UP, 1 << 4),
+            (HapticFeatures::SAWTOOTH_DOWN, 1 << 5),
+            (HapticFeatures::RAMP, 1 << 6),
+            (HapticFeatures::SPRING, 1 << 7),
+            (HapticFeatures::DAMPER, 1 << 8),
+            (HapticFeatures::INERTIA, 1 << 9),
+            (HapticFeatures::FRICTION, 1 << 10),
+            (HapticFeatures::LEFT_RIGHT, 1 << 11),
+            (HapticFeatures::CUSTOM, 1 << 15),
+            (HapticFeatures::GAIN, 1 << 16),
+            (HapticFeatures::AUTOCENTER, 1 << 17),
+            (HapticFeatures::STATUS, 1 << 18),
+            (HapticFeatures::PAUSE, 1 << 19),
+        ];
+        for (feature, bit) in bits {
+            assert_eq!(feature.bits(), bit, "{:?}", feature);
+        }
+        // Reserved bits are dropped.
+        assert_eq!(
+            HapticFeatures::from_bits_truncate((1 << 12) | 1),
+            HapticFeatures::CONSTANT
+        );
+
+        // Each effect maps to the feature a device needs to play it.
+        let effects: [(HapticEffect, HapticFeatures); 4] = [
+            (
+                PeriodicEffect::new(Waveform::Triangle, 1, 1, 1).into(),
+                HapticFeatures::TRIANGLE,
+            ),
+            (
+                ConditionEffect::new(Condition::Friction, 1).into(),
+                HapticFeatures::FRICTION,
+            ),
+            (
+                LeftRightEffect::new(1, 1, 1).into(),
+                HapticFeatures::LEFT_RIGHT,
+            ),
+            (
+                CustomEffect::new(1, 1, vec![1], 1).into(),
+                HapticFeatures::CUSTOM,
+            ),
+        ];
+        for (effect, feature) in effects {
+            assert_eq!(
+                HapticFeatures::from_bits_truncate(effect.feature() as u32),
+                feature
+            );
+        }
+    }
+}
@@ -7,11 +7,14 @@ use crate::get_error;
 use crate::guid::Guid;
 use crate::Error;
 use crate::JoystickSubsystem;
-use libc::{c_char, c_void};
-use std::ffi::CStr;
+use libc::{c_char, c_int, c_void};
+use std::ffi::{CStr, CString};
 use std::fmt;
-use sys::joystick::SDL_JoystickID;
+use std::ptr;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use sys::joystick::{SDL_JoystickConnectionState, SDL_JoystickID, SDL_JoystickType};
 use sys::power::{SDL_PowerState, SDL_POWERSTATE_UNKNOWN};
+use sys::sensor::SDL_SensorType;
 use sys::stdinc::SDL_free;
 
 pub struct JoystickInstance {
@@ -218,6 +221,14 @@ impl Joystick {
         }
     }
 
+    /// Retrieve how this joystick is connected to the system
+    #[doc(alias = "SDL_GetJoystickConnectionState")]
+    pub fn connection_state(&self) -> Result<ConnectionState, Error> {
+        let result = unsafe { sys::joystick::SDL_GetJoystickConnectionState(self.raw) };
+
+        ConnectionState::from_ll(result).ok_or_else(get_error)
+    }
+
     /// Retrieve the number of axes for this joystick
     #[doc(alias = "SDL_GetNumJoystickAxes")]
     pub fn num_axes(&self) -> u32 {
@@ -467,6 +478,418 @@ impl Drop for Joystick {
     }
 }
 
+/// The kind of device a joystick is.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum JoystickType {
+    #[default]
+    Unknown,
+    Gamepad,
+    Wheel,
+    ArcadeStick,
+    FlightStick,
+    DancePad,
+    Guitar,
+    DrumKit,
+    ArcadePad,
+    Throttle,
+}
+
+impl JoystickType {
+    pub fn from_ll(raw: SDL_JoystickType) -> JoystickType {
+        match raw {
+            SDL_JoystickType::GAMEPAD => JoystickType::Gamepad,
+            SDL_JoystickType::WHEEL => JoystickType::Wheel,
+            SDL_JoystickType::ARCADE_STICK => JoystickType::ArcadeStick,
+            SDL_JoystickType::FLIGHT_STICK => JoystickType::FlightStick,
+            SDL_JoystickType::DANCE_PAD => JoystickType::DancePad,
+            SDL_JoystickType::GUITAR => JoystickType::Guitar,
+            SDL_JoystickType::DRUM_KIT => JoystickType::DrumKit,
+            SDL_JoystickType::ARCADE_PAD => JoystickType::ArcadePad,
+            SDL_JoystickType::THROTTLE => JoystickType::Throttle,
+            _ => JoystickType::Unknown,
+        }
+    }
+
+    pub fn to_ll(self) -> SDL_JoystickType {
+        match self {
+            JoystickType::Unknown => SDL_JoystickType::UNKNOWN,
+            JoystickType::Gamepad => SDL_JoystickType::GAMEPAD,
+            JoystickType::Wheel => SDL_JoystickType::WHEEL,
+            JoystickType::ArcadeStick => SDL_JoystickType::ARCADE_STICK,
+            JoystickType::FlightStick => SDL_JoystickType::FLIGHT_STICK,
+            JoystickType::DancePad => SDL_JoystickType::DANCE_PAD,
+            JoystickType::Guitar => SDL_JoystickType::GUITAR,
+            JoystickType::DrumKit => SDL_JoystickType::DRUM_KIT,
+            JoystickType::ArcadePad => SDL_JoystickType::ARCADE_PAD,
+            JoystickType::Throttle => SDL_JoystickType::THROTTLE,
+        }
+    }
+}
+
+/// How a joystick is connected to the system.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ConnectionState {
+    Unknown,
+    Wired,
+    Wireless,
+}
+
+impl ConnectionState {
+    /// Returns `None` for `SDL_JOYSTICK_CONNECTION_INVALID`.
+    pub fn from_ll(raw: SDL_JoystickConnectionState) -> Option<ConnectionState> {
+        match raw {
+            SDL_JoystickConnectionState::WIRED => Some(ConnectionState::Wired),
+            SDL_JoystickConnectionState::WIRELESS => Some(ConnectionState::Wireless),
+            SDL_JoystickConnectionState::INVALID => None,
+            _ => Some(ConnectionState::Unknown),
+        }
+    }
+
+    pub fn to_ll(self) -> SDL_JoystickConnectionState {
+        match self {
+            ConnectionState::Unknown => SDL_JoystickConnectionState::UNKNOWN,
+            ConnectionState::Wired => SDL_JoystickConnectionState::WIRED,
+            ConnectionState::Wireless => SDL_JoystickConnectionState::WIRELESS,
+        }
+    }
+}
+
+pub type VirtualRumbleCallback = Box<dyn FnMut(u16, u16) -> bool + Send>;
+pub type VirtualLedCallback = Box<dyn FnMut(u8, u8, u8) -> bool + Send>;
+pub type VirtualPlayerIndexCallback = Box<dyn FnMut(i32) + Send>;
+pub type VirtualEffectCallback = Box<dyn FnMut(&[u8]) -> bool + Send>;
+pub type VirtualSensorsEnabledCallback = Box<dyn FnMut(bool) -> bool + Send>;
+
+/// Describes a virtual joystick to create with [`JoystickSubsystem::attach_virtual`].
+///
+/// The callbacks are called when the application drives the device's outputs, and may be
+/// called from any thread. A callback that is `None` makes the matching operation fail as
+/// unsupported.
+///
+/// # Example
+/// ```no_run
+/// use sdl3::joystick::{JoystickType, VirtualJoystickDesc};
+///
+/// let desc = VirtualJoystickDesc {
+///     joystick_type: JoystickType::Gamepad,
+///     name: Some("Test pad".to_owned()),
+///     naxes: 6,
+///     nbuttons: 15,
+///     rumble: Some(Box::new(|low, high| {
+///         println!("rumble {} {}", low, high);
+///         true
+///     })),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Default)]
+pub struct VirtualJoystickDesc {
+    pub joystick_type: JoystickType,
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub naxes: u16,
+    pub nbuttons: u16,
+    pub nhats: u16,
+    /// The number of fingers each touchpad supports.
+    pub touchpads: Vec<u16>,
+    /// The sensors and their data rate in events per second. With the `hidapi` feature a
+    /// `sensor::SensorType` converts to the sensor type with `into()`.
+    pub sensors: Vec<(SDL_SensorType, f32)>,
+    /// For gamepads, a mask of the `gamepad::Button`s the device has, by `1 << button`.
+    /// Zero derives the buttons from `nbuttons`.
+    pub button_mask: u32,
+    /// For gamepads, a mask of the `gamepad::Axis` values the device has, by `1 << axis`.
+    /// Zero derives the axes from `naxes`.
+    pub axis_mask: u32,
+    pub name: Option<String>,
+    /// Called with the low and high frequency rumble intensities.
+    pub rumble: Option<VirtualRumbleCallback>,
+    /// Called with the left and right trigger rumble intensities.
+    pub rumble_triggers: Option<VirtualRumbleCallback>,
+    /// Called with the red, green and blue LED values.
+    pub set_led: Option<VirtualLedCallback>,
+    pub set_player_index: Option<VirtualPlayerIndexCallback>,
+    /// Called with the data of a device specific effect packet.
+    pub send_effect: Option<VirtualEffectCallback>,
+    pub set_sensors_enabled: Option<VirtualSensorsEnabledCallback>,
+}
+
+#[derive(Default)]
+struct VirtualJoystickCallbacks {
+    rumble: Option<Mutex<VirtualRumbleCallback>>,
+    rumble_triggers: Option<Mutex<VirtualRumbleCallback>>,
+    set_led: Option<Mutex<VirtualLedCallback>>,
+    set_player_index: Option<Mutex<VirtualPlayerIndexCallback>>,
+    send_effect: Option<Mutex<VirtualEffectCallback>>,
+    set_sensors_enabled: Option<Mutex<VirtualSensorsEnabledCallback>>,
+}
+
+/// Locks a callback called from SDL. A lock poisoned by a panicking callback is recovered
+/// rather than panicking across the C boundary.
+fn lock<T>(callback: &Mutex<T>) -> MutexGuard<'_, T> {
+    callback.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+unsafe fn virtual_callbacks<'a>(userdata: *mut c_void) -> &'a VirtualJoystickCallbacks {
+    &*(userdata as *const VirtualJoystickCallbacks)
+}
+
+unsafe extern "C" fn c_virtual_rumble(userdata: *mut c_void, low: u16, high: u16) -> bool {
+    match virtual_callbacks(userdata).rumble {
+        Some(ref callback) => (lock(callback))(low, high),
+        None => false,
+    }
+}
+
+unsafe extern "C" fn c_virtual_rumble_triggers(
+    userdata: *mut c_void,
+    left: u16,
+    right: u16,
+) -> bool {
+    match virtual_callbacks(userdata).rumble_triggers {
+        Some(ref callback) => (lock(callback))(left, right),
+        None => false,
+    }
+}
+
+unsafe extern "C" fn c_virtual_set_led(
+    userdata: *mut c_void,
+    red: u8,
+    green: u8,
+    blue: u8,
+) -> bool {
+    match virtual_callbacks(userdata).set_led {
+        Some(ref callback) => (lock(callback))(red, green, blue),
+        None => false,
+    }
+}
+
+unsafe extern "C" fn c_virtual_set_player_index(userdata: *mut c_void, player_index: c_int) {
+    if let Some(ref callback) = virtual_callbacks(userdata).set_player_index {
+        (lock(callback))(player_index)
+    }
+}
+
+unsafe extern "C" fn c_virtual_send_effect(
+    userdata: *mut c_void,
+    data: *const c_void,
+    size: c_int,
+) -> bool {
+    match virtual_callbacks(userdata).send_effect {
+        Some(ref callback) => {
+            let data = if data.is_null() || size <= 0 {
+                &[]
+            } else {
+                std::slice::from_raw_parts(data as *const u8, size as usize)
+            };
+            (lock(callback))(data)
+        }
+        None => false,
+    }
+}
+
+unsafe extern "C" fn c_virtual_set_sensors_enabled(userdata: *mut c_void, enabled: bool) -> bool {
+    match virtual_callbacks(userdata).set_sensors_enabled {
+        Some(ref callback) => (lock(callback))(enabled),
+        None => false,
+    }
+}
+
+impl JoystickSubsystem {
+    /// Attaches a virtual joystick and opens it.
+    ///
+    /// The device shows up like a physical one, with `JoyDeviceAdded` (and, for gamepads,
+    /// `ControllerDeviceAdded`) events. Its inputs are set through the returned
+    /// [`VirtualJoystick`], which detaches the device when dropped.
+    #[doc(alias = "SDL_AttachVirtualJoystick")]
+    pub fn attach_virtual(&self, desc: VirtualJoystickDesc) -> Result<VirtualJoystick, Error> {
+        let name = match desc.name {
+            Some(ref name) => Some(CString::new(name.as_str()).map_err(|e| Error(e.to_string()))?),
+            None => None,
+        };
+        let touchpads: Vec<sys::joystick::SDL_VirtualJoystickTouchpadDesc> = desc
+            .touchpads
+            .iter()
+            .map(|&nfingers| sys::joystick::SDL_VirtualJoystickTouchpadDesc {
+                nfingers,
+                padding: [0; 3],
+            })
+            .collect();
+        let sensors: Vec<sys::joystick::SDL_VirtualJoystickSensorDesc> = desc
+            .sensors
+            .iter()
+            .map(
+                |&(sensor_type, rate)| sys::joystick::SDL_VirtualJoystickSensorDesc {
+                    r#type: sensor_type,
+                    rate,
+                },
+            )
+            .collect();
+
+        let callbacks = Box::new(VirtualJoystickCallbacks {
+            rumble: desc.rumble.map(Mutex::new),
+            rumble_triggers: desc.rumble_triggers.map(Mutex::new),
+            set_led: desc.set_led.map(Mutex::new),
+            set_player_index: desc.set_player_index.map(Mutex::new),
+            send_effect: desc.send_effect.map(Mutex::new),
+            set_sensors_enabled: desc.set_sensors_enabled.map(Mutex::new),
+        });
+
+        let mut raw_desc = sys::joystick::SDL_VirtualJoystickDesc::new();
+        raw_desc.r#type = desc.joystick_type.to_ll().0 as u16;
+        raw_desc.vendor_id = desc.vendor_id;
+        raw_desc.product_id = desc.product_id;
+        raw_desc.naxes = desc.naxes;
+        raw_desc.nbuttons = desc.nbuttons;
+        raw_desc.nhats = desc.nhats;
+        raw_desc.ntouchpads = touchpads.len() as u16;
+        raw_desc.touchpads = touchpads.as_ptr();
+        raw_desc.nsensors = sensors.len() as u16;
+        raw_desc.sensors = sensors.as_ptr();
+        raw_desc.button_mask = desc.button_mask;
+        raw_desc.axis_mask = desc.axis_mask;
+        raw_desc.name = name.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+        raw_desc.userdata = &*callbacks as *const VirtualJoystickCallbacks as *mut c_void;
+        raw_desc.Rumble = Some(c_virtual_rumble);
+        raw_desc.RumbleTriggers = Some(c_virtual_rumble_triggers);
+        raw_desc.SetLED = Some(c_virtual_set_led);
+        raw_desc.SetPlayerIndex = Some(c_virtual_set_player_index);
+        raw_desc.SendEffect = Some(c_virtual_send_effect);
+        raw_desc.SetSensorsEnabled = Some(c_virtual_set_sensors_enabled);
+
+        // SDL copies the name, touchpads and sensors.
+        let id = unsafe { sys::joystick::SDL_AttachVirtualJoystick(&raw_desc) };
+        if id == 0 {
+            return Err(get_error());
+        }
+        let raw = unsafe { sys::joystick::SDL_OpenJoystick(id) };
+        if raw.is_null() {
+            let error = get_error();
+            unsafe { sys::joystick::SDL_DetachVirtualJoystick(id) };
+            return Err(error);
+        }
+        Ok(VirtualJoystick {
+            subsystem: self.clone(),
+            id,
+            raw,
+            _callbacks: callbacks,
+        })
+    }
+
+    /// Returns `true` if the joystick is a virtual one.
+    #[doc(alias = "SDL_IsJoystickVirtual")]
+    pub fn is_virtual(&self, instance_id: SDL_JoystickID) -> bool {
+        unsafe { sys::joystick::SDL_IsJoystickVirtual(instance_id) }
+    }
+}
+
+/// A virtual joystick attached with [`JoystickSubsystem::attach_virtual`].
+///
+/// Input changes take effect on the next joystick update, usually during event
+/// processing. The device is detached when this is dropped.
+pub struct VirtualJoystick {
+    subsystem: JoystickSubsystem,
+    id: SDL_JoystickID,
+    raw: *mut sys::joystick::SDL_Joystick,
+    _callbacks: Box<VirtualJoystickCallbacks>,
+}
+
+impl VirtualJoystick {
+    #[inline]
+    pub const fn subsystem(&self) -> &JoystickSubsystem {
+        &self.subsystem
+    }
+
+    /// The instance ID to open the device with, as a `Joystick` or a `Gamepad`.
+    pub fn instance_id(&self) -> SDL_JoystickID {
+        self.id
+    }
+
+    #[doc(alias = "SDL_SetJoystickVirtualAxis")]
+    pub fn set_axis(&mut self, axis: u32, value: i16) -> Result<(), IntegerOrSdlError> {
+        let axis = validate_int(axis, "axis")?;
+        let result = unsafe { sys::joystick::SDL_SetJoystickVirtualAxis(self.raw, axis, value) };
+        virtual_result(result)
+    }
+
+    #[doc(alias = "SDL_SetJoystickVirtualButton")]
+    pub fn set_button(&mut self, button: u32, down: bool) -> Result<(), IntegerOrSdlError> {
+        let button = validate_int(button, "button")?;
+        let result = unsafe { sys::joystick::SDL_SetJoystickVirtualButton(self.raw, button, down) };
+        virtual_result(result)
+    }
+
+    #[doc(alias = "SDL_SetJoystickVirtualHat")]
+    pub fn set_hat(&mut self, hat: u32, state: HatState) -> Result<(), IntegerOrSdlError> {
+        let hat = validate_int(hat, "hat")?;
+        let result =
+            unsafe { sys::joystick::SDL_SetJoystickVirtualHat(self.raw, hat, state.to_raw()) };
+        virtual_result(result)
+    }
+
+    /// Sets the state of a finger on a touchpad. `x` and `y` range from 0 to 1, from the
+    /// top left corner.
+    #[doc(alias = "SDL_SetJoystickVirtualTouchpad")]
+    pub fn set_touchpad(
+        &mut self,
+        touchpad: u32,
+        finger: u32,
+        down: bool,
+        x: f32,
+        y: f32,
+        pressure: f32,
+    ) -> Result<(), IntegerOrSdlError> {
+        let touchpad = validate_int(touchpad, "touchpad")?;
+        let finger = validate_int(finger, "finger")?;
+        let result = unsafe {
+            sys::joystick::SDL_SetJoystickVirtualTouchpad(
+                self.raw, touchpad, finger, down, x, y, pressure,
+            )
+        };
+        virtual_result(result)
+    }
+
+    /// Sends a sensor reading. `timestamp` is the sensor's own timestamp in nanoseconds,
+    /// or 0 if there is none.
+    #[doc(alias = "SDL_SendJoystickVirtualSensorData")]
+    pub fn send_sensor_data(
+        &mut self,
+        sensor_type: SDL_SensorType,
+        timestamp: u64,
+        data: &[f32],
+    ) -> Result<(), IntegerOrSdlError> {
+        let len = validate_int(data.len() as u32, "data")?;
+        let result = unsafe {
+            sys::joystick::SDL_SendJoystickVirtualSensorData(
+                self.raw,
+                sensor_type,
+                timestamp,
+                data.as_ptr(),
+                len,
+            )
+        };
+        virtual_result(result)
+    }
+}
+
+fn virtual_result(result: bool) -> Result<(), IntegerOrSdlError> {
+    if result {
+        Ok(())
+    } else {
+        Err(IntegerOrSdlError::SdlError(get_error()))
+    }
+}
+
+impl Drop for VirtualJoystick {
+    #[doc(alias = "SDL_DetachVirtualJoystick")]
+    fn drop(&mut self) {
+        unsafe {
+            sys::joystick::SDL_CloseJoystick(self.raw);
+            sys::joystick::SDL_DetachVirtualJoystick(self.id);
+        }
+    }
+}
+
 /// This is represented in SDL2 as a bitfield but obviously not all
 /// combinations make sense: 5 for instance would mean up and down at
 /// the same time... To simplify things I turn it into an enum which
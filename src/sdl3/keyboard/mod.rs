@@ -1,8 +1,12 @@
+use crate::get_error;
 use crate::rect::Rect;
 use crate::video::Window;
+use crate::Error;
 use crate::EventPump;
 
 use crate::sys;
+use libc::{c_char, c_void};
+use std::ffi::CStr;
 use std::fmt;
 use sys::video::SDL_GetWindowID;
 
@@ -149,6 +153,17 @@ impl Iterator for PressedScancodeIterator<'_> {
     }
 }
 
+/// Instance ID of a keyboard, unique for as long as the keyboard stays connected.
+pub type KeyboardId = sys::keyboard::SDL_KeyboardID;
+
+/// A connected keyboard, as returned by `KeyboardUtil::keyboards`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyboardInstance {
+    pub id: KeyboardId,
+    /// Implementation-dependent name; empty if the driver doesn't report one.
+    pub name: String,
+}
+
 impl crate::Sdl {
     #[inline]
     pub fn keyboard(&self) -> KeyboardUtil {
@@ -191,6 +206,36 @@ impl KeyboardUtil {
         }
     }
 
+    /// Returns `true` if at least one keyboard is connected.
+    #[doc(alias = "SDL_HasKeyboard")]
+    pub fn has_keyboard(&self) -> bool {
+        unsafe { sys::keyboard::SDL_HasKeyboard() }
+    }
+
+    /// Gets the IDs and names of the currently connected keyboards.
+    ///
+    /// Keyboard events carry the ID of the originating device in their `which` field,
+    /// so this can be used to tell several physical keyboards apart.
+    #[doc(alias = "SDL_GetKeyboards")]
+    pub fn keyboards(&self) -> Result<Vec<KeyboardInstance>, Error> {
+        let mut count = 0;
+        unsafe {
+            let ids = sys::keyboard::SDL_GetKeyboards(&mut count);
+            if ids.is_null() {
+                return Err(get_error());
+            }
+            let keyboards = std::slice::from_raw_parts(ids, count as usize)
+                .iter()
+                .map(|&id| KeyboardInstance {
+                    id,
+                    name: c_str_to_string(sys::keyboard::SDL_GetKeyboardNameForID(id)),
+                })
+                .collect();
+            sys::stdinc::SDL_free(ids as *mut c_void);
+            Ok(keyboards)
+        }
+    }
+
     #[doc(alias = "SDL_GetModState")]
     pub fn mod_state(&self) -> Mod {
         unsafe { Mod::from_bits(sys::keyboard::SDL_GetModState() as u16).unwrap() }
@@ -261,3 +306,13 @@ impl TextInputUtil {
         unsafe { sys::keyboard::SDL_ScreenKeyboardShown(window.raw()) }
     }
 }
+
+fn c_str_to_string(c_str: *const c_char) -> String {
+    if c_str.is_null() {
+        String::new()
+    } else {
+        let bytes = unsafe { CStr::from_ptr(c_str as *const _).to_bytes() };
+
+        String::from_utf8_lossy(bytes).to_string()
+    }
+}
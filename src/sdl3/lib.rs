@@ -54,6 +54,8 @@
     clippy::missing_safety_doc
 )]
 
+#[cfg(feature = "ash")]
+pub extern crate ash;
 #[macro_use]
 extern crate bitflags;
 #[cfg(feature = "gfx")]
@@ -82,8 +84,10 @@ pub mod iostream;
 pub mod joystick;
 pub mod keyboard;
 pub mod log;
+pub mod main_thread;
 pub mod messagebox;
 pub mod mouse;
+pub mod pen;
 pub mod pixels;
 pub mod properties;
 pub mod rect;
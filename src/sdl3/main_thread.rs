@@ -0,0 +1,82 @@
+//! Running code on the main thread from other threads.
+//!
+//! Queued closures run on the main thread while it processes events, that is during
+//! [`EventPump::poll_event`](crate::EventPump::poll_event),
+//! [`EventPump::pump_events`](crate::EventPump::pump_events) and friends.
+
+use crate::get_error;
+use crate::sys;
+use crate::Error;
+use libc::c_void;
+use std::sync::mpsc;
+
+type MainThreadCallback = Box<dyn FnOnce() + Send>;
+
+/// Returns `true` if called on the main thread.
+#[doc(alias = "SDL_IsMainThread")]
+pub fn is_main_thread() -> bool {
+    unsafe { sys::init::SDL_IsMainThread() }
+}
+
+/// Runs `callback` on the main thread.
+///
+/// Called on the main thread, `callback` runs immediately. Otherwise it's queued for the
+/// main thread's next round of event processing, and with `wait` set this blocks until it
+/// has run. Don't wait while the main thread is itself waiting for the calling thread.
+///
+/// Closures that are still queued when SDL shuts down are never run.
+#[doc(alias = "SDL_RunOnMainThread")]
+pub fn run<F>(callback: F, wait: bool) -> Result<(), Error>
+where
+    F: FnOnce() + Send + 'static,
+{
+    let callback: Box<MainThreadCallback> = Box::new(Box::new(callback));
+    let userdata = Box::into_raw(callback) as *mut c_void;
+    let result =
+        unsafe { sys::init::SDL_RunOnMainThread(Some(c_main_thread_callback), userdata, wait) };
+    if result {
+        Ok(())
+    } else {
+        // SDL didn't take the callback, so it never runs.
+        drop(unsafe { Box::from_raw(userdata as *mut MainThreadCallback) });
+        Err(get_error())
+    }
+}
+
+/// Runs `callback` on the main thread and returns its result, blocking until it has run.
+///
+/// See [`run`] for when the closure is run.
+///
+/// # Example
+/// ```no_run
+/// use sdl3::main_thread;
+///
+/// let loader = std::thread::spawn(|| {
+///     let pixels = vec![0u8; 64 * 64 * 4]; // decoded off the main thread
+///     main_thread::run_sync(move || {
+///         assert!(main_thread::is_main_thread());
+///         pixels.len() // create the texture here
+///     })
+/// });
+/// ```
+pub fn run_sync<F, R>(callback: F) -> Result<R, Error>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let (sender, receiver) = mpsc::sync_channel(1);
+    run(
+        move || {
+            let _ = sender.send(callback());
+        },
+        true,
+    )?;
+    receiver
+        .recv()
+        .map_err(|_| Error("Main thread callback was not run".to_owned()))
+}
+
+unsafe extern "C" fn c_main_thread_callback(userdata: *mut c_void) {
+    let callback = Box::from_raw(userdata as *mut MainThreadCallback);
+    callback();
+}
@@ -4,7 +4,9 @@ use crate::sys;
 use crate::video;
 use crate::Error;
 use crate::EventPump;
+use libc::{c_char, c_void};
 use std::convert::TryInto;
+use std::ffi::CStr;
 use std::mem::transmute;
 use sys::mouse::{
     SDL_GetWindowRelativeMouseMode, SDL_MouseWheelDirection, SDL_SetWindowRelativeMouseMode,
@@ -376,6 +378,23 @@ impl Iterator for PressedMouseButtonIterator<'_> {
     }
 }
 
+/// Instance ID of a mouse, unique for as long as the mouse stays connected.
+///
+/// Events synthesized from touch or pen input use `TOUCH_MOUSE_ID` or
+/// `crate::pen::PEN_MOUSE_ID` instead of a real device ID.
+pub type MouseId = sys::mouse::SDL_MouseID;
+
+/// The `which` value of mouse events synthesized from touch input.
+pub const TOUCH_MOUSE_ID: MouseId = sys::touch::SDL_TOUCH_MOUSEID;
+
+/// A connected mouse, as returned by `MouseUtil::mice`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MouseInstance {
+    pub id: MouseId,
+    /// Implementation-dependent name; empty if the driver doesn't report one.
+    pub name: String,
+}
+
 impl crate::Sdl {
     #[inline]
     pub fn mouse(&self) -> MouseUtil {
@@ -398,6 +417,36 @@ pub struct MouseUtil {
 }
 
 impl MouseUtil {
+    /// Returns `true` if at least one mouse is connected.
+    #[doc(alias = "SDL_HasMouse")]
+    pub fn has_mouse(&self) -> bool {
+        unsafe { sys::mouse::SDL_HasMouse() }
+    }
+
+    /// Gets the IDs and names of the currently connected mice.
+    ///
+    /// Mouse events carry the ID of the originating device in their `which` field,
+    /// so this can be used to tell several physical mice apart.
+    #[doc(alias = "SDL_GetMice")]
+    pub fn mice(&self) -> Result<Vec<MouseInstance>, Error> {
+        let mut count = 0;
+        unsafe {
+            let ids = sys::mouse::SDL_GetMice(&mut count);
+            if ids.is_null() {
+                return Err(get_error());
+            }
+            let mice = std::slice::from_raw_parts(ids, count as usize)
+                .iter()
+                .map(|&id| MouseInstance {
+                    id,
+                    name: c_str_to_string(sys::mouse::SDL_GetMouseNameForID(id)),
+                })
+                .collect();
+            sys::stdinc::SDL_free(ids as *mut c_void);
+            Ok(mice)
+        }
+    }
+
     /// Gets the id of the window which currently has mouse focus.
     #[doc(alias = "SDL_GetMouseFocus")]
     pub fn focused_window_id(&self) -> Option<u32> {
@@ -448,3 +497,13 @@ impl MouseUtil {
         }
     }
 }
+
+fn c_str_to_string(c_str: *const c_char) -> String {
+    if c_str.is_null() {
+        String::new()
+    } else {
+        let bytes = unsafe { CStr::from_ptr(c_str as *const _).to_bytes() };
+
+        String::from_utf8_lossy(bytes).to_string()
+    }
+}
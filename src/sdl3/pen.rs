@@ -0,0 +1,73 @@
+//! Pen (stylus and tablet) input.
+//!
+//! Pens are reported through [`Event::PenProximityIn`](crate::event::Event::PenProximityIn)
+//! and the other `Pen*` events. Each pen gets an ID for as long as it is in
+//! proximity of the tablet; SDL makes no promise that a pen keeps its ID after it
+//! leaves and comes back.
+
+use crate::sys;
+use sys::pen::SDL_PenAxis;
+
+/// The ID of a pen, valid while it is in proximity.
+pub type PenId = sys::pen::SDL_PenID;
+
+/// The mouse ID used for mouse events synthesized from pen input.
+pub const PEN_MOUSE_ID: sys::mouse::SDL_MouseID = sys::pen::SDL_PEN_MOUSEID;
+
+/// The touch ID used for touch events synthesized from pen input.
+pub const PEN_TOUCH_ID: sys::touch::SDL_TouchID = sys::pen::SDL_PEN_TOUCHID;
+
+bitflags! {
+    /// The state of a pen: whether its tip is down and which buttons are held.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct PenInputFlags: sys::pen::SDL_PenInputFlags {
+        /// The pen is touching the tablet.
+        const DOWN = sys::pen::SDL_PEN_INPUT_DOWN;
+        const BUTTON_1 = sys::pen::SDL_PEN_INPUT_BUTTON_1;
+        const BUTTON_2 = sys::pen::SDL_PEN_INPUT_BUTTON_2;
+        const BUTTON_3 = sys::pen::SDL_PEN_INPUT_BUTTON_3;
+        const BUTTON_4 = sys::pen::SDL_PEN_INPUT_BUTTON_4;
+        const BUTTON_5 = sys::pen::SDL_PEN_INPUT_BUTTON_5;
+        /// The eraser end of the pen is in use.
+        const ERASER_TIP = sys::pen::SDL_PEN_INPUT_ERASER_TIP;
+    }
+}
+
+/// A continuous value reported by a pen.
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum PenAxis {
+    /// Pressure from `0.0` (none) to `1.0` (full).
+    Pressure = SDL_PenAxis::PRESSURE.0,
+    /// Horizontal tilt in degrees, from `-90.0` to `90.0`.
+    XTilt = SDL_PenAxis::XTILT.0,
+    /// Vertical tilt in degrees, from `-90.0` to `90.0`.
+    YTilt = SDL_PenAxis::YTILT.0,
+    /// Distance to the tablet, from `0.0` to `1.0`.
+    Distance = SDL_PenAxis::DISTANCE.0,
+    /// Barrel rotation in degrees, from `-180.0` to `179.9`.
+    Rotation = SDL_PenAxis::ROTATION.0,
+    /// Pen finger wheel or slider, from `0.0` to `1.0`.
+    Slider = SDL_PenAxis::SLIDER.0,
+    /// Barrel pressure from `0.0` to `1.0`.
+    TangentialPressure = SDL_PenAxis::TANGENTIAL_PRESSURE.0,
+}
+
+impl PenAxis {
+    pub fn from_ll(axis: SDL_PenAxis) -> Option<PenAxis> {
+        Some(match axis {
+            SDL_PenAxis::PRESSURE => PenAxis::Pressure,
+            SDL_PenAxis::XTILT => PenAxis::XTilt,
+            SDL_PenAxis::YTILT => PenAxis::YTilt,
+            SDL_PenAxis::DISTANCE => PenAxis::Distance,
+            SDL_PenAxis::ROTATION => PenAxis::Rotation,
+            SDL_PenAxis::SLIDER => PenAxis::Slider,
+            SDL_PenAxis::TANGENTIAL_PRESSURE => PenAxis::TangentialPressure,
+            _ => return None,
+        })
+    }
+
+    pub fn to_ll(self) -> SDL_PenAxis {
+        SDL_PenAxis(self as i32)
+    }
+}
@@ -12,18 +12,18 @@ use std::convert::TryFrom;
 use std::error;
 use std::ffi::{CStr, CString, NulError};
 use std::ops::{Deref, DerefMut};
-use std::ptr::{null, null_mut};
+use std::ptr::null_mut;
 use std::sync::Arc;
 use std::{fmt, mem, ptr};
 use sys::properties::{
     SDL_CreateProperties, SDL_DestroyProperties, SDL_SetNumberProperty, SDL_SetStringProperty,
 };
-use sys::stdinc::{SDL_FunctionPointer, SDL_free, Uint64};
+use sys::stdinc::{SDL_FunctionPointer, SDL_free};
 use sys::video::{SDL_DisplayMode, SDL_DisplayModeData, SDL_DisplayOrientation, SDL_WindowFlags};
 
 use crate::sys;
 
-pub use crate::sys::vulkan::{VkInstance, VkSurfaceKHR};
+pub use crate::sys::vulkan::{VkInstance, VkPhysicalDevice, VkSurfaceKHR};
 
 pub struct WindowSurfaceRef<'a>(&'a mut SurfaceRef, &'a Window);
 
@@ -399,6 +399,314 @@ pub mod gl_attr {
 
             ContextFlags { flags }
         }
+
+        /// Resets all OpenGL attributes to their default values.
+        ///
+        /// This also clears any callbacks installed with
+        /// `VideoSubsystem::egl_set_attribute_callbacks`.
+        #[doc(alias = "SDL_GL_ResetAttributes")]
+        pub fn reset(&self) {
+            unsafe { sys::video::SDL_GL_ResetAttributes() };
+            super::egl::clear_attribute_callbacks();
+        }
+    }
+}
+
+/// EGL accessors, for use when SDL drives OpenGL through EGL (e.g. the `offscreen`
+/// and `kmsdrm` video drivers, or OpenGL ES on Linux).
+///
+/// # Example
+/// ```no_run
+/// use sdl3::video::egl::{EGLAttributeCallbacks, EGL_NONE};
+///
+/// let sdl_context = sdl3::init().unwrap();
+/// let video_subsystem = sdl_context.video().unwrap();
+///
+/// const EGL_CONTEXT_PRIORITY_LEVEL_IMG: i32 = 0x3100;
+/// const EGL_CONTEXT_PRIORITY_HIGH_IMG: i32 = 0x3101;
+///
+/// let mut callbacks = EGLAttributeCallbacks::new();
+/// callbacks.context(|_display, _config| {
+///     vec![EGL_CONTEXT_PRIORITY_LEVEL_IMG, EGL_CONTEXT_PRIORITY_HIGH_IMG, EGL_NONE]
+/// });
+/// video_subsystem.egl_set_attribute_callbacks(callbacks);
+///
+/// let window = video_subsystem.window("egl", 800, 600).opengl().build().unwrap();
+/// let _context = window.gl_create_context().unwrap();
+/// let _display = video_subsystem.egl_get_current_display().unwrap();
+/// let _surface = window.egl_window_surface().unwrap();
+/// ```
+pub mod egl {
+    use super::Window;
+    use crate::get_error;
+    use crate::sys;
+    use crate::Error;
+    use libc::c_void;
+    use std::ffi::CString;
+    use std::sync::{Mutex, MutexGuard, PoisonError};
+    use std::{mem, ptr};
+
+    pub use sys::video::{
+        SDL_EGLAttrib as EGLAttrib, SDL_EGLConfig as EGLConfig, SDL_EGLDisplay as EGLDisplay,
+        SDL_EGLSurface as EGLSurface, SDL_EGLint as EGLint,
+    };
+
+    /// The `EGL_NONE` value that terminates EGL attribute lists.
+    pub const EGL_NONE: EGLint = 0x3038;
+
+    type PlatformCallback = Box<dyn FnMut() -> Vec<EGLAttrib> + Send>;
+    type IntCallback = Box<dyn FnMut(EGLDisplay, EGLConfig) -> Vec<EGLint> + Send>;
+
+    /// Callbacks providing extra attributes for the EGL calls SDL makes when creating
+    /// windows and OpenGL contexts.
+    ///
+    /// The returned attribute lists are appended to the ones SDL already uses. A list
+    /// that is not terminated with `EGL_NONE` is terminated automatically.
+    #[derive(Default)]
+    pub struct EGLAttributeCallbacks {
+        platform: Option<PlatformCallback>,
+        surface: Option<IntCallback>,
+        context: Option<IntCallback>,
+    }
+
+    impl EGLAttributeCallbacks {
+        pub fn new() -> EGLAttributeCallbacks {
+            Default::default()
+        }
+
+        /// Sets the callback for attributes passed to `eglGetPlatformDisplay`.
+        pub fn platform<F>(&mut self, callback: F) -> &mut EGLAttributeCallbacks
+        where
+            F: FnMut() -> Vec<EGLAttrib> + Send + 'static,
+        {
+            self.platform = Some(Box::new(callback));
+            self
+        }
+
+        /// Sets the callback for attributes passed to `eglCreateWindowSurface`.
+        pub fn surface<F>(&mut self, callback: F) -> &mut EGLAttributeCallbacks
+        where
+            F: FnMut(EGLDisplay, EGLConfig) -> Vec<EGLint> + Send + 'static,
+        {
+            self.surface = Some(Box::new(callback));
+            self
+        }
+
+        /// Sets the callback for attributes passed to `eglCreateContext`.
+        pub fn context<F>(&mut self, callback: F) -> &mut EGLAttributeCallbacks
+        where
+            F: FnMut(EGLDisplay, EGLConfig) -> Vec<EGLint> + Send + 'static,
+        {
+            self.context = Some(Box::new(callback));
+            self
+        }
+    }
+
+    lazy_static! {
+        static ref ATTRIBUTE_CALLBACKS: Mutex<EGLAttributeCallbacks> =
+            Mutex::new(EGLAttributeCallbacks::new());
+    }
+
+    /// Locks the installed callbacks. The lock is taken from SDL's C callbacks, where a
+    /// panic must not happen, so a lock poisoned by a panicking callback is recovered.
+    fn attribute_callbacks() -> MutexGuard<'static, EGLAttributeCallbacks> {
+        ATTRIBUTE_CALLBACKS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(super) fn clear_attribute_callbacks() {
+        *attribute_callbacks() = EGLAttributeCallbacks::new();
+    }
+
+    /// Copies an attribute list into memory owned by SDL, which frees it once used.
+    unsafe fn into_sdl_array<T: Copy + PartialEq>(mut attribs: Vec<T>, none: T) -> *mut T {
+        if attribs.last() != Some(&none) {
+            attribs.push(none);
+        }
+        let size = mem::size_of::<T>() * attribs.len();
+        let array = sys::stdinc::SDL_malloc(size) as *mut T;
+        if !array.is_null() {
+            ptr::copy_nonoverlapping(attribs.as_ptr(), array, attribs.len());
+        }
+        array
+    }
+
+    unsafe extern "C" fn platform_callback(_userdata: *mut c_void) -> *mut EGLAttrib {
+        let mut callbacks = attribute_callbacks();
+        match callbacks.platform {
+            Some(ref mut callback) => into_sdl_array(callback(), EGL_NONE as EGLAttrib),
+            None => into_sdl_array(Vec::new(), EGL_NONE as EGLAttrib),
+        }
+    }
+
+    unsafe extern "C" fn surface_callback(
+        _userdata: *mut c_void,
+        display: EGLDisplay,
+        config: EGLConfig,
+    ) -> *mut EGLint {
+        let mut callbacks = attribute_callbacks();
+        match callbacks.surface {
+            Some(ref mut callback) => into_sdl_array(callback(display, config), EGL_NONE),
+            None => into_sdl_array(Vec::new(), EGL_NONE),
+        }
+    }
+
+    unsafe extern "C" fn context_callback(
+        _userdata: *mut c_void,
+        display: EGLDisplay,
+        config: EGLConfig,
+    ) -> *mut EGLint {
+        let mut callbacks = attribute_callbacks();
+        match callbacks.context {
+            Some(ref mut callback) => into_sdl_array(callback(display, config), EGL_NONE),
+            None => into_sdl_array(Vec::new(), EGL_NONE),
+        }
+    }
+
+    impl crate::VideoSubsystem {
+        /// Gets the EGL display of the current OpenGL context.
+        #[doc(alias = "SDL_EGL_GetCurrentDisplay")]
+        pub fn egl_get_current_display(&self) -> Result<EGLDisplay, Error> {
+            let display = unsafe { sys::video::SDL_EGL_GetCurrentDisplay() };
+            if display.is_null() {
+                Err(get_error())
+            } else {
+                Ok(display)
+            }
+        }
+
+        /// Gets the EGL config of the current OpenGL context.
+        #[doc(alias = "SDL_EGL_GetCurrentConfig")]
+        pub fn egl_get_current_config(&self) -> Result<EGLConfig, Error> {
+            let config = unsafe { sys::video::SDL_EGL_GetCurrentConfig() };
+            if config.is_null() {
+                Err(get_error())
+            } else {
+                Ok(config)
+            }
+        }
+
+        /// Gets an EGL library function by name.
+        #[doc(alias = "SDL_EGL_GetProcAddress")]
+        pub fn egl_get_proc_address(&self, procname: &str) -> sys::stdinc::SDL_FunctionPointer {
+            match CString::new(procname) {
+                Ok(procname) => unsafe { sys::video::SDL_EGL_GetProcAddress(procname.as_ptr()) },
+                // string contains a nul byte - it won't match anything.
+                Err(_) => None,
+            }
+        }
+
+        /// Installs callbacks supplying extra EGL attributes when SDL initializes EGL.
+        ///
+        /// The callbacks must be set before creating the window they should apply to, and
+        /// replace any previously installed callbacks. They are removed by `GLAttr::reset`.
+        #[doc(alias = "SDL_EGL_SetAttributeCallbacks")]
+        pub fn egl_set_attribute_callbacks(&self, callbacks: EGLAttributeCallbacks) {
+            let platform: sys::video::SDL_EGLAttribArrayCallback = match callbacks.platform {
+                Some(_) => Some(platform_callback),
+                None => None,
+            };
+            let surface: sys::video::SDL_EGLIntArrayCallback = match callbacks.surface {
+                Some(_) => Some(surface_callback),
+                None => None,
+            };
+            let context: sys::video::SDL_EGLIntArrayCallback = match callbacks.context {
+                Some(_) => Some(context_callback),
+                None => None,
+            };
+            *attribute_callbacks() = callbacks;
+            unsafe {
+                sys::video::SDL_EGL_SetAttributeCallbacks(
+                    platform,
+                    surface,
+                    context,
+                    ptr::null_mut(),
+                )
+            };
+        }
+    }
+
+    impl Window {
+        /// Gets the EGL surface associated with the window.
+        #[doc(alias = "SDL_EGL_GetWindowSurface")]
+        pub fn egl_window_surface(&self) -> Result<EGLSurface, Error> {
+            let surface = unsafe { sys::video::SDL_EGL_GetWindowSurface(self.raw()) };
+            if surface.is_null() {
+                Err(get_error())
+            } else {
+                Ok(surface)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        /// Reads an SDL-allocated attribute list up to and including `none`, then frees it.
+        unsafe fn take_sdl_array<T: Copy + PartialEq>(array: *mut T, none: T) -> Vec<T> {
+            assert!(!array.is_null());
+            let mut attribs = Vec::new();
+            let mut i = 0;
+            loop {
+                let attrib = *array.add(i);
+                attribs.push(attrib);
+                if attrib == none {
+                    break;
+                }
+                i += 1;
+            }
+            sys::stdinc::SDL_free(array as *mut c_void);
+            attribs
+        }
+
+        #[test]
+        fn test_attribute_callbacks() {
+            let mut callbacks = EGLAttributeCallbacks::new();
+            callbacks
+                .platform(|| vec![1, 2])
+                .context(|_display, _config| vec![3, 4, EGL_NONE]);
+            *attribute_callbacks() = callbacks;
+
+            let null = ptr::null_mut();
+            unsafe {
+                // Unterminated lists get an `EGL_NONE` appended, terminated ones are
+                // passed through, and missing callbacks produce an empty list.
+                assert_eq!(
+                    take_sdl_array(platform_callback(null), EGL_NONE as EGLAttrib),
+                    [1, 2, EGL_NONE as EGLAttrib]
+                );
+                assert_eq!(
+                    take_sdl_array(context_callback(null, null, null), EGL_NONE),
+                    [3, 4, EGL_NONE]
+                );
+                assert_eq!(
+                    take_sdl_array(surface_callback(null, null, null), EGL_NONE),
+                    [EGL_NONE]
+                );
+
+                clear_attribute_callbacks();
+                assert_eq!(
+                    take_sdl_array(context_callback(null, null, null), EGL_NONE),
+                    [EGL_NONE]
+                );
+            }
+
+            // A panic while the callbacks are locked must not break later calls from SDL.
+            let _ = std::thread::spawn(|| {
+                let _callbacks = ATTRIBUTE_CALLBACKS.lock();
+                panic!("poison the callbacks");
+            })
+            .join();
+            assert!(ATTRIBUTE_CALLBACKS.is_poisoned());
+            unsafe {
+                assert_eq!(
+                    take_sdl_array(surface_callback(null, null, null), EGL_NONE),
+                    [EGL_NONE]
+                );
+            }
+        }
     }
 }
 
@@ -469,18 +777,81 @@ impl DisplayMode {
     }
 }
 
+bitflags! {
+    /// The flags of a window, as passed to window creation and returned by
+    /// `Window::window_flags`.
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct WindowFlags: SDL_WindowFlags {
+        /// The window is in fullscreen mode
+        const FULLSCREEN = sys::video::SDL_WINDOW_FULLSCREEN;
+        /// The window is usable with an OpenGL context
+        const OPENGL = sys::video::SDL_WINDOW_OPENGL;
+        /// The window is occluded
+        const OCCLUDED = sys::video::SDL_WINDOW_OCCLUDED;
+        /// The window is neither mapped onto the desktop nor shown in the taskbar/dock/window list
+        const HIDDEN = sys::video::SDL_WINDOW_HIDDEN;
+        /// No window decoration
+        const BORDERLESS = sys::video::SDL_WINDOW_BORDERLESS;
+        /// The window can be resized
+        const RESIZABLE = sys::video::SDL_WINDOW_RESIZABLE;
+        /// The window is minimized
+        const MINIMIZED = sys::video::SDL_WINDOW_MINIMIZED;
+        /// The window is maximized
+        const MAXIMIZED = sys::video::SDL_WINDOW_MAXIMIZED;
+        /// The window has grabbed mouse input
+        const MOUSE_GRABBED = sys::video::SDL_WINDOW_MOUSE_GRABBED;
+        /// The window has input focus
+        const INPUT_FOCUS = sys::video::SDL_WINDOW_INPUT_FOCUS;
+        /// The window has mouse focus
+        const MOUSE_FOCUS = sys::video::SDL_WINDOW_MOUSE_FOCUS;
+        /// The window was not created by SDL
+        const EXTERNAL = sys::video::SDL_WINDOW_EXTERNAL;
+        /// The window is modal
+        const MODAL = sys::video::SDL_WINDOW_MODAL;
+        /// The window uses a high pixel density buffer if possible
+        const HIGH_PIXEL_DENSITY = sys::video::SDL_WINDOW_HIGH_PIXEL_DENSITY;
+        /// The window has mouse captured (unrelated to `MOUSE_GRABBED`)
+        const MOUSE_CAPTURE = sys::video::SDL_WINDOW_MOUSE_CAPTURE;
+        /// The window has relative mode enabled
+        const MOUSE_RELATIVE_MODE = sys::video::SDL_WINDOW_MOUSE_RELATIVE_MODE;
+        /// The window should always be above others
+        const ALWAYS_ON_TOP = sys::video::SDL_WINDOW_ALWAYS_ON_TOP;
+        /// The window should be treated as a utility window, not showing in the task bar and window list
+        const UTILITY = sys::video::SDL_WINDOW_UTILITY;
+        /// The window should be treated as a tooltip and does not get mouse or keyboard focus
+        const TOOLTIP = sys::video::SDL_WINDOW_TOOLTIP;
+        /// The window should be treated as a popup menu
+        const POPUP_MENU = sys::video::SDL_WINDOW_POPUP_MENU;
+        /// The window has grabbed keyboard input
+        const KEYBOARD_GRABBED = sys::video::SDL_WINDOW_KEYBOARD_GRABBED;
+        /// The window is usable for a Vulkan surface
+        const VULKAN = sys::video::SDL_WINDOW_VULKAN;
+        /// The window is usable for a Metal view
+        const METAL = sys::video::SDL_WINDOW_METAL;
+        /// The window has a transparent buffer
+        const TRANSPARENT = sys::video::SDL_WINDOW_TRANSPARENT;
+        /// The window should not be focusable
+        const NOT_FOCUSABLE = sys::video::SDL_WINDOW_NOT_FOCUSABLE;
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum FullscreenType {
-    Off = 0,
-    True = 0x00_00_00_01,
-    Desktop = 0x00_00_10_01,
+    Off,
+    /// Exclusive fullscreen using a specific display mode
+    True,
+    /// Borderless fullscreen at the desktop resolution
+    Desktop,
 }
 
 impl FullscreenType {
-    pub fn from_window_flags(window_flags: u32) -> FullscreenType {
-        if window_flags & FullscreenType::Desktop as u32 == FullscreenType::Desktop as u32 {
-            FullscreenType::Desktop
-        } else if window_flags & FullscreenType::True as u32 == FullscreenType::True as u32 {
+    /// Determines the fullscreen state from window flags alone.
+    ///
+    /// SDL3 does not encode the kind of fullscreen in the window flags, so this only
+    /// distinguishes `Off` from `True`; use `Window::fullscreen_state` to tell exclusive
+    /// fullscreen apart from desktop fullscreen.
+    pub fn from_window_flags(window_flags: WindowFlags) -> FullscreenType {
+        if window_flags.contains(WindowFlags::FULLSCREEN) {
             FullscreenType::True
         } else {
             FullscreenType::Off
@@ -488,6 +859,51 @@ impl FullscreenType {
     }
 }
 
+/// A snapshot of the state of a window, as returned by `Window::state`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct WindowState {
+    /// The raw flags the snapshot was built from
+    pub flags: WindowFlags,
+    pub fullscreen: FullscreenType,
+    pub hidden: bool,
+    pub occluded: bool,
+    pub minimized: bool,
+    pub maximized: bool,
+    pub borderless: bool,
+    pub resizable: bool,
+    pub always_on_top: bool,
+    pub high_pixel_density: bool,
+    pub input_focus: bool,
+    pub mouse_focus: bool,
+    pub keyboard_grabbed: bool,
+    pub mouse_grabbed: bool,
+    pub mouse_captured: bool,
+    pub mouse_relative_mode: bool,
+}
+
+impl WindowState {
+    fn new(flags: WindowFlags, fullscreen: FullscreenType) -> WindowState {
+        WindowState {
+            flags,
+            fullscreen,
+            hidden: flags.contains(WindowFlags::HIDDEN),
+            occluded: flags.contains(WindowFlags::OCCLUDED),
+            minimized: flags.contains(WindowFlags::MINIMIZED),
+            maximized: flags.contains(WindowFlags::MAXIMIZED),
+            borderless: flags.contains(WindowFlags::BORDERLESS),
+            resizable: flags.contains(WindowFlags::RESIZABLE),
+            always_on_top: flags.contains(WindowFlags::ALWAYS_ON_TOP),
+            high_pixel_density: flags.contains(WindowFlags::HIGH_PIXEL_DENSITY),
+            input_focus: flags.contains(WindowFlags::INPUT_FOCUS),
+            mouse_focus: flags.contains(WindowFlags::MOUSE_FOCUS),
+            keyboard_grabbed: flags.contains(WindowFlags::KEYBOARD_GRABBED),
+            mouse_grabbed: flags.contains(WindowFlags::MOUSE_GRABBED),
+            mouse_captured: flags.contains(WindowFlags::MOUSE_CAPTURE),
+            mouse_relative_mode: flags.contains(WindowFlags::MOUSE_RELATIVE_MODE),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum WindowPos {
     Undefined,
@@ -523,6 +939,20 @@ impl Drop for GLContext {
 }
 
 impl GLContext {
+    /// Destroys the OpenGL context, reporting any error.
+    ///
+    /// Dropping a `GLContext` destroys it too, but silently ignores failures.
+    #[doc(alias = "SDL_GL_DestroyContext")]
+    pub fn destroy(self) -> Result<(), Error> {
+        let result = unsafe { sys::video::SDL_GL_DestroyContext(self.raw) };
+        mem::forget(self);
+        if result {
+            Ok(())
+        } else {
+            Err(get_error())
+        }
+    }
+
     /// Returns true if the OpenGL context is the current one in the thread.
     #[doc(alias = "SDL_GL_GetCurrentContext")]
     pub fn is_current(&self) -> bool {
@@ -1077,6 +1507,136 @@ impl VideoSubsystem {
     pub fn vulkan_get_proc_address_function(&self) -> SDL_FunctionPointer {
         unsafe { sys::vulkan::SDL_Vulkan_GetVkGetInstanceProcAddr() }
     }
+
+    /// Returns whether the given queue family of a physical device supports presenting to
+    /// windows created by SDL.
+    ///
+    /// # Safety
+    ///
+    /// `instance` and `physical_device` must be valid Vulkan handles.
+    #[cfg(not(feature = "ash"))]
+    #[doc(alias = "SDL_Vulkan_GetPresentationSupport")]
+    pub unsafe fn vulkan_presentation_support(
+        &self,
+        instance: VkInstance,
+        physical_device: VkPhysicalDevice,
+        queue_family_index: u32,
+    ) -> bool {
+        sys::vulkan::SDL_Vulkan_GetPresentationSupport(
+            instance,
+            physical_device,
+            queue_family_index,
+        )
+    }
+}
+
+/// Vulkan integration using the types of the [`ash`](https://docs.rs/ash) crate.
+#[cfg(feature = "ash")]
+mod vulkan_ash {
+    use super::Window;
+    use crate::get_error;
+    use crate::sys;
+    use crate::Error;
+    use ash::vk;
+    use std::ptr;
+
+    fn allocator_ptr(
+        allocator: Option<&vk::AllocationCallbacks<'_>>,
+    ) -> *const sys::vulkan::VkAllocationCallbacks {
+        match allocator {
+            Some(allocator) => allocator as *const _ as *const _,
+            None => ptr::null(),
+        }
+    }
+
+    impl crate::VideoSubsystem {
+        /// Returns whether the given queue family of a physical device supports presenting
+        /// to windows created by SDL.
+        ///
+        /// # Safety
+        ///
+        /// `instance` and `physical_device` must be valid Vulkan handles.
+        #[doc(alias = "SDL_Vulkan_GetPresentationSupport")]
+        pub unsafe fn vulkan_presentation_support(
+            &self,
+            instance: &ash::Instance,
+            physical_device: vk::PhysicalDevice,
+            queue_family_index: u32,
+        ) -> bool {
+            sys::vulkan::SDL_Vulkan_GetPresentationSupport(
+                instance.handle(),
+                physical_device,
+                queue_family_index,
+            )
+        }
+    }
+
+    impl Window {
+        /// Create a Vulkan rendering surface for a window.
+        ///
+        /// The instance must have been created with the extensions returned by
+        /// `vulkan_instance_extension_ptrs` enabled.
+        ///
+        /// # Example
+        /// ```no_run
+        /// use sdl3::ash::{self, vk};
+        ///
+        /// # fn create_instance(info: &vk::InstanceCreateInfo) -> ash::Instance { unimplemented!() }
+        /// let sdl_context = sdl3::init().unwrap();
+        /// let video_subsystem = sdl_context.video().unwrap();
+        /// let window = video_subsystem.window("vulkan", 800, 600).vulkan().build().unwrap();
+        ///
+        /// let extensions = window.vulkan_instance_extension_ptrs().unwrap();
+        /// let create_info = vk::InstanceCreateInfo::default().enabled_extension_names(extensions);
+        /// let instance = create_instance(&create_info);
+        ///
+        /// let surface = window.vulkan_create_surface(&instance, None).unwrap();
+        /// // ... render ...
+        /// unsafe { window.vulkan_destroy_surface(&instance, surface, None) };
+        /// ```
+        #[doc(alias = "SDL_Vulkan_CreateSurface")]
+        pub fn vulkan_create_surface(
+            &self,
+            instance: &ash::Instance,
+            allocator: Option<&vk::AllocationCallbacks<'_>>,
+        ) -> Result<vk::SurfaceKHR, Error> {
+            let mut surface = vk::SurfaceKHR::null();
+            if unsafe {
+                sys::vulkan::SDL_Vulkan_CreateSurface(
+                    self.raw(),
+                    instance.handle(),
+                    allocator_ptr(allocator),
+                    &mut surface,
+                )
+            } {
+                Ok(surface)
+            } else {
+                Err(get_error())
+            }
+        }
+
+        /// Destroy a Vulkan rendering surface created with `vulkan_create_surface`.
+        ///
+        /// This should be called before the window is destroyed.
+        ///
+        /// # Safety
+        ///
+        /// `surface` must have been created from `instance` with a compatible `allocator`,
+        /// must not be in use by the device and must not be used afterwards.
+        #[doc(alias = "SDL_Vulkan_DestroySurface")]
+        pub unsafe fn vulkan_destroy_surface(
+            &self,
+            instance: &ash::Instance,
+            surface: vk::SurfaceKHR,
+            allocator: Option<&vk::AllocationCallbacks<'_>>,
+        ) {
+            sys::vulkan::SDL_Vulkan_DestroySurface(
+                instance.handle(),
+                surface,
+                allocator_ptr(allocator),
+            );
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1121,7 +1681,7 @@ pub struct WindowBuilder {
     height: u32,
     x: WindowPos,
     y: WindowPos,
-    window_flags: u32,
+    window_flags: WindowFlags,
     create_metal_view: bool,
     /// The window builder cannot be built on a non-main thread, so prevent cross-threaded moves and references.
     /// `!Send` and `!Sync`,
@@ -1137,7 +1697,7 @@ impl WindowBuilder {
             height,
             x: WindowPos::Undefined,
             y: WindowPos::Undefined,
-            window_flags: 0,
+            window_flags: WindowFlags::empty(),
             subsystem: v.clone(),
             create_metal_view: false,
         }
@@ -1194,7 +1754,7 @@ impl WindowBuilder {
                 raw_height.into(),
             );
             let flags_cstr = CString::new("SDL.window.create.flags").unwrap();
-            SDL_SetNumberProperty(props, flags_cstr.as_ptr(), self.window_flags.into());
+            SDL_SetNumberProperty(props, flags_cstr.as_ptr(), self.window_flags.bits() as i64);
 
             let raw = sys::video::SDL_CreateWindowWithProperties(props);
             SDL_DestroyProperties(props);
@@ -1217,13 +1777,13 @@ impl WindowBuilder {
     }
 
     /// Gets the underlying window flags.
-    pub fn window_flags(&self) -> u32 {
+    pub fn window_flags(&self) -> WindowFlags {
         self.window_flags
     }
 
     /// Sets the underlying window flags.
     /// This will effectively undo any previous build operations, excluding window size and position.
-    pub fn set_window_flags(&mut self, flags: u32) -> &mut WindowBuilder {
+    pub fn set_window_flags(&mut self, flags: WindowFlags) -> &mut WindowBuilder {
         self.window_flags = flags;
         self
     }
@@ -1244,55 +1804,55 @@ impl WindowBuilder {
 
     /// Sets the window to fullscreen.
     pub fn fullscreen(&mut self) -> &mut WindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_FULLSCREEN as u32;
+        self.window_flags |= WindowFlags::FULLSCREEN;
         self
     }
 
     /// Sets the window to be usable with an OpenGL context
     pub fn opengl(&mut self) -> &mut WindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_OPENGL as u32;
+        self.window_flags |= WindowFlags::OPENGL;
         self
     }
 
     /// Sets the window to be usable with a Vulkan instance
     pub fn vulkan(&mut self) -> &mut WindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_VULKAN as u32;
+        self.window_flags |= WindowFlags::VULKAN;
         self
     }
 
     /// Hides the window.
     pub fn hidden(&mut self) -> &mut WindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_HIDDEN as u32;
+        self.window_flags |= WindowFlags::HIDDEN;
         self
     }
 
     /// Removes the window decoration.
     pub fn borderless(&mut self) -> &mut WindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_BORDERLESS as u32;
+        self.window_flags |= WindowFlags::BORDERLESS;
         self
     }
 
     /// Sets the window to be resizable.
     pub fn resizable(&mut self) -> &mut WindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_RESIZABLE as u32;
+        self.window_flags |= WindowFlags::RESIZABLE;
         self
     }
 
     /// Minimizes the window.
     pub fn minimized(&mut self) -> &mut WindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_MINIMIZED as u32;
+        self.window_flags |= WindowFlags::MINIMIZED;
         self
     }
 
     /// Maximizes the window.
     pub fn maximized(&mut self) -> &mut WindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_MAXIMIZED as u32;
+        self.window_flags |= WindowFlags::MAXIMIZED;
         self
     }
 
     /// Sets the window to have grabbed input focus.
     pub fn input_grabbed(&mut self) -> &mut WindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_MOUSE_GRABBED as u32;
+        self.window_flags |= WindowFlags::MOUSE_GRABBED;
         self
     }
 
@@ -1312,7 +1872,7 @@ pub struct PopupWindowBuilder {
     height: u32,
     offset_x: i32,
     offset_y: i32,
-    window_flags: u32,
+    window_flags: WindowFlags,
     create_metal_view: bool,
     /// The window builder cannot be built on a non-main thread, so prevent cross-threaded moves and references.
     /// `!Send` and `!Sync`,
@@ -1333,7 +1893,7 @@ impl PopupWindowBuilder {
             height,
             offset_x: 0,
             offset_y: 0,
-            window_flags: 0,
+            window_flags: WindowFlags::empty(),
             subsystem: v.clone(),
             create_metal_view: false,
         }
@@ -1349,15 +1909,17 @@ impl PopupWindowBuilder {
         if self.height >= (1 << 31) {
             return Err(HeightOverflows(self.width));
         }
-        if (self.window_flags & sys::video::SDL_WINDOW_TOOLTIP as u32 != 0)
-            && (self.window_flags & sys::video::SDL_WINDOW_POPUP_MENU as u32 != 0)
+        if self
+            .window_flags
+            .contains(WindowFlags::TOOLTIP | WindowFlags::POPUP_MENU)
         {
             return Err(SdlError(Error(
                 "SDL_WINDOW_TOOLTIP and SDL_WINDOW_POPUP are mutually exclusive".to_owned(),
             )));
         }
-        if (self.window_flags & sys::video::SDL_WINDOW_TOOLTIP as u32 == 0)
-            && (self.window_flags & sys::video::SDL_WINDOW_POPUP_MENU as u32 == 0)
+        if !self
+            .window_flags
+            .intersects(WindowFlags::TOOLTIP | WindowFlags::POPUP_MENU)
         {
             return Err(SdlError(Error(
                 "SDL_WINDOW_TOOLTIP or SDL_WINDOW_POPUP are required for popup windows".to_owned(),
@@ -1373,7 +1935,7 @@ impl PopupWindowBuilder {
                 self.offset_y,
                 raw_width,
                 raw_height,
-                self.window_flags.into(),
+                self.window_flags.bits(),
             );
             let mut metal_view = 0 as sys::metal::SDL_MetalView;
             #[cfg(target_os = "macos")]
@@ -1390,13 +1952,13 @@ impl PopupWindowBuilder {
     }
 
     /// Gets the underlying window flags.
-    pub fn window_flags(&self) -> u32 {
+    pub fn window_flags(&self) -> WindowFlags {
         self.window_flags
     }
 
     /// Sets the underlying window flags.
     /// This will effectively undo any previous build operations, excluding window size and position.
-    pub fn set_window_flags(&mut self, flags: u32) -> &mut PopupWindowBuilder {
+    pub fn set_window_flags(&mut self, flags: WindowFlags) -> &mut PopupWindowBuilder {
         self.window_flags = flags;
         self
     }
@@ -1410,31 +1972,31 @@ impl PopupWindowBuilder {
 
     /// Sets the window to be usable with an OpenGL context
     pub fn opengl(&mut self) -> &mut PopupWindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_OPENGL as u32;
+        self.window_flags |= WindowFlags::OPENGL;
         self
     }
 
     /// Sets the window to be usable with a Vulkan instance
     pub fn vulkan(&mut self) -> &mut PopupWindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_VULKAN as u32;
+        self.window_flags |= WindowFlags::VULKAN;
         self
     }
 
     /// Hides the window.
     pub fn hidden(&mut self) -> &mut PopupWindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_HIDDEN as u32;
+        self.window_flags |= WindowFlags::HIDDEN;
         self
     }
 
     /// Sets the window to be resizable.
     pub fn resizable(&mut self) -> &mut PopupWindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_RESIZABLE as u32;
+        self.window_flags |= WindowFlags::RESIZABLE;
         self
     }
 
     /// Sets the window to have grabbed input focus.
     pub fn input_grabbed(&mut self) -> &mut PopupWindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_MOUSE_GRABBED as u32;
+        self.window_flags |= WindowFlags::MOUSE_GRABBED;
         self
     }
 
@@ -1448,25 +2010,25 @@ impl PopupWindowBuilder {
 
     /// Sets the window to be a tooltip.
     pub fn tooltip(&mut self) -> &mut PopupWindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_TOOLTIP as u32;
+        self.window_flags |= WindowFlags::TOOLTIP;
         self
     }
 
     /// Sets the window to be a popup menu.
     pub fn popup_menu(&mut self) -> &mut PopupWindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_POPUP_MENU as u32;
+        self.window_flags |= WindowFlags::POPUP_MENU;
         self
     }
 
     /// Sets the window to be transparent
     pub fn transparent(&mut self) -> &mut PopupWindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_TRANSPARENT as u32;
+        self.window_flags |= WindowFlags::TRANSPARENT;
         self
     }
 
     /// Sets the window to be shown on top of all other windows
     pub fn always_on_top(&mut self) -> &mut PopupWindowBuilder {
-        self.window_flags |= sys::video::SDL_WINDOW_ALWAYS_ON_TOP as u32;
+        self.window_flags |= WindowFlags::ALWAYS_ON_TOP;
         self
     }
 }
@@ -1577,6 +2139,42 @@ impl Window {
     /// Get the names of the Vulkan instance extensions needed to create a surface with `vulkan_create_surface`.
     #[doc(alias = "SDL_Vulkan_GetInstanceExtensions")]
     pub fn vulkan_instance_extensions(&self) -> Result<Vec<String>, Error> {
+        Ok(self
+            .vulkan_instance_extension_names()?
+            .into_iter()
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect())
+    }
+
+    /// Get the names of the Vulkan instance extensions needed to create a surface with
+    /// `vulkan_create_surface`, as C strings owned by SDL.
+    ///
+    /// The names are owned by the Vulkan library, which a Vulkan window keeps loaded, so
+    /// they borrow from the window.
+    #[doc(alias = "SDL_Vulkan_GetInstanceExtensions")]
+    pub fn vulkan_instance_extension_names(&self) -> Result<Vec<&CStr>, Error> {
+        self.vulkan_instance_extension_ptrs()?
+            .iter()
+            .map(|&ext| {
+                if ext.is_null() {
+                    Err(Error(
+                        "Received null pointer for extension name".to_string(),
+                    ))
+                } else {
+                    Ok(unsafe { CStr::from_ptr(ext) })
+                }
+            })
+            .collect()
+    }
+
+    /// Get the names of the Vulkan instance extensions needed to create a surface with
+    /// `vulkan_create_surface`, as an array that can be passed directly as
+    /// `VkInstanceCreateInfo::ppEnabledExtensionNames`.
+    ///
+    /// Like the names, the array borrows from the window that keeps the Vulkan library
+    /// loaded.
+    #[doc(alias = "SDL_Vulkan_GetInstanceExtensions")]
+    pub fn vulkan_instance_extension_ptrs(&self) -> Result<&[*const c_char], Error> {
         let mut count: c_uint = 0;
         // returns a pointer to an array of extension names
         let extension_names_raw =
@@ -1585,23 +2183,7 @@ impl Window {
             return Err(get_error());
         }
 
-        // Create a slice from the raw pointer to the array
-        let names_slice =
-            unsafe { std::slice::from_raw_parts(extension_names_raw, count as usize) };
-
-        // Convert the C strings to Rust Strings
-        let mut extension_names = Vec::with_capacity(count as usize);
-        for &ext in names_slice {
-            if ext.is_null() {
-                return Err(Error(
-                    "Received null pointer for extension name".to_string(),
-                ));
-            }
-            let c_str = unsafe { CStr::from_ptr(ext) };
-            extension_names.push(c_str.to_string_lossy().into_owned());
-        }
-
-        Ok(extension_names)
+        Ok(unsafe { std::slice::from_raw_parts(extension_names_raw, count as usize) })
     }
 
     /// Create a Vulkan rendering surface for a window.
@@ -1609,15 +2191,17 @@ impl Window {
     /// The `VkInstance` must be created using a prior call to the
     /// [`vkCreateInstance`](https://www.khronos.org/registry/vulkan/specs/1.1-extensions/man/html/vkCreateInstance.html)
     /// function in the Vulkan library.
+    #[cfg(not(feature = "ash"))]
     #[doc(alias = "SDL_Vulkan_CreateSurface")]
     pub fn vulkan_create_surface(&self, instance: VkInstance) -> Result<VkSurfaceKHR, Error> {
-        #[cfg(feature = "ash")]
-        let mut surface: VkSurfaceKHR = VkSurfaceKHR::default();
-
-        #[cfg(not(feature = "ash"))]
         let mut surface: VkSurfaceKHR = 0 as _;
         if unsafe {
-            sys::vulkan::SDL_Vulkan_CreateSurface(self.context.raw, instance, null(), &mut surface)
+            sys::vulkan::SDL_Vulkan_CreateSurface(
+                self.context.raw,
+                instance,
+                ptr::null(),
+                &mut surface,
+            )
         } {
             Ok(surface)
         } else {
@@ -1625,6 +2209,20 @@ impl Window {
         }
     }
 
+    /// Destroy a Vulkan rendering surface created with `vulkan_create_surface`.
+    ///
+    /// This should be called before the window is destroyed.
+    ///
+    /// # Safety
+    ///
+    /// `surface` must have been created from `instance`, must not be in use by the device
+    /// and must not be used afterwards.
+    #[cfg(not(feature = "ash"))]
+    #[doc(alias = "SDL_Vulkan_DestroySurface")]
+    pub unsafe fn vulkan_destroy_surface(&self, instance: VkInstance, surface: VkSurfaceKHR) {
+        sys::vulkan::SDL_Vulkan_DestroySurface(instance, surface, ptr::null());
+    }
+
     #[doc(alias = "SDL_GetDisplayForWindow")]
     pub fn display_index(&self) -> Result<i32, Error> {
         let result = unsafe { sys::video::SDL_GetDisplayForWindow(self.context.raw) };
@@ -1691,33 +2289,41 @@ impl Window {
     }
 
     #[doc(alias = "SDL_GetWindowFlags")]
-    pub fn window_flags(&self) -> SDL_WindowFlags {
-        unsafe { sys::video::SDL_GetWindowFlags(self.context.raw) }
+    pub fn window_flags(&self) -> WindowFlags {
+        let flags = unsafe { sys::video::SDL_GetWindowFlags(self.context.raw) };
+        WindowFlags::from_bits_retain(flags)
+    }
+
+    /// Returns a snapshot of the window's current state.
+    #[doc(alias = "SDL_GetWindowFlags")]
+    pub fn state(&self) -> WindowState {
+        let flags = self.window_flags();
+        WindowState::new(flags, self.fullscreen_state_from_flags(flags))
     }
 
     /// Does the window have input focus?
     pub fn has_input_focus(&self) -> bool {
-        0 != self.window_flags() & sys::video::SDL_WINDOW_INPUT_FOCUS as Uint64
+        self.window_flags().contains(WindowFlags::INPUT_FOCUS)
     }
 
     /// Has the window grabbed input focus?
     pub fn has_input_grabbed(&self) -> bool {
-        0 != self.window_flags() & sys::video::SDL_WINDOW_MOUSE_GRABBED as Uint64
+        self.window_flags().contains(WindowFlags::MOUSE_GRABBED)
     }
 
     /// Does the window have mouse focus?
     pub fn has_mouse_focus(&self) -> bool {
-        0 != self.window_flags() & sys::video::SDL_WINDOW_MOUSE_FOCUS as Uint64
+        self.window_flags().contains(WindowFlags::MOUSE_FOCUS)
     }
 
     /// Is the window maximized?
     pub fn is_maximized(&self) -> bool {
-        0 != self.window_flags() & sys::video::SDL_WINDOW_MAXIMIZED as Uint64
+        self.window_flags().contains(WindowFlags::MAXIMIZED)
     }
 
     /// Is the window minimized?
     pub fn is_minimized(&self) -> bool {
-        0 != self.window_flags() & sys::video::SDL_WINDOW_MINIMIZED as Uint64
+        self.window_flags().contains(WindowFlags::MINIMIZED)
     }
 
     #[doc(alias = "SDL_SetWindowTitle")]
@@ -1912,8 +2518,24 @@ impl Window {
         unsafe { sys::video::SDL_RestoreWindow(self.context.raw) }
     }
 
+    #[doc(alias = "SDL_GetWindowFullscreenMode")]
     pub fn fullscreen_state(&self) -> FullscreenType {
-        FullscreenType::from_window_flags(self.window_flags() as u32)
+        self.fullscreen_state_from_flags(self.window_flags())
+    }
+
+    fn fullscreen_state_from_flags(&self, flags: WindowFlags) -> FullscreenType {
+        match FullscreenType::from_window_flags(flags) {
+            FullscreenType::Off => FullscreenType::Off,
+            _ => {
+                // A window without an exclusive display mode is fullscreen at the desktop resolution.
+                let mode = unsafe { sys::video::SDL_GetWindowFullscreenMode(self.context.raw) };
+                if mode.is_null() {
+                    FullscreenType::Desktop
+                } else {
+                    FullscreenType::True
+                }
+            }
+        }
     }
 
     #[doc(alias = "SDL_SetWindowFullscreen")]
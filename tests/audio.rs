@@ -1,4 +1,25 @@
 extern crate sdl3;
+#[macro_use]
+extern crate lazy_static;
+
+use sdl3::audio::{AudioFormat, AudioSpec, AudioStream};
+use std::sync::Mutex;
+
+// Only one `Sdl` context can exist at a time, so tests that initialize it run serially.
+lazy_static! {
+    static ref CONTEXT_MUTEX: Mutex<()> = Mutex::new(());
+}
+
+fn init_dummy_audio() -> (sdl3::Sdl, sdl3::AudioSubsystem) {
+    sdl3::hint::set("SDL_AUDIO_DRIVER", "dummy");
+    let sdl = sdl3::init().unwrap();
+    let audio = sdl.audio().unwrap();
+    (sdl, audio)
+}
+
+fn s16_mono_spec() -> AudioSpec {
+    AudioSpec::new(Some(22_050), Some(1), Some(AudioFormat::s16_sys()))
+}
 
 #[test]
 fn audio_spec_wav() {
@@ -11,3 +32,633 @@ fn audio_spec_wav() {
     let buffer = wav.buffer();
     assert_eq!(buffer.len(), 4_410);
 }
+
+#[test]
+fn audio_stream_gain_and_frequency_ratio() {
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, audio) = init_dummy_audio();
+    assert_eq!(audio.current_audio_driver(), "dummy");
+
+    let spec = s16_mono_spec();
+    let stream = AudioStream::new(Some(&spec), Some(&spec)).unwrap();
+
+    assert_eq!(stream.get_gain().unwrap(), 1.0);
+    stream.set_gain(0.5).unwrap();
+    assert_eq!(stream.get_gain().unwrap(), 0.5);
+    assert!(stream.set_gain(-1.0).is_err());
+
+    assert_eq!(stream.get_frequency_ratio().unwrap(), 1.0);
+    stream.set_frequency_ratio(1.5).unwrap();
+    assert_eq!(stream.get_frequency_ratio().unwrap(), 1.5);
+    assert!(stream.set_frequency_ratio(0.0).is_err());
+}
+
+#[test]
+fn audio_stream_set_format() {
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, _audio) = init_dummy_audio();
+
+    let spec = s16_mono_spec();
+    let stream = AudioStream::new(Some(&spec), Some(&spec)).unwrap();
+
+    let f32_stereo = AudioSpec::new(Some(48_000), Some(2), Some(AudioFormat::f32_sys()));
+    stream.set_format(Some(&f32_stereo), None).unwrap();
+
+    let (src, dst) = stream.get_format().unwrap();
+    let (src, dst) = (src.unwrap(), dst.unwrap());
+    assert_eq!(src.format, Some(AudioFormat::f32_sys()));
+    assert_eq!(src.channels, Some(2));
+    assert_eq!(src.freq, Some(48_000));
+    assert_eq!(dst.format, Some(AudioFormat::s16_sys()));
+    assert_eq!(dst.channels, Some(1));
+}
+
+#[test]
+fn audio_stream_queue_flush_clear() {
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, _audio) = init_dummy_audio();
+
+    let spec = s16_mono_spec();
+    let stream = AudioStream::new(Some(&spec), Some(&spec)).unwrap();
+    assert_eq!(stream.queued_bytes().unwrap(), 0);
+
+    let data = [0u8; 400];
+    stream.put_data(&data).unwrap();
+    assert_eq!(stream.queued_bytes().unwrap(), 400);
+
+    stream.flush().unwrap();
+    assert_eq!(stream.available_bytes().unwrap(), 400);
+
+    stream.clear().unwrap();
+    assert_eq!(stream.queued_bytes().unwrap(), 0);
+    assert_eq!(stream.available_bytes().unwrap(), 0);
+}
+
+#[test]
+fn audio_stream_on_dummy_device() {
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, audio) = init_dummy_audio();
+
+    let device_id = audio.default_playback_device().id();
+    let stream = AudioStream::open_device_stream(device_id, Some(&s16_mono_spec())).unwrap();
+
+    stream.set_frequency_ratio(2.0).unwrap();
+    assert_eq!(stream.get_frequency_ratio().unwrap(), 2.0);
+
+    stream.put_data(&[0u8; 256]).unwrap();
+    stream.flush().unwrap();
+    stream.clear().unwrap();
+    assert_eq!(stream.queued_bytes().unwrap(), 0);
+}
+
+#[test]
+fn audio_stream_channel_maps() {
+    use sdl3::audio::{ChannelLayout, ChannelMap};
+    use std::io::Read;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, _audio) = init_dummy_audio();
+
+    let spec = AudioSpec::new(Some(22_050), Some(2), Some(AudioFormat::s16_sys()));
+    assert_eq!(spec.layout(), Some(ChannelLayout::Stereo));
+    let mut stream = AudioStream::new(Some(&spec), Some(&spec)).unwrap();
+    assert_eq!(stream.get_input_channel_map(), None);
+    assert_eq!(stream.get_output_channel_map(), None);
+
+    let mut swapped = ChannelMap::from(ChannelLayout::Stereo);
+    swapped.swap(0, 1);
+    stream.set_input_channel_map(Some(&swapped)).unwrap();
+    assert_eq!(stream.get_input_channel_map(), Some(swapped));
+
+    // A map with the wrong number of channels is rejected.
+    let surround = ChannelMap::from(ChannelLayout::FivePointOne);
+    assert!(stream.set_output_channel_map(Some(&surround)).is_err());
+
+    let frame: Vec<u8> = [100i16, -100]
+        .iter()
+        .flat_map(|s| s.to_ne_bytes())
+        .collect();
+    stream.put_data(&frame).unwrap();
+    stream.flush().unwrap();
+
+    let mut out = [0u8; 4];
+    assert_eq!(stream.read(&mut out).unwrap(), 4);
+    assert_eq!(i16::from_ne_bytes([out[0], out[1]]), -100);
+    assert_eq!(i16::from_ne_bytes([out[2], out[3]]), 100);
+
+    stream.set_input_channel_map(None).unwrap();
+    assert_eq!(stream.get_input_channel_map(), None);
+}
+
+#[test]
+fn audio_stream_typed_samples() {
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, _audio) = init_dummy_audio();
+
+    let spec = AudioSpec::new(Some(22_050), Some(2), Some(AudioFormat::f32_sys()));
+    let stream = AudioStream::new(Some(&spec), Some(&spec)).unwrap();
+
+    // The sample type must match the stream format.
+    assert!(stream.put_samples(&[0i16; 4]).is_err());
+    assert!(stream.get_samples(&mut [0i16; 4]).is_err());
+
+    stream.put_samples(&[0.25f32, -0.25, 0.5, -0.5]).unwrap();
+    stream.flush().unwrap();
+
+    let mut out = [0.0f32; 4];
+    assert_eq!(stream.get_samples(&mut out).unwrap(), 4);
+    assert_eq!(out, [0.25, -0.25, 0.5, -0.5]);
+}
+
+#[test]
+fn audio_stream_read_swapped_endian_samples() {
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, _audio) = init_dummy_audio();
+
+    let (f32_swapped, s16_swapped) = if cfg!(target_endian = "little") {
+        (AudioFormat::F32BE, AudioFormat::S16BE)
+    } else {
+        (AudioFormat::F32LE, AudioFormat::S16LE)
+    };
+
+    let src = AudioSpec::new(Some(22_050), Some(1), Some(AudioFormat::f32_sys()));
+    let dst = AudioSpec::new(Some(22_050), Some(1), Some(f32_swapped));
+    let mut stream = AudioStream::new(Some(&src), Some(&dst)).unwrap();
+    stream.put_samples(&[0.25f32, -0.5]).unwrap();
+    stream.flush().unwrap();
+
+    let mut out = [0.0f32; 2];
+    assert_eq!(stream.read_f32_samples(&mut out).unwrap(), 2);
+    assert_eq!(out, [0.25, -0.5]);
+
+    let src = AudioSpec::new(Some(22_050), Some(1), Some(AudioFormat::s16_sys()));
+    let dst = AudioSpec::new(Some(22_050), Some(1), Some(s16_swapped));
+    let mut stream = AudioStream::new(Some(&src), Some(&dst)).unwrap();
+    stream.put_samples(&[1000i16, -2]).unwrap();
+    stream.flush().unwrap();
+
+    let mut out = [0i16; 2];
+    assert_eq!(stream.read_i16_samples(&mut out).unwrap(), 2);
+    assert_eq!(out, [1000, -2]);
+}
+
+#[test]
+fn audio_stream_typed_frames() {
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, _audio) = init_dummy_audio();
+
+    let spec = AudioSpec::new(Some(22_050), Some(2), Some(AudioFormat::s16_sys()));
+    let stream = AudioStream::new(Some(&spec), Some(&spec)).unwrap();
+
+    // The frame width must match the channel count.
+    assert!(stream.put_frames(&[[0i16; 1]; 2]).is_err());
+    assert!(stream.put_frames(&[[0f32; 2]; 2]).is_err());
+
+    let frames = [[1i16, -1], [2, -2], [3, -3]];
+    stream.put_frames(&frames).unwrap();
+    stream.flush().unwrap();
+
+    let mut out = [[0i16; 2]; 4];
+    assert_eq!(stream.get_frames(&mut out).unwrap(), 3);
+    assert_eq!(&out[..3], &frames);
+}
+
+#[test]
+fn audio_stream_put_and_get_callbacks() {
+    use std::sync::{Arc, Mutex};
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, _audio) = init_dummy_audio();
+
+    let spec = s16_mono_spec();
+    let mut stream = AudioStream::new(Some(&spec), Some(&spec)).unwrap();
+
+    let puts = Arc::new(Mutex::new(Vec::new()));
+    let puts_cb = puts.clone();
+    stream
+        .set_put_callback(move |_, additional, total| {
+            puts_cb.lock().unwrap().push((additional, total));
+        })
+        .unwrap();
+    stream.put_samples(&[0i16; 32]).unwrap();
+    assert_eq!(*puts.lock().unwrap(), [(64, 64)]);
+
+    // Feed the stream just in time from the get callback.
+    stream
+        .set_get_callback(|stream, additional, _| {
+            let samples = vec![7i16; additional as usize / 2];
+            stream.put_samples(&samples).unwrap();
+        })
+        .unwrap();
+    stream.clear_put_callback().unwrap();
+    stream.clear().unwrap();
+
+    let mut out = [0i16; 16];
+    assert_eq!(stream.get_samples(&mut out).unwrap(), 16);
+    assert_eq!(out, [7; 16]);
+
+    stream.clear_get_callback().unwrap();
+    stream.clear().unwrap();
+    assert_eq!(stream.get_samples(&mut out).unwrap(), 0);
+    assert_eq!(puts.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn audio_device_postmix_callback() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, audio) = init_dummy_audio();
+
+    let device = audio.open_playback_device(&s16_mono_spec()).unwrap();
+    let mixed = Arc::new(AtomicUsize::new(0));
+    let mixed_cb = mixed.clone();
+    let postmix = device
+        .set_postmix_callback(move |spec, samples| {
+            assert!(spec.channels.is_some());
+            mixed_cb.fetch_add(samples.len(), Ordering::SeqCst);
+        })
+        .unwrap();
+
+    let stream = AudioStream::new(Some(&s16_mono_spec()), None).unwrap();
+    device.bind_stream(&stream).unwrap();
+    stream.put_samples(&[0i16; 4096]).unwrap();
+
+    let start = Instant::now();
+    while mixed.load(Ordering::SeqCst) == 0 && start.elapsed() < Duration::from_secs(2) {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(mixed.load(Ordering::SeqCst) > 0);
+
+    drop(postmix);
+    let after_drop = mixed.load(Ordering::SeqCst);
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(mixed.load(Ordering::SeqCst), after_drop);
+}
+
+#[test]
+fn audio_format_silence_and_sizes() {
+    assert_eq!(AudioFormat::U8.silence_value(), 0x80);
+    assert_eq!(AudioFormat::S16LE.silence_value(), 0);
+    assert_eq!(AudioFormat::F32BE.silence_value(), 0);
+
+    assert_eq!(AudioFormat::S16BE.byte_size(), 2);
+    assert_eq!(AudioFormat::F32LE.bit_size(), 32);
+    assert!(AudioFormat::F32LE.is_float());
+    assert!(AudioFormat::S32BE.is_big_endian());
+    assert!(!AudioFormat::U8.is_signed());
+}
+
+#[test]
+fn audio_convert_samples() {
+    let src_spec = AudioSpec::new(Some(22_050), Some(1), Some(AudioFormat::S16LE));
+    let dst_spec = AudioSpec::new(Some(22_050), Some(2), Some(AudioFormat::F32LE));
+    let src: Vec<u8> = [i16::MAX, 0, i16::MIN]
+        .iter()
+        .flat_map(|s| s.to_le_bytes())
+        .collect();
+
+    let dst = sdl3::audio::convert_samples(&src_spec, &src, &dst_spec).unwrap();
+    let dst: Vec<f32> = dst
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+        .collect();
+    assert_eq!(dst.len(), 6);
+    assert!(dst[0] > 0.99 && dst[0] == dst[1]);
+    assert_eq!(dst[2], 0.0);
+    assert_eq!(dst[4], -1.0);
+
+    let bad_spec = AudioSpec::default();
+    assert!(sdl3::audio::convert_samples(&bad_spec, &src, &dst_spec).is_err());
+}
+
+#[test]
+fn audio_mix_into() {
+    let format = AudioFormat::s16_sys();
+    let to_bytes =
+        |samples: &[i16]| -> Vec<u8> { samples.iter().flat_map(|s| s.to_ne_bytes()).collect() };
+
+    let mut dst = to_bytes(&[1000, 1000, i16::MAX, 5]);
+    let src = to_bytes(&[500, -2000, 1000]);
+    sdl3::audio::mix_into(&mut dst, &src, format, 1.0).unwrap();
+
+    let mixed: Vec<i16> = dst
+        .chunks_exact(2)
+        .map(|b| i16::from_ne_bytes([b[0], b[1]]))
+        .collect();
+    assert_eq!(mixed, [1500, -1000, i16::MAX, 5]);
+
+    let mut short = vec![0u8; 2];
+    assert!(sdl3::audio::mix_into(&mut short, &src, format, 1.0).is_err());
+}
+
+#[test]
+fn wav_writer_round_trip() {
+    use sdl3::audio::{AudioSpecWAV, WavWriter};
+    use sdl3::iostream::IOStream;
+
+    let spec = AudioSpec::new(Some(8_000), Some(2), Some(AudioFormat::S16BE));
+    let frames: Vec<u8> = [100i16, -100, 200, -200]
+        .iter()
+        .flat_map(|s| s.to_be_bytes())
+        .collect();
+
+    let mut file = vec![0u8; 256];
+    {
+        let stream = IOStream::from_bytes_mut(&mut file).unwrap();
+        let mut writer = WavWriter::new(stream, &spec).unwrap();
+        assert!(writer.write_data(&frames[..2]).is_err());
+        assert!(writer.write_samples(&[0.0f32; 2]).is_err());
+        writer.write_data(&frames).unwrap();
+        assert_eq!(writer.data_len(), 8);
+        writer.finish().unwrap();
+    }
+
+    let mut stream = IOStream::from_bytes(&file[..44 + 8]).unwrap();
+    let wav = AudioSpecWAV::load_wav_rw(&mut stream).unwrap();
+    assert_eq!(wav.freq, 8_000);
+    assert_eq!(wav.channels, 2);
+    assert_eq!(wav.format, AudioFormat::S16LE);
+    let samples: Vec<i16> = wav
+        .buffer()
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    assert_eq!(samples, [100, -100, 200, -200]);
+}
+
+#[test]
+fn audio_device_queries() {
+    use sdl3::audio::AudioDirection;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, audio) = init_dummy_audio();
+
+    let devices = audio.audio_playback_device_ids().unwrap();
+    assert!(!devices.is_empty());
+    for id in devices {
+        assert!(id.is_physical());
+        assert!(id.is_playback());
+        assert_eq!(id.direction(), AudioDirection::Playback);
+        let (spec, sample_frames) = id.format().unwrap();
+        assert!(spec.freq.is_some());
+        assert!(sample_frames > 0);
+    }
+
+    let device = audio.open_playback_device(&s16_mono_spec()).unwrap();
+    assert!(!device.id().is_physical());
+}
+
+#[test]
+fn audio_device_watcher() {
+    use sdl3::audio::{AudioDeviceChange, AudioDeviceID, AudioDeviceWatcher, AudioDirection};
+    use sdl3::event::Event;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, audio) = init_dummy_audio();
+
+    let mut watcher = AudioDeviceWatcher::new(&audio).unwrap();
+    assert_eq!(
+        watcher.playback_devices(),
+        &audio.audio_playback_device_ids().unwrap()[..]
+    );
+    let initial_recording = watcher.recording_devices().len();
+    assert!(watcher.default_playback_name().is_some());
+
+    let added = Event::AudioDeviceAdded {
+        timestamp: 0,
+        which: AudioDeviceID::Device(0xF00),
+        direction: AudioDirection::Recording,
+    };
+    assert_eq!(
+        watcher.handle_event(&added),
+        [AudioDeviceChange::Added(
+            AudioDeviceID::Device(0xF00),
+            AudioDirection::Recording
+        )]
+    );
+    // Duplicate notifications do not add the device twice.
+    assert!(watcher.handle_event(&added).is_empty());
+    assert_eq!(watcher.recording_devices().len(), initial_recording + 1);
+
+    let removed = Event::AudioDeviceRemoved {
+        timestamp: 0,
+        which: AudioDeviceID::Device(0xF00),
+        direction: AudioDirection::Recording,
+    };
+    assert_eq!(
+        watcher.handle_event(&removed),
+        [AudioDeviceChange::Removed(
+            AudioDeviceID::Device(0xF00),
+            AudioDirection::Recording
+        )]
+    );
+    assert_eq!(watcher.recording_devices().len(), initial_recording);
+
+    assert!(watcher
+        .handle_event(&Event::Quit { timestamp: 0 })
+        .is_empty());
+}
+
+fn constant_sound(
+    mixer: &sdl3::audio::mixer::Mixer,
+    value: f32,
+    frames: usize,
+) -> sdl3::audio::mixer::Sound {
+    let spec = mixer.spec().clone();
+    let data: Vec<u8> = std::iter::repeat(value)
+        .take(frames * 2)
+        .flat_map(|s| s.to_ne_bytes())
+        .collect();
+    mixer.load_samples(&spec, &data).unwrap()
+}
+
+#[test]
+fn mixer_plays_and_stops() {
+    use sdl3::audio::mixer::Mixer;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, audio) = init_dummy_audio();
+
+    let mut mixer = Mixer::offline(&audio, 1_000, 4).unwrap();
+    let sound = constant_sound(&mixer, 0.5, 100);
+    assert_eq!(sound.frames(), 100);
+
+    let voice = mixer.play(&sound).unwrap();
+    assert!(voice.is_playing());
+    assert_eq!(mixer.playing_voices(), 1);
+
+    let out = mixer.render(150).unwrap();
+    assert!(out[..200].iter().all(|&s| s == 0.5));
+    assert!(out[200..].iter().all(|&s| s == 0.0));
+    assert!(!voice.is_playing());
+    assert_eq!(mixer.playing_voices(), 0);
+}
+
+#[test]
+fn mixer_gain_pan_and_sum() {
+    use sdl3::audio::mixer::{Mixer, PlayOptions};
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, audio) = init_dummy_audio();
+
+    let mut mixer = Mixer::offline(&audio, 1_000, 4).unwrap();
+    let sound = constant_sound(&mixer, 0.5, 50);
+
+    let right = mixer
+        .play_with(
+            &sound,
+            &PlayOptions {
+                gain: 0.5,
+                pan: 1.0,
+                ..PlayOptions::default()
+            },
+        )
+        .unwrap();
+    let out = mixer.render(10).unwrap();
+    assert_eq!(&out[..2], &[0.0, 0.25]);
+
+    right.set_pan(0.0);
+    right.set_gain(1.0);
+    mixer.play(&sound).unwrap();
+    let out = mixer.render(10).unwrap();
+    assert_eq!(&out[..2], &[1.0, 1.0]);
+
+    mixer.stop_all();
+    assert!(mixer.render(10).unwrap().iter().all(|&s| s == 0.0));
+}
+
+#[test]
+fn mixer_loops_and_fades() {
+    use sdl3::audio::mixer::Mixer;
+    use std::time::Duration;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, audio) = init_dummy_audio();
+
+    let mut mixer = Mixer::offline(&audio, 1_000, 1).unwrap();
+    let spec = mixer.spec().clone();
+    let ramp: Vec<u8> = (0..10)
+        .flat_map(|i| [i as f32 / 10.0; 2])
+        .flat_map(|s| s.to_ne_bytes())
+        .collect();
+    let sound = mixer.load_samples(&spec, &ramp).unwrap();
+
+    let voice = mixer.play(&sound).unwrap();
+    voice.set_looping(true);
+    let out = mixer.render(25).unwrap();
+    assert_eq!(out[2 * 9], 0.9);
+    assert_eq!(out[2 * 10], 0.0);
+    assert_eq!(out[2 * 23], 0.3);
+    assert!(voice.is_playing());
+
+    // 10ms at 1kHz is ten frames; the voice stops afterwards.
+    let constant = constant_sound(&mixer, 1.0, 100);
+    voice.stop();
+    let voice = mixer.play(&constant).unwrap();
+    voice.fade_out(Duration::from_millis(10));
+    let out = mixer.render(20).unwrap();
+    assert_eq!(out[0], 1.0);
+    assert!(out[2 * 5] < out[2 * 4]);
+    assert!(out[2 * 9] > 0.0);
+    assert!(out[2 * 10..].iter().all(|&s| s == 0.0));
+    assert!(!voice.is_playing());
+}
+
+#[test]
+fn mixer_voice_pool() {
+    use sdl3::audio::mixer::Mixer;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, audio) = init_dummy_audio();
+
+    let mut mixer = Mixer::offline(&audio, 1_000, 1).unwrap();
+    let sound = constant_sound(&mixer, 0.5, 100);
+
+    let first = mixer.play(&sound).unwrap();
+    assert!(mixer.play(&sound).is_err());
+
+    first.stop();
+    let second = mixer.play(&sound).unwrap();
+    assert!(second.is_playing());
+
+    // The stale handle no longer controls the recycled voice.
+    assert!(!first.is_playing());
+    first.set_gain(0.0);
+    assert_eq!(mixer.render(1).unwrap(), [0.5, 0.5]);
+}
+
+#[test]
+fn mixer_on_dummy_device() {
+    use sdl3::audio::mixer::Mixer;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, audio) = init_dummy_audio();
+
+    let mut mixer = Mixer::open(&audio, None, 2).unwrap();
+    assert!(mixer.device().is_some());
+    let sound = constant_sound(&mixer, 0.1, 10);
+    let voice = mixer.play(&sound).unwrap();
+
+    let start = std::time::Instant::now();
+    while voice.is_playing() && start.elapsed() < std::time::Duration::from_secs(2) {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert!(!voice.is_playing());
+}
+
+#[test]
+fn audio_recorder_on_dummy_device() {
+    use sdl3::audio::{AudioRecorder, AudioSpecWAV, RecorderState};
+    use sdl3::iostream::IOStream;
+    use std::time::{Duration, Instant};
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let (_sdl, audio) = init_dummy_audio();
+
+    let mut recorder = AudioRecorder::<i16>::open(&audio, None, 8_000, 2, 8_000).unwrap();
+    assert_eq!(recorder.state(), RecorderState::Stopped);
+    assert_eq!(recorder.capacity_samples(), 16_000);
+
+    recorder.start().unwrap();
+    assert_eq!(recorder.state(), RecorderState::Recording);
+    let start = Instant::now();
+    while recorder.buffered_samples() < 200 && start.elapsed() < Duration::from_secs(2) {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    recorder.pause().unwrap();
+    assert_eq!(recorder.state(), RecorderState::Paused);
+    let paused = recorder.buffered_samples();
+    // Stopping keeps what the stream still held, in whole frames.
+    recorder.stop().unwrap();
+    assert_eq!(recorder.state(), RecorderState::Stopped);
+    assert!(recorder.buffered_samples() >= paused);
+    assert_eq!(recorder.buffered_samples() % 2, 0);
+
+    let buffered = recorder.buffered_samples();
+    assert!(buffered >= 200);
+    // The dummy driver records silence.
+    let levels = recorder.levels();
+    assert_eq!(levels.len(), 2);
+    assert!(levels
+        .iter()
+        .all(|level| level.peak == 0.0 && level.rms == 0.0));
+
+    let mut file = vec![0u8; 44 + buffered * 2];
+    recorder
+        .write_wav(IOStream::from_bytes_mut(&mut file).unwrap())
+        .unwrap();
+    assert_eq!(recorder.buffered_samples(), buffered);
+    let wav = AudioSpecWAV::load_wav_rw(&mut IOStream::from_bytes(&file).unwrap()).unwrap();
+    assert_eq!(wav.freq, 8_000);
+    assert_eq!(wav.channels, 2);
+    assert_eq!(wav.buffer().len(), (buffered - buffered % 2) * 2);
+
+    let mut out = vec![1i16; buffered];
+    assert_eq!(recorder.read(&mut out), buffered);
+    assert!(out.iter().all(|&s| s == 0));
+    assert_eq!(recorder.buffered_samples(), 0);
+    assert_eq!(recorder.dropped_samples(), 0);
+}
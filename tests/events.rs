@@ -96,11 +96,13 @@ struct SomeOtherEventTypeTest3 {
 }
 
 fn test3(ev: &sdl3::EventSubsystem) {
-    ev.register_custom_event::<SomeEventTypeTest3>().unwrap();
+    let first = ev.register_custom_event::<SomeEventTypeTest3>().unwrap();
     ev.register_custom_event::<SomeOtherEventTypeTest3>()
         .unwrap();
 
-    assert!(ev.register_custom_event::<SomeEventTypeTest3>().is_err());
+    // Registering the same type again yields a distinct event type.
+    let second = ev.register_custom_event::<SomeEventTypeTest3>().unwrap();
+    assert_ne!(first.event_type(), second.event_type());
 }
 
 struct SomeEventTypeTest4 {
@@ -108,15 +110,16 @@ struct SomeEventTypeTest4 {
 }
 
 fn test4(ev: &sdl3::EventSubsystem, ep: &mut sdl3::EventPump) {
-    ev.register_custom_event::<SomeEventTypeTest4>().unwrap();
+    let custom = ev.register_custom_event::<SomeEventTypeTest4>().unwrap();
     let event = SomeEventTypeTest4 { a: 42 };
-    ev.push_custom_event(event).unwrap();
+    ev.push_custom_event(&custom, event).unwrap();
 
     let received = ep.poll_event().unwrap();
-    if received.is_user_event() {
-        let e2 = received.as_user_event_type::<SomeEventTypeTest4>().unwrap();
-        assert_eq!(e2.a, 42);
-    }
+    assert!(received.is_user_event());
+    let e2 = custom.take(&received).unwrap();
+    assert_eq!(e2.a, 42);
+    // The payload can only be taken once.
+    assert!(custom.take(&received).is_none());
 }
 
 #[test]
@@ -145,3 +148,533 @@ fn test_event_sender_no_subsystem() {
         })
         .is_err());
 }
+
+#[test]
+fn test_push_window_events() {
+    use sdl3::event::{Event, WindowEvent};
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let ev = sdl.event().unwrap();
+    let mut ep = sdl.event_pump().unwrap();
+
+    let win_events = [
+        WindowEvent::PixelSizeChanged(640, 480),
+        WindowEvent::MetalViewResized,
+        WindowEvent::HitTest(1, 2),
+        WindowEvent::ICCProfChanged,
+        WindowEvent::DisplayChanged(2),
+        WindowEvent::DisplayScaleChanged,
+        WindowEvent::SafeAreaChanged,
+        WindowEvent::Occluded,
+        WindowEvent::EnterFullscreen,
+        WindowEvent::LeaveFullscreen,
+        WindowEvent::Destroyed,
+        WindowEvent::HDRStateChanged(true),
+        WindowEvent::HDRStateChanged(false),
+    ];
+
+    for win_event in win_events {
+        ev.push_event(Event::Window {
+            timestamp: 0,
+            window_id: 7,
+            win_event,
+        })
+        .unwrap();
+
+        match ep.poll_event() {
+            Some(Event::Window {
+                window_id,
+                win_event: received,
+                ..
+            }) => {
+                assert_eq!(window_id, 7);
+                assert_eq!(received, win_event);
+            }
+            other => panic!("Expected window event {:?}, got {:?}", win_event, other),
+        }
+    }
+}
+
+#[test]
+fn test_push_pen_events() {
+    use sdl3::event::Event;
+    use sdl3::pen::{PenAxis, PenInputFlags};
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let ev = sdl.event().unwrap();
+    let mut ep = sdl.event_pump().unwrap();
+
+    let pen_events = [
+        Event::PenProximityIn {
+            timestamp: 0,
+            window_id: 3,
+            which: 9,
+        },
+        Event::PenDown {
+            timestamp: 0,
+            window_id: 3,
+            which: 9,
+            pen_state: PenInputFlags::DOWN,
+            x: 10.0,
+            y: 20.0,
+            eraser: false,
+        },
+        Event::PenAxis {
+            timestamp: 0,
+            window_id: 3,
+            which: 9,
+            pen_state: PenInputFlags::DOWN,
+            x: 10.0,
+            y: 20.0,
+            axis: PenAxis::Pressure,
+            value: 0.75,
+        },
+        Event::PenButtonDown {
+            timestamp: 0,
+            window_id: 3,
+            which: 9,
+            pen_state: PenInputFlags::DOWN | PenInputFlags::BUTTON_1,
+            x: 10.0,
+            y: 20.0,
+            button: 1,
+        },
+        Event::PenUp {
+            timestamp: 0,
+            window_id: 3,
+            which: 9,
+            pen_state: PenInputFlags::empty(),
+            x: 11.0,
+            y: 21.0,
+            eraser: false,
+        },
+        Event::PenProximityOut {
+            timestamp: 0,
+            window_id: 3,
+            which: 9,
+        },
+    ];
+
+    for pen_event in pen_events {
+        ev.push_event(pen_event.clone()).unwrap();
+
+        match ep.poll_event() {
+            Some(received) => {
+                assert!(received.is_pen());
+                assert!(received.is_same_kind_as(&pen_event));
+                assert_eq!(received.get_window_id(), Some(3));
+            }
+            None => panic!("Expected pen event {:?}, got nothing", pen_event),
+        }
+    }
+}
+
+#[test]
+fn test_push_input_device_events() {
+    use sdl3::event::Event;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let ev = sdl.event().unwrap();
+    let mut ep = sdl.event_pump().unwrap();
+
+    // Enumeration must succeed even when no devices are attached.
+    let keyboards = sdl.keyboard().keyboards().unwrap();
+    assert_eq!(keyboards.is_empty(), !sdl.keyboard().has_keyboard());
+    let mice = sdl.mouse().mice().unwrap();
+    assert_eq!(mice.is_empty(), !sdl.mouse().has_mouse());
+
+    let device_events = [
+        Event::KeyboardAdded {
+            timestamp: 0,
+            which: 21,
+        },
+        Event::MouseAdded {
+            timestamp: 0,
+            which: 22,
+        },
+        Event::MouseRemoved {
+            timestamp: 0,
+            which: 22,
+        },
+        Event::KeyboardRemoved {
+            timestamp: 0,
+            which: 21,
+        },
+    ];
+
+    for device_event in device_events {
+        ev.push_event(device_event.clone()).unwrap();
+
+        match ep.poll_event() {
+            Some(Event::KeyboardAdded { which, .. })
+            | Some(Event::KeyboardRemoved { which, .. }) => {
+                assert!(device_event.is_keyboard());
+                assert_eq!(which, 21);
+            }
+            Some(Event::MouseAdded { which, .. }) | Some(Event::MouseRemoved { which, .. }) => {
+                assert!(device_event.is_mouse());
+                assert_eq!(which, 22);
+            }
+            other => panic!("Expected device event {:?}, got {:?}", device_event, other),
+        }
+    }
+}
+
+#[test]
+fn test_event_filter_and_enabled() {
+    use sdl3::event::{Event, EventType};
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let ev = sdl.event().unwrap();
+    let mut ep = sdl.event_pump().unwrap();
+    while ep.poll_event().is_some() {}
+
+    let motion = Event::MouseMotion {
+        timestamp: 0,
+        window_id: 0,
+        which: 0,
+        mousestate: sdl3::mouse::MouseState::from_sdl_state(0),
+        x: 1.0,
+        y: 2.0,
+        xrel: 1.0,
+        yrel: 2.0,
+    };
+
+    {
+        let _filter = ev.set_event_filter(|event: &Event| !event.is_mouse());
+        // Rejected events are reported as a failed push.
+        let _ = ev.push_event(motion.clone());
+        ev.push_event(Event::Quit { timestamp: 0 }).unwrap();
+
+        assert!(matches!(ep.poll_event(), Some(Event::Quit { .. })));
+        assert!(ep.poll_event().is_none());
+    }
+
+    // Dropping the guard removes the filter again.
+    ev.push_event(motion.clone()).unwrap();
+    assert!(matches!(ep.poll_event(), Some(Event::MouseMotion { .. })));
+
+    // Replaced filters are not reinstated, even if their guard is dropped first.
+    let first = ev.set_event_filter(|_: &Event| false);
+    let second = ev.set_event_filter(|event: &Event| !event.is_mouse());
+    drop(first);
+    let _ = ev.push_event(motion.clone());
+    assert!(ep.poll_event().is_none());
+    drop(second);
+    ev.push_event(motion.clone()).unwrap();
+    assert!(matches!(ep.poll_event(), Some(Event::MouseMotion { .. })));
+
+    assert!(ev.is_event_enabled(EventType::MouseMotion));
+    ev.set_event_enabled(EventType::MouseMotion, false);
+    assert!(!ev.is_event_enabled(EventType::MouseMotion));
+    let _ = ev.push_event(motion.clone());
+    assert!(ep.poll_event().is_none());
+
+    ev.set_event_enabled(EventType::MouseMotion, true);
+    ev.push_event(motion).unwrap();
+    assert!(matches!(ep.poll_event(), Some(Event::MouseMotion { .. })));
+}
+
+#[cfg(feature = "async-events")]
+#[test]
+fn test_event_stream() {
+    use futures_core::Stream;
+    use sdl3::event::Event;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct Flag(AtomicBool);
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    struct Payload(u32);
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let ev = sdl.event().unwrap();
+    let mut ep = sdl.event_pump().unwrap();
+
+    let flag = Arc::new(Flag(AtomicBool::new(false)));
+    let waker = Waker::from(flag.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    let mut stream = ev.event_stream();
+    assert!(Pin::new(&mut stream).poll_next(&mut cx).is_pending());
+
+    // Events pushed from another thread wake the stream without any pumping.
+    let sender = ev.event_sender();
+    std::thread::spawn(move || sender.push_event(Event::Quit { timestamp: 0 }).unwrap())
+        .join()
+        .unwrap();
+    assert!(flag.0.load(Ordering::SeqCst));
+    match Pin::new(&mut stream).poll_next(&mut cx) {
+        Poll::Ready(Some(Event::Quit { .. })) => {}
+        other => panic!("Expected quit event, got {:?}", other),
+    }
+
+    let custom = ev.register_custom_event::<Payload>().unwrap();
+    let mut push = ev
+        .event_sender()
+        .push_custom_event_async(&custom, Payload(5));
+    match Pin::new(&mut push).poll(&mut cx) {
+        Poll::Ready(result) => result.unwrap(),
+        Poll::Pending => panic!("Pushing into an empty queue should not wait"),
+    }
+    let received = stream.try_next().unwrap();
+    assert_eq!(custom.take(&received).unwrap().0, 5);
+
+    // The pump hook drains the SDL queue once the stream has seen everything.
+    ep.pump_event_streams();
+    assert!(ep.poll_event().is_none());
+    drop(stream);
+
+    // With the queue full the push waits, without waking itself, until an event is taken out.
+    while ev.push_event(Event::Quit { timestamp: 0 }).is_ok() {}
+    flag.0.store(false, Ordering::SeqCst);
+    let mut push = ev
+        .event_sender()
+        .push_custom_event_async(&custom, Payload(6));
+    assert!(Pin::new(&mut push).poll(&mut cx).is_pending());
+    assert!(!flag.0.load(Ordering::SeqCst));
+    assert!(ep.poll_event().is_some());
+    assert!(flag.0.load(Ordering::SeqCst));
+    match Pin::new(&mut push).poll(&mut cx) {
+        Poll::Ready(result) => result.unwrap(),
+        Poll::Pending => panic!("The push should fit after an event was taken out"),
+    }
+    ev.flush_events(0, u32::MAX);
+}
+
+#[test]
+fn test_custom_event_payloads() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct Tracked(Arc<AtomicUsize>);
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let ev = sdl.event().unwrap();
+    let mut ep = sdl.event_pump().unwrap();
+    while ep.poll_event().is_some() {}
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let custom = ev.register_custom_event::<Tracked>().unwrap();
+    let other = ev.register_custom_event::<Tracked>().unwrap();
+
+    // Flushed payloads are dropped.
+    ev.push_custom_event(&custom, Tracked(drops.clone()))
+        .unwrap();
+    ev.push_custom_event(&other, Tracked(drops.clone()))
+        .unwrap();
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    ev.flush_events(custom.event_type(), other.event_type());
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+    // Received but never taken payloads are dropped with the event.
+    ev.push_custom_event(&other, Tracked(drops.clone()))
+        .unwrap();
+    let received = ep.poll_event().unwrap();
+    assert!(custom.take(&received).is_none());
+    drop(received);
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+
+    // Codes tell events of the same type apart.
+    let loaded = custom.with_code(1);
+    let saved = custom.with_code(2);
+    ev.push_custom_event(&saved, Tracked(drops.clone()))
+        .unwrap();
+    let received = ep.poll_event().unwrap();
+    assert!(!loaded.matches(&received));
+    assert_eq!(custom.code_of(&received), Some(2));
+    assert!(loaded.take(&received).is_none());
+    let payload = saved.take(&received).unwrap();
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+    drop(payload);
+    assert_eq!(drops.load(Ordering::SeqCst), 4);
+
+    // Queued events rejected by a new filter drop their payloads, as do rejected pushes.
+    ev.push_custom_event(&custom, Tracked(drops.clone()))
+        .unwrap();
+    let filter = ev.set_event_filter(|event: &sdl3::event::Event| !event.is_user_event());
+    assert_eq!(drops.load(Ordering::SeqCst), 5);
+    assert!(ev
+        .push_custom_event(&custom, Tracked(drops.clone()))
+        .is_err());
+    assert_eq!(drops.load(Ordering::SeqCst), 6);
+    drop(filter);
+
+    // The deprecated accessor still takes payloads out of received events.
+    ev.push_custom_event(&custom, Tracked(drops.clone()))
+        .unwrap();
+    let received = ep.wait_event_timeout(0).unwrap();
+    #[allow(deprecated)]
+    let payload = received.as_user_event_type::<Tracked>().unwrap();
+    assert!(custom.take(&received).is_none());
+    drop(payload);
+    assert_eq!(drops.load(Ordering::SeqCst), 7);
+}
+
+#[test]
+fn test_push_drop_events() {
+    use sdl3::event::{DropSession, Event};
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let ev = sdl.event().unwrap();
+    let mut ep = sdl.event_pump().unwrap();
+    while ep.poll_event().is_some() {}
+
+    let mut session = DropSession::new();
+    ev.push_event(Event::DropBegin {
+        timestamp: 0,
+        window_id: 5,
+    })
+    .unwrap();
+    ev.push_event(Event::DropPosition {
+        timestamp: 0,
+        window_id: 5,
+        x: 30.0,
+        y: 40.0,
+        source: None,
+    })
+    .unwrap();
+    ev.push_event(Event::DropComplete {
+        timestamp: 0,
+        window_id: 5,
+    })
+    .unwrap();
+
+    let mut completed = None;
+    while let Some(event) = ep.poll_event() {
+        assert!(event.is_drop());
+        if let Event::DropPosition { x, y, .. } = event {
+            assert_eq!((x, y), (30.0, 40.0));
+        }
+        if let Some(items) = session.handle_event(&event) {
+            completed = Some(items);
+        } else {
+            assert!(session.is_active(5));
+        }
+    }
+    assert_eq!(completed, Some(Vec::new()));
+}
+
+#[test]
+fn test_push_system_events() {
+    use sdl3::event::Event;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let ev = sdl.event().unwrap();
+    let mut ep = sdl.event_pump().unwrap();
+    while ep.poll_event().is_some() {}
+
+    let system_events = [
+        Event::LocaleChanged { timestamp: 0 },
+        Event::SystemThemeChanged { timestamp: 0 },
+        Event::KeymapChanged { timestamp: 0 },
+        Event::ClipboardUpdate {
+            timestamp: 0,
+            owner: false,
+            mime_types: Vec::new(),
+        },
+    ];
+
+    for system_event in system_events {
+        ev.push_event(system_event.clone()).unwrap();
+
+        let received = ep.poll_event().unwrap();
+        assert!(received.is_same_kind_as(&system_event));
+        if let Event::ClipboardUpdate {
+            owner, mime_types, ..
+        } = received
+        {
+            assert!(!owner);
+            assert!(mime_types.is_empty());
+        }
+    }
+
+    // MIME type strings can't be handed to SDL's queue.
+    assert!(ev
+        .push_event(Event::ClipboardUpdate {
+            timestamp: 0,
+            owner: true,
+            mime_types: vec!["text/plain".to_owned()],
+        })
+        .is_err());
+}
+
+#[test]
+fn test_record_and_replay() {
+    use sdl3::event::record::{EventPlayer, EventRecorder};
+    use sdl3::event::{Event, WindowEvent};
+    use sdl3::keyboard::{Keycode, Mod, Scancode};
+
+    let _lock = CONTEXT_MUTEX.lock();
+    sdl3::hint::set("SDL_VIDEO_DRIVER", "dummy");
+    let sdl = sdl3::init().unwrap();
+    let _video = sdl.video().unwrap();
+    let ev = sdl.event().unwrap();
+    let mut ep = sdl.event_pump().unwrap();
+    while ep.poll_event().is_some() {}
+
+    let session = [
+        Event::Window {
+            timestamp: 1_000,
+            window_id: 1,
+            win_event: WindowEvent::FocusGained,
+        },
+        Event::KeyDown {
+            timestamp: 2_000,
+            window_id: 1,
+            keycode: Some(Keycode::Space),
+            scancode: Some(Scancode::Space),
+            keymod: Mod::empty(),
+            repeat: false,
+            which: 1,
+            raw: 0,
+        },
+        Event::KeyUp {
+            timestamp: 3_000,
+            window_id: 1,
+            keycode: Some(Keycode::Space),
+            scancode: Some(Scancode::Space),
+            keymod: Mod::empty(),
+            repeat: false,
+            which: 1,
+            raw: 0,
+        },
+    ];
+
+    let mut recorder = EventRecorder::new(Vec::new()).unwrap();
+    for event in &session {
+        assert!(recorder.record(event).unwrap());
+    }
+    let recording = recorder.finish().unwrap();
+
+    let mut player = EventPlayer::new(recording.as_slice()).unwrap();
+    player.set_speed(f64::INFINITY);
+    assert_eq!(player.play(&ev).unwrap(), session.len());
+    assert!(player.is_finished());
+
+    for expected in &session {
+        let event = ep.poll_event().unwrap();
+        assert!(event.is_same_kind_as(expected));
+        assert_eq!(event.get_window_id(), expected.get_window_id());
+    }
+}
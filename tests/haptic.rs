@@ -0,0 +1,81 @@
+extern crate sdl3;
+#[macro_use]
+extern crate lazy_static;
+
+use sdl3::haptic::{
+    ConstantEffect, Haptic, HapticEffect, HapticFeatures, LeftRightEffect, PeriodicEffect, Waveform,
+};
+use std::sync::Mutex;
+
+// Only one `Sdl` context can exist at a time, so tests that initialize it run serially.
+lazy_static! {
+    static ref CONTEXT_MUTEX: Mutex<()> = Mutex::new(());
+}
+
+/// Uploads, plays, updates and removes the first effect the device supports.
+fn exercise_effects(haptic: &mut Haptic) {
+    let features = haptic.features();
+    let effects: [(HapticFeatures, HapticEffect, HapticEffect); 3] = [
+        (
+            HapticFeatures::LEFT_RIGHT,
+            LeftRightEffect::new(0x4000, 0x4000, 50).into(),
+            LeftRightEffect::new(0x2000, 0x2000, 50).into(),
+        ),
+        (
+            HapticFeatures::SINE,
+            PeriodicEffect::new(Waveform::Sine, 20, 8_000, 50).into(),
+            PeriodicEffect::new(Waveform::Sine, 40, 4_000, 50).into(),
+        ),
+        (
+            HapticFeatures::CONSTANT,
+            ConstantEffect::new(8_000, 50).into(),
+            ConstantEffect::new(4_000, 50).into(),
+        ),
+    ];
+    let (effect, update) = match effects
+        .into_iter()
+        .find(|(feature, _, _)| features.contains(*feature))
+    {
+        Some((_, effect, update)) => (effect, update),
+        None => return,
+    };
+
+    assert!(haptic.is_effect_supported(&effect));
+    let id = haptic.new_effect(&effect).unwrap();
+    haptic.run_effect(id, 1).unwrap();
+    if features.contains(HapticFeatures::STATUS) {
+        // Only checks the query goes through; the effect may already be over.
+        let _ = haptic.effect_status(id);
+    } else {
+        assert!(!haptic.effect_status(id));
+    }
+    haptic.update_effect(id, &update).unwrap();
+    haptic.stop_effect(id).unwrap();
+    haptic.destroy_effect(id);
+}
+
+#[test]
+fn haptic_devices() {
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let haptic_subsystem = match sdl.haptic() {
+        Ok(haptic_subsystem) => haptic_subsystem,
+        Err(_) => return,
+    };
+
+    // Skips when no haptic device is connected.
+    for instance in haptic_subsystem.haptics().unwrap() {
+        let mut haptic = haptic_subsystem.open(instance.id).unwrap();
+        assert_eq!(haptic.id(), instance.id);
+        assert_eq!(haptic.name(), instance.name);
+        assert!(haptic.max_effects().unwrap() >= haptic.max_effects_playing().unwrap());
+        exercise_effects(&mut haptic);
+    }
+
+    if haptic_subsystem.is_mouse_haptic() {
+        let mut haptic = haptic_subsystem.open_from_mouse().unwrap();
+        exercise_effects(&mut haptic);
+    } else {
+        assert!(haptic_subsystem.open_from_mouse().is_err());
+    }
+}
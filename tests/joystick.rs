@@ -0,0 +1,217 @@
+extern crate sdl3;
+#[macro_use]
+extern crate lazy_static;
+
+use sdl3::event::Event;
+use sdl3::gamepad::{Axis, BindingOutput, Button, ButtonLabel, GamepadType};
+use sdl3::joystick::{HatState, JoystickType, VirtualJoystickDesc};
+use std::sync::{Arc, Mutex};
+
+// Only one `Sdl` context can exist at a time, so tests that initialize it run serially.
+lazy_static! {
+    static ref CONTEXT_MUTEX: Mutex<()> = Mutex::new(());
+}
+
+#[test]
+fn virtual_joystick_inputs() {
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let joystick_subsystem = sdl.joystick().unwrap();
+    let mut event_pump = sdl.event_pump().unwrap();
+
+    let mut device = joystick_subsystem
+        .attach_virtual(VirtualJoystickDesc {
+            name: Some("Virtual stick".to_owned()),
+            naxes: 2,
+            nbuttons: 4,
+            nhats: 1,
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(joystick_subsystem.is_virtual(device.instance_id()));
+
+    let instance = joystick_subsystem
+        .joysticks()
+        .unwrap()
+        .into_iter()
+        .find(|instance| instance.id == device.instance_id())
+        .unwrap();
+    let joystick = joystick_subsystem.open(instance).unwrap();
+    assert_eq!(joystick.name(), "Virtual stick");
+    assert_eq!(joystick.num_axes(), 2);
+
+    device.set_axis(1, -12_000).unwrap();
+    device.set_button(3, true).unwrap();
+    device.set_hat(0, HatState::LeftUp).unwrap();
+    assert!(device.set_axis(5, 0).is_err());
+    joystick_subsystem.update();
+
+    assert_eq!(joystick.axis(1).unwrap(), -12_000);
+    assert!(joystick.button(3).unwrap());
+    assert_eq!(joystick.hat(0).unwrap(), HatState::LeftUp);
+
+    let mut saw_button = false;
+    for event in event_pump.poll_iter() {
+        if let Event::JoyButtonDown {
+            which, button_idx, ..
+        } = event
+        {
+            assert_eq!(which, device.instance_id());
+            assert_eq!(button_idx, 3);
+            saw_button = true;
+        }
+    }
+    assert!(saw_button);
+}
+
+#[test]
+fn virtual_gamepad_callbacks() {
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let joystick_subsystem = sdl.joystick().unwrap();
+    let gamepad_subsystem = sdl.gamepad().unwrap();
+
+    let rumbles = Arc::new(Mutex::new(Vec::new()));
+    let leds = Arc::new(Mutex::new(Vec::new()));
+    let rumble_log = Arc::clone(&rumbles);
+    let led_log = Arc::clone(&leds);
+    let mut device = joystick_subsystem
+        .attach_virtual(VirtualJoystickDesc {
+            joystick_type: JoystickType::Gamepad,
+            naxes: 6,
+            nbuttons: 15,
+            rumble: Some(Box::new(move |low, high| {
+                rumble_log.lock().unwrap().push((low, high));
+                true
+            })),
+            set_led: Some(Box::new(move |red, green, blue| {
+                led_log.lock().unwrap().push((red, green, blue));
+                true
+            })),
+            ..Default::default()
+        })
+        .unwrap();
+
+    let mut gamepad = gamepad_subsystem.open(device.instance_id()).unwrap();
+    device.set_button(Button::South as u32, true).unwrap();
+    device.set_axis(Axis::LeftX as u32, 20_000).unwrap();
+    gamepad_subsystem.update();
+
+    assert!(gamepad.button(Button::South));
+    assert!(gamepad
+        .bindings()
+        .unwrap()
+        .iter()
+        .any(|binding| binding.output == BindingOutput::Button(Button::South)));
+    assert_eq!(gamepad.axis(Axis::LeftX), 20_000);
+
+    gamepad.set_rumble(100, 200, 50).unwrap();
+    gamepad.set_led(1, 2, 3).unwrap();
+    assert!(rumbles.lock().unwrap().contains(&(100, 200)));
+    assert_eq!(leds.lock().unwrap().as_slice(), &[(1, 2, 3)]);
+
+    // Detaching disconnects the gamepad.
+    drop(device);
+    gamepad_subsystem.update();
+    assert!(!gamepad.attached());
+}
+
+#[test]
+fn virtual_gamepad_touchpad_and_metadata() {
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let joystick_subsystem = sdl.joystick().unwrap();
+    let gamepad_subsystem = sdl.gamepad().unwrap();
+
+    let mut device = joystick_subsystem
+        .attach_virtual(VirtualJoystickDesc {
+            joystick_type: JoystickType::Gamepad,
+            naxes: 6,
+            nbuttons: 15,
+            touchpads: vec![2],
+            ..Default::default()
+        })
+        .unwrap();
+
+    let mut gamepad = gamepad_subsystem.open(device.instance_id()).unwrap();
+    device.set_touchpad(0, 1, true, 0.25, 0.75, 1.0).unwrap();
+    gamepad_subsystem.update();
+
+    assert_eq!(gamepad.num_touchpads(), 1);
+    assert_eq!(gamepad.num_touchpad_fingers(0).unwrap(), 2);
+    let finger = gamepad.touchpad_finger(0, 1).unwrap();
+    assert!(finger.down);
+    assert_eq!((finger.x, finger.y), (0.25, 0.75));
+    assert!(gamepad.touchpad_finger(1, 0).is_err());
+
+    gamepad.set_player_index(Some(2)).unwrap();
+    assert_eq!(gamepad.player_index(), Some(2));
+    gamepad.set_player_index(None).unwrap();
+    assert_eq!(gamepad.player_index(), None);
+    assert_eq!(
+        GamepadType::PS5.button_label(Button::South),
+        ButtonLabel::Cross
+    );
+
+    // Virtual devices aren't driven by Steam Input.
+    assert_eq!(gamepad.steam_handle(), 0);
+}
+
+#[test]
+fn virtual_gamepad_sensors() {
+    use sdl3::sys;
+    use sys::sensor::SDL_SENSOR_ACCEL;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl = sdl3::init().unwrap();
+    let joystick_subsystem = sdl.joystick().unwrap();
+    let gamepad_subsystem = sdl.gamepad().unwrap();
+
+    let enabled = Arc::new(Mutex::new(Vec::new()));
+    let enabled_log = Arc::clone(&enabled);
+    let mut device = joystick_subsystem
+        .attach_virtual(VirtualJoystickDesc {
+            joystick_type: JoystickType::Gamepad,
+            naxes: 6,
+            nbuttons: 15,
+            sensors: vec![(SDL_SENSOR_ACCEL, 60.0)],
+            set_sensors_enabled: Some(Box::new(move |on| {
+                enabled_log.lock().unwrap().push(on);
+                true
+            })),
+            ..Default::default()
+        })
+        .unwrap();
+
+    // The gamepad sensor API needs `hidapi`, so drive it through the raw bindings.
+    let gamepad = unsafe { sys::gamepad::SDL_OpenGamepad(device.instance_id()) };
+    assert!(!gamepad.is_null());
+    unsafe {
+        assert!(sys::gamepad::SDL_GamepadHasSensor(
+            gamepad,
+            SDL_SENSOR_ACCEL
+        ));
+        assert!(sys::gamepad::SDL_SetGamepadSensorEnabled(
+            gamepad,
+            SDL_SENSOR_ACCEL,
+            true
+        ));
+    }
+    assert_eq!(enabled.lock().unwrap().as_slice(), &[true]);
+
+    device
+        .send_sensor_data(SDL_SENSOR_ACCEL, 0, &[1.0, -9.8, 0.5])
+        .unwrap();
+    gamepad_subsystem.update();
+    let mut data = [0.0f32; 3];
+    unsafe {
+        assert!(sys::gamepad::SDL_GetGamepadSensorData(
+            gamepad,
+            SDL_SENSOR_ACCEL,
+            data.as_mut_ptr(),
+            3
+        ));
+        sys::gamepad::SDL_CloseGamepad(gamepad);
+    }
+    assert_eq!(data, [1.0, -9.8, 0.5]);
+}
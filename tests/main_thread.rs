@@ -0,0 +1,35 @@
+//! SDL records the first thread that initializes it as the main thread, once per process.
+//! This runs without the test harness so that thread is the process' own main thread,
+//! whatever other tests do.
+
+extern crate sdl3;
+
+use sdl3::main_thread;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn main() {
+    let sdl = sdl3::init().unwrap();
+    let mut ep = sdl.event_pump().unwrap();
+    assert!(main_thread::is_main_thread());
+
+    // On the main thread the closure runs right away.
+    let ran = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&ran);
+    main_thread::run(move || flag.store(true, Ordering::SeqCst), false).unwrap();
+    assert!(ran.load(Ordering::SeqCst));
+
+    // Other threads wait until the main thread pumps events.
+    let worker = std::thread::spawn(|| {
+        assert!(!main_thread::is_main_thread());
+        main_thread::run_sync(|| (main_thread::is_main_thread(), 42)).unwrap()
+    });
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !worker.is_finished() {
+        assert!(Instant::now() < deadline, "run_sync never ran");
+        ep.pump_events();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(worker.join().unwrap(), (true, 42));
+}
@@ -1,7 +1,17 @@
 extern crate sdl3;
+#[macro_use]
+extern crate lazy_static;
+
+use std::sync::Mutex;
+
+// Only one `Sdl` context can be alive at a time, so run these tests serially.
+lazy_static! {
+    static ref CONTEXT_MUTEX: Mutex<()> = Mutex::new(());
+}
 
 #[test]
 fn display_name_no_segfault() {
+    let _lock = CONTEXT_MUTEX.lock();
     let sdl_context = sdl3::init().unwrap();
     let video_subsystem = sdl_context.video();
     if let Ok(video_subsystem) = video_subsystem {
@@ -11,3 +21,176 @@ fn display_name_no_segfault() {
     } // in Err(), environment has no video device (for instance travis)
       // so ignore it
 }
+
+#[test]
+fn window_flags_round_trip() {
+    use sdl3::video::{FullscreenType, WindowFlags};
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl_context = sdl3::init().unwrap();
+    if let Ok(video_subsystem) = sdl_context.video() {
+        let mut builder = video_subsystem.window("flags", 64, 64);
+        builder.hidden().resizable();
+        assert_eq!(
+            builder.window_flags(),
+            WindowFlags::HIDDEN | WindowFlags::RESIZABLE
+        );
+
+        let window = match builder.build() {
+            Ok(window) => window,
+            Err(_) => return,
+        };
+        let flags = window.window_flags();
+        assert!(flags.contains(WindowFlags::HIDDEN));
+        assert!(flags.contains(WindowFlags::RESIZABLE));
+
+        let state = window.state();
+        assert_eq!(state.flags, flags);
+        assert!(state.hidden);
+        assert_eq!(state.fullscreen, FullscreenType::Off);
+    }
+}
+
+/// Selects a video driver for one test, going back to the default once dropped.
+struct VideoDriverHint;
+
+impl VideoDriverHint {
+    fn set(driver: &str) -> VideoDriverHint {
+        sdl3::hint::set("SDL_VIDEO_DRIVER", driver);
+        VideoDriverHint
+    }
+}
+
+impl Drop for VideoDriverHint {
+    fn drop(&mut self) {
+        sdl3::hint::set("SDL_VIDEO_DRIVER", "");
+    }
+}
+
+#[test]
+fn gl_attr_reset() {
+    use sdl3::video::GLProfile;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let _driver = VideoDriverHint::set("dummy");
+    let sdl_context = sdl3::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    assert_eq!(video_subsystem.current_video_driver(), "dummy");
+
+    // Without a context only the attributes SDL stores itself can be read back.
+    let gl_attr = video_subsystem.gl_attr();
+    gl_attr.set_context_version(4, 1);
+    gl_attr.set_context_profile(GLProfile::Core);
+    gl_attr.set_context_flags().debug().set();
+    assert_eq!(gl_attr.context_version(), (4, 1));
+    assert_eq!(gl_attr.context_profile(), GLProfile::Core);
+    assert!(gl_attr.context_flags().has_debug());
+
+    gl_attr.reset();
+    assert_ne!(gl_attr.context_version(), (4, 1));
+    assert_ne!(gl_attr.context_profile(), GLProfile::Core);
+    assert!(!gl_attr.context_flags().has_debug());
+}
+
+#[test]
+fn egl_offscreen() {
+    use sdl3::video::egl::{EGLAttributeCallbacks, EGL_NONE};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let _driver = VideoDriverHint::set("offscreen");
+    let sdl_context = sdl3::init().unwrap();
+    // The offscreen driver needs EGL; skip where it is unavailable.
+    let video_subsystem = match sdl_context.video() {
+        Ok(video_subsystem) => video_subsystem,
+        Err(_) => return,
+    };
+    if video_subsystem.gl_load_library_default().is_err() {
+        return;
+    }
+
+    let context_called = Arc::new(AtomicBool::new(false));
+    let called = Arc::clone(&context_called);
+    let mut callbacks = EGLAttributeCallbacks::new();
+    callbacks.context(move |_display, _config| {
+        called.store(true, Ordering::SeqCst);
+        vec![EGL_NONE]
+    });
+    video_subsystem.egl_set_attribute_callbacks(callbacks);
+
+    let window = video_subsystem
+        .window("egl", 64, 64)
+        .opengl()
+        .hidden()
+        .build()
+        .unwrap();
+    let _context = match window.gl_create_context() {
+        Ok(context) => context,
+        Err(_) => return,
+    };
+    assert!(context_called.load(Ordering::SeqCst));
+
+    assert!(!video_subsystem.egl_get_current_display().unwrap().is_null());
+    assert!(!video_subsystem.egl_get_current_config().unwrap().is_null());
+    assert!(!window.egl_window_surface().unwrap().is_null());
+    assert!(video_subsystem
+        .egl_get_proc_address("eglGetError")
+        .is_some());
+    assert!(video_subsystem.egl_get_proc_address("egl\0bad").is_none());
+
+    video_subsystem.gl_attr().reset();
+}
+
+#[cfg(feature = "ash")]
+#[test]
+fn vulkan_ash_surface() {
+    use sdl3::ash::{self, vk};
+
+    let _lock = CONTEXT_MUTEX.lock();
+    let sdl_context = sdl3::init().unwrap();
+    // Skip where there is no video device or Vulkan driver.
+    let video_subsystem = match sdl_context.video() {
+        Ok(video_subsystem) => video_subsystem,
+        Err(_) => return,
+    };
+    if video_subsystem.vulkan_load_library_default().is_err() {
+        return;
+    }
+    let window = match video_subsystem
+        .window("vulkan", 64, 64)
+        .vulkan()
+        .hidden()
+        .build()
+    {
+        Ok(window) => window,
+        Err(_) => return,
+    };
+
+    let get_instance_proc_addr = video_subsystem.vulkan_get_proc_address_function();
+    assert!(get_instance_proc_addr.is_some());
+    let entry = unsafe {
+        ash::Entry::from_static_fn(ash::StaticFn {
+            get_instance_proc_addr: std::mem::transmute(get_instance_proc_addr),
+        })
+    };
+    let extensions = window.vulkan_instance_extension_ptrs().unwrap();
+    let create_info = vk::InstanceCreateInfo::default().enabled_extension_names(extensions);
+    let instance = match unsafe { entry.create_instance(&create_info, None) } {
+        Ok(instance) => instance,
+        Err(_) => return,
+    };
+
+    let surface = window.vulkan_create_surface(&instance, None).unwrap();
+    assert_ne!(surface, vk::SurfaceKHR::null());
+    for physical_device in unsafe { instance.enumerate_physical_devices() }.unwrap() {
+        // Only checks the call goes through; support depends on the device.
+        let _ =
+            unsafe { video_subsystem.vulkan_presentation_support(&instance, physical_device, 0) };
+    }
+
+    unsafe {
+        window.vulkan_destroy_surface(&instance, surface, None);
+        instance.destroy_instance(None);
+    }
+}